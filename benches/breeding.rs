@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use ga::{GeneticAlgorithm, GeneticAlgorithmBuilder, NoCallback};
+
+fn new_run(batch: bool) -> GeneticAlgorithm<NoCallback> {
+    GeneticAlgorithmBuilder::new()
+        .target("the quick brown fox jumps over the lazy dog")
+        .population_size(1000)
+        .num_fit_to_keep(100)
+        .seed(0)
+        .batch(batch)
+        .build()
+        .unwrap()
+}
+
+fn bench_breeding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generation_1000");
+    group.sample_size(10);
+    for (name, batch) in [("one_per_step", false), ("batch", true)] {
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || new_run(batch),
+                |genetic_algorithm| genetic_algorithm.by_generation().nth(5),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_breeding);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use ga::{compute_fitness, compute_fitness_batch, Candidate, FitnessMode, StringContext, LETTERS};
+
+const POPULATION_SIZE: usize = 10_000;
+const TARGET_LEN: usize = 200;
+
+fn random_text(len: usize, rng: &mut StdRng) -> String {
+    (0..len)
+        .map(|_| LETTERS.chars().choose(rng).unwrap())
+        .collect()
+}
+
+// Run with `--features parallel` to compare the batch path against rayon.
+fn bench_fitness(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut context = StringContext::new(random_text(TARGET_LEN, &mut rng));
+    context.fitness_mode = FitnessMode::Levenshtein;
+    let population: Vec<Candidate> = (0..POPULATION_SIZE)
+        .map(|_| Candidate::new(random_text(TARGET_LEN, &mut rng)))
+        .collect();
+
+    let mut group = c.benchmark_group("fitness_10000");
+    group.sample_size(10);
+    group.bench_function("one_per_step", |b| {
+        b.iter_batched_ref(
+            || population.clone(),
+            |population| while compute_fitness(population, &context) {},
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("batch", |b| {
+        b.iter_batched_ref(
+            || population.clone(),
+            |population| compute_fitness_batch(population, &context),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_fitness);
+criterion_main!(benches);
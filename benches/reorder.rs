@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use ga::{
+    reorder_by_fitness, reorder_by_fitness_fast, Candidate, Objective, StringContext, LETTERS,
+};
+
+const POPULATION_SIZE: usize = 5000;
+const TARGET_STR: &str = "hello world";
+
+fn random_population(rng: &mut StdRng) -> Vec<Candidate> {
+    let context = StringContext::new(TARGET_STR);
+    (0..POPULATION_SIZE)
+        .map(|_| {
+            let text = (0..TARGET_STR.len())
+                .map(|_| LETTERS.chars().choose(rng).unwrap())
+                .collect();
+            let mut candidate = Candidate::new(text);
+            candidate.set_fitness(&context);
+            candidate
+        })
+        .collect()
+}
+
+fn bench_reorder(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let population = random_population(&mut rng);
+
+    let mut group = c.benchmark_group("reorder_5000");
+    group.sample_size(10);
+    group.bench_function("bubble", |b| {
+        b.iter_batched_ref(
+            || population.clone(),
+            |population| reorder_by_fitness(population, Objective::Maximize),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("sort_by", |b| {
+        b.iter_batched_ref(
+            || population.clone(),
+            |population| reorder_by_fitness_fast(population, Objective::Maximize),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_reorder);
+criterion_main!(benches);
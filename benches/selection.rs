@@ -0,0 +1,68 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use ga::{GeneticAlgorithmBuilder, SelectionStrategy};
+
+const SEEDS: u64 = 20;
+const MAX_GENERATIONS: usize = 2000;
+
+const STRATEGIES: [SelectionStrategy; 4] = [
+    SelectionStrategy::RandomPair,
+    SelectionStrategy::Tournament { k: 3 },
+    SelectionStrategy::RouletteWheel,
+    SelectionStrategy::Rank { pressure: 1.5 },
+];
+
+fn builder(selection: SelectionStrategy, seed: u64) -> GeneticAlgorithmBuilder {
+    GeneticAlgorithmBuilder::new()
+        .selection(selection)
+        .max_generations(MAX_GENERATIONS)
+        .batch(true)
+        .seed(seed)
+}
+
+/// Prints the mean number of generations each strategy needs on the default target, with
+/// the number of runs that hit the generation cap, before timing them.
+fn print_generations_table() {
+    println!();
+    println!(
+        "{:<16} {:>16} {:>8}",
+        "selection", "mean generations", "unsolved"
+    );
+    for selection in STRATEGIES {
+        let results: Vec<_> = (0..SEEDS)
+            .map(|seed| builder(selection, seed).build().unwrap().solve())
+            .collect();
+        let mean = results.iter().map(|r| r.generations).sum::<usize>() as f64 / SEEDS as f64;
+        let unsolved = results.iter().filter(|r| !r.is_solved()).count();
+        println!(
+            "{:<16} {:>16.1} {:>8}",
+            selection.to_string(),
+            mean,
+            unsolved
+        );
+    }
+    println!();
+}
+
+fn bench_selection(c: &mut Criterion) {
+    print_generations_table();
+    let mut group = c.benchmark_group("solve_hello_world");
+    group.sample_size(10);
+    for selection in STRATEGIES {
+        group.bench_function(selection.to_string(), |b| {
+            let mut seed = 0;
+            b.iter_batched_ref(
+                || {
+                    seed = (seed + 1) % SEEDS;
+                    builder(selection, seed).build().unwrap()
+                },
+                |genetic_algorithm| genetic_algorithm.solve(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_selection);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Runs four islands, each its own population in its own thread, that swap their best
+//! candidates every few generations, and compares them with a single population of the
+//! same total size.
+//!
+//! ```text
+//! cargo run --release --example islands
+//! ```
+
+use ga::{GeneticAlgorithmBuilder, IslandModel, Outcome};
+
+const TARGET: &str = "the quick brown fox jumps over the lazy dog";
+/// Per character, so a long target needs a lower rate than the default to keep children
+/// close to their parents.
+const MUTATION_PROB: f64 = 0.02;
+
+fn main() {
+    let single = GeneticAlgorithmBuilder::new()
+        .target(TARGET)
+        .mutation_prob(MUTATION_PROB)
+        .population_size(4 * 48)
+        .num_fit_to_keep(4 * 5)
+        .fast_sort(true)
+        .batch(true)
+        .seed(1)
+        .build()
+        .unwrap()
+        .solve();
+    println!(
+        "one population of {}: {:?} after {} generations",
+        4 * 48,
+        single.outcome,
+        single.generations
+    );
+
+    let model = IslandModel::new(4, 1).with_migration(5, 2);
+    let results = model.run(|seed| {
+        GeneticAlgorithmBuilder::new()
+            .target(TARGET)
+            .mutation_prob(MUTATION_PROB)
+            .fast_sort(true)
+            .batch(true)
+            .seed(seed)
+            .build()
+            .unwrap()
+    });
+    for (island, result) in results.iter().enumerate() {
+        println!(
+            "island {} (seed {}): {:?} after {} generations, best \"{}\"",
+            island,
+            model.island_seed(island),
+            result.outcome,
+            result.generations,
+            result.best.genome
+        );
+    }
+    let winner = results.iter().position(|r| r.outcome == Outcome::Reached);
+    println!("first to reach the target: island {:?}", winner);
+}
@@ -0,0 +1,62 @@
+//! Cracks a Mastermind code: the genes are peg colors and a guess is scored the way the
+//! codemaker answers it, two points for every peg of the right color in the right place
+//! and one for every other peg of a right color, instead of matching characters.
+//!
+//! ```text
+//! cargo run --example mastermind
+//! ```
+
+use ga::{GeneticAlgorithmBuilder, SelectionStrategy};
+
+/// Red, green, blue, yellow, orange and purple.
+const COLORS: &str = "RGBYOP";
+const SECRET: &str = "GRYBOPYR";
+
+/// Pegs of the right color in the right place, and pegs of a right color elsewhere.
+fn feedback(guess: &str, secret: &str) -> (usize, usize) {
+    let exact = guess
+        .chars()
+        .zip(secret.chars())
+        .filter(|(a, b)| a == b)
+        .count();
+    // Each color matches as often as it occurs in both, wherever it is.
+    let colors = COLORS
+        .chars()
+        .map(|color| {
+            let count = |code: &str| code.chars().filter(|&c| c == color).count();
+            count(guess).min(count(secret))
+        })
+        .sum::<usize>();
+    (exact, colors - exact)
+}
+
+fn score(guess: &str, secret: &str) -> isize {
+    let (exact, misplaced) = feedback(guess, secret);
+    (2 * exact + misplaced) as isize
+}
+
+fn main() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target(SECRET)
+        .alphabet(COLORS)
+        .fitness_fn(score)
+        .population_size(30)
+        .num_fit_to_keep(6)
+        .mutation_prob(0.1)
+        .selection(SelectionStrategy::Tournament { k: 3 })
+        .seed(42)
+        .build()
+        .unwrap()
+        .with_on_improvement(|best, generation| {
+            let (exact, misplaced) = feedback(&best.genome, SECRET);
+            println!(
+                "generation {:>3}: {}  {} exact, {} misplaced",
+                generation, best.genome, exact, misplaced
+            );
+        });
+    let result = genetic_algorithm.solve();
+    println!(
+        "cracked {} in {} generations ({:?})",
+        result.best.genome, result.generations, result.outcome
+    );
+}
@@ -0,0 +1,39 @@
+//! Evolves three 16 bit numbers toward a target, once with Gray code and once with plain
+//! binary, using the same driver as the string demo.
+//!
+//! ```text
+//! cargo run --example numbers
+//! ```
+
+use std::ops::ControlFlow;
+
+use ga::{Candidate, GeneticAlgorithm, IntContext, IntGenome, Objective, SelectionStrategy};
+
+const TARGET: [u32; 3] = [1234, 42, 60000];
+
+fn main() {
+    for gray_code in [true, false] {
+        let context = IntContext::new(TARGET, 16).with_gray_code(gray_code);
+        let mut genetic_algorithm: GeneticAlgorithm<_, IntGenome> = GeneticAlgorithm::new(
+            context,
+            10,
+            100,
+            0.02,
+            Some(7),
+            |_: &Vec<Candidate<IntGenome>>, _: &str, _: &[usize]| ControlFlow::Continue(()),
+        )
+        .with_objective(Objective::Minimize)
+        .with_selection(SelectionStrategy::Tournament { k: 3 })
+        .with_batch(true)
+        .with_max_generations(2000);
+        let result = genetic_algorithm.solve();
+        println!(
+            "{}: {:?} after {} generations, off by {} ({:?})",
+            if gray_code { "gray code" } else { "binary" },
+            result.best.genome.0,
+            result.generations,
+            result.best.fitness.unwrap(),
+            result.outcome
+        );
+    }
+}
@@ -0,0 +1,1214 @@
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
+
+#[cfg(feature = "alloc-counts")]
+use crate::alloc_counts::AllocCounts;
+use crate::cache::{compute_fitness_batch_cached, compute_fitness_cached, FitnessCache};
+use crate::candidate::{breed_at, Candidate};
+#[cfg(feature = "serde")]
+use crate::checkpoint::Checkpoint;
+use crate::crossover::CrossoverMode;
+use crate::diversity::{
+    apply_fitness_sharing, population_diversity, promote_diverse, promote_young, CullStrategy,
+    FitnessSharing, RestartPolicy,
+};
+use crate::genome::{Crossover, Distance, Fitness, Genome, Score};
+use crate::hall_of_fame::admit_to_hall_of_fame;
+use crate::lineage::Lineage;
+use crate::mutation::{AdaptiveMutation, MutationSchedule};
+use crate::objective::{Key, Objective};
+use crate::replacement::{replace_worst, ReplacementMode};
+use crate::selection::{breeding_parents, SelectionStrategy};
+use crate::stats::{is_plateau, GenerationStats};
+use crate::tie_break::{fitness_order_fast_with, fitness_order_with, TieBreak};
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum STATE {
+    Init,
+    ComputeFitness,
+    Reorder,
+    RemoveUnfit,
+    BreedNew,
+    /// Steady-state replacement, with the number of children tried this generation.
+    Replace(usize),
+}
+
+impl STATE {
+    pub fn description(&self) -> &'static str {
+        match *self {
+            STATE::Init => "Seeding the population",
+            STATE::ComputeFitness => "Computing fitness",
+            STATE::Reorder => "Sorting by fitness",
+            STATE::RemoveUnfit => "Removing unfit candidates",
+            STATE::BreedNew => "Breeding new candidates",
+            STATE::Replace(_) => "Replacing the worst candidate",
+        }
+    }
+
+    /// A short name for the part of a generation this state belongs to, used to group
+    /// timings: `seeding`, `fitness`, `sorting`, `culling` or `breeding`.
+    pub fn phase(&self) -> &'static str {
+        match *self {
+            STATE::Init => "seeding",
+            STATE::ComputeFitness => "fitness",
+            STATE::Reorder => "sorting",
+            STATE::RemoveUnfit => "culling",
+            STATE::BreedNew | STATE::Replace(_) => "breeding",
+        }
+    }
+}
+
+/// Called with the new best candidate and the generation it appeared in.
+pub type ImprovementHook<G> = Box<dyn FnMut(&Candidate<G, <G as Fitness>::Score>, usize)>;
+
+/// Steps through the algorithm one micro-step per `next()`, calling `callback` with the
+/// population, the current state's description and the indices of the candidates the
+/// step touched after every step. The callback returns `ControlFlow::Break(())` to stop
+/// the run.
+pub struct GeneticAlgorithm<F, G = String>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    population: Vec<Candidate<G, G::Score>>,
+    context: G::Context,
+    state: STATE,
+    num_fit_to_keep: usize,
+    elite_count: usize,
+    generation_gap: Option<f64>,
+    population_size: usize,
+    mutation_prob: f64,
+    effective_mutation_prob: f64,
+    mutation_schedule: Option<Box<dyn MutationSchedule>>,
+    adaptive_mutation: Option<AdaptiveMutation>,
+    best_fitness: Option<G::Score>,
+    generations_since_improvement: usize,
+    // The generator behind `StdRng`, used directly because it can be serialized.
+    rng: ChaCha12Rng,
+    fast_sort: bool,
+    tie_break: TieBreak,
+    batch: bool,
+    selection: SelectionStrategy,
+    objective: Objective,
+    crossover: CrossoverMode,
+    num_parents: usize,
+    cull: CullStrategy,
+    replacement: ReplacementMode,
+    generation: usize,
+    solved: bool,
+    target_fitness: Option<G::Score>,
+    max_generations: Option<usize>,
+    patience: Option<usize>,
+    stalled: bool,
+    stopped: bool,
+    restart: Option<RestartPolicy>,
+    restarts: usize,
+    fitness_sharing: Option<FitnessSharing>,
+    stats: Vec<GenerationStats<G, G::Score>>,
+    elapsed: Duration,
+    #[cfg(feature = "alloc-counts")]
+    alloc_counts: AllocCounts,
+    focus: Vec<usize>,
+    hall_of_fame: Vec<Candidate<G, G::Score>>,
+    hall_of_fame_size: usize,
+    lineage: Option<Lineage<G>>,
+    fitness_cache: Option<FitnessCache<G>>,
+    callback: F,
+    on_improvement: Option<ImprovementHook<G>>,
+}
+
+impl<F, G> GeneticAlgorithm<F, G>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    pub fn new(
+        context: G::Context,
+        num_fit_to_keep: usize,
+        population_size: usize,
+        mutation_prob: f64,
+        seed: Option<u64>,
+        callback: F,
+    ) -> Self {
+        Self {
+            population: Vec::new(),
+            context,
+            state: STATE::Init,
+            num_fit_to_keep,
+            elite_count: num_fit_to_keep,
+            generation_gap: None,
+            population_size,
+            mutation_prob,
+            effective_mutation_prob: mutation_prob,
+            mutation_schedule: None,
+            adaptive_mutation: None,
+            best_fitness: None,
+            generations_since_improvement: 0,
+            rng: match seed {
+                Some(seed) => ChaCha12Rng::seed_from_u64(seed),
+                None => ChaCha12Rng::from_entropy(),
+            },
+            fast_sort: false,
+            tie_break: TieBreak::default(),
+            batch: false,
+            selection: SelectionStrategy::default(),
+            objective: Objective::default(),
+            crossover: CrossoverMode::default(),
+            num_parents: 2,
+            cull: CullStrategy::default(),
+            replacement: ReplacementMode::default(),
+            generation: 0,
+            solved: false,
+            target_fitness: None,
+            max_generations: None,
+            patience: None,
+            stalled: false,
+            stopped: false,
+            restart: None,
+            restarts: 0,
+            fitness_sharing: None,
+            stats: Vec::new(),
+            elapsed: Duration::ZERO,
+            #[cfg(feature = "alloc-counts")]
+            alloc_counts: AllocCounts::now(),
+            focus: Vec::new(),
+            hall_of_fame: Vec::new(),
+            hall_of_fame_size: 0,
+            lineage: None,
+            fitness_cache: None,
+            callback,
+            on_improvement: None,
+        }
+    }
+
+    /// Resume a run saved with `checkpoint`. The settings are not part of the checkpoint
+    /// and should match the ones of the saved run.
+    #[cfg(feature = "serde")]
+    pub fn from_checkpoint(
+        context: G::Context,
+        num_fit_to_keep: usize,
+        population_size: usize,
+        mutation_prob: f64,
+        checkpoint: Checkpoint<G, G::Score>,
+        callback: F,
+    ) -> Self {
+        let mut genetic_algorithm = Self::new(
+            context,
+            num_fit_to_keep,
+            population_size,
+            mutation_prob,
+            None,
+            callback,
+        );
+        genetic_algorithm.population = checkpoint.population;
+        genetic_algorithm.state = checkpoint.state;
+        genetic_algorithm.rng = checkpoint.rng;
+        genetic_algorithm.generation = checkpoint.generation;
+        genetic_algorithm.best_fitness = checkpoint.best_fitness;
+        genetic_algorithm.generations_since_improvement = checkpoint.generations_since_improvement;
+        genetic_algorithm.effective_mutation_prob = checkpoint.effective_mutation_prob;
+        genetic_algorithm.restarts = checkpoint.restarts;
+        genetic_algorithm.hall_of_fame = checkpoint.hall_of_fame;
+        genetic_algorithm
+    }
+
+    /// Everything that changes while the algorithm runs, including the RNG, so that a
+    /// resumed run continues exactly like the original would have.
+    #[cfg(feature = "serde")]
+    pub fn checkpoint(&self) -> Checkpoint<G, G::Score> {
+        Checkpoint {
+            population: self.population.clone(),
+            state: self.state.clone(),
+            rng: self.rng.clone(),
+            generation: self.generation,
+            best_fitness: self.best_fitness,
+            generations_since_improvement: self.generations_since_improvement,
+            effective_mutation_prob: self.effective_mutation_prob,
+            restarts: self.restarts,
+            hall_of_fame: self.hall_of_fame.clone(),
+        }
+    }
+
+    pub fn population(&self) -> &[Candidate<G, G::Score>] {
+        &self.population
+    }
+
+    /// The best scored candidate in the population right now, according to the objective,
+    /// whether or not the population has been sorted yet. `None` until something is scored.
+    pub fn best(&self) -> Option<&Candidate<G, G::Score>> {
+        self.population
+            .iter()
+            .filter(|c| c.fitness.is_some())
+            .max_by_key(|c| self.objective.key(c.fitness))
+    }
+
+    /// The fitness of `best`.
+    pub fn best_fitness(&self) -> Option<G::Score> {
+        self.best().and_then(|c| c.fitness)
+    }
+
+    /// Up to `n` of the best scored candidates, best first, like `best`.
+    pub fn fittest(&self, n: usize) -> Vec<&Candidate<G, G::Score>> {
+        let mut scored: Vec<&Candidate<G, G::Score>> = self
+            .population
+            .iter()
+            .filter(|c| c.fitness.is_some())
+            .collect();
+        scored.sort_by_key(|c| std::cmp::Reverse(self.objective.key(c.fitness)));
+        scored.truncate(n);
+        scored
+    }
+
+    /// Replaces the last candidates, the worst of a sorted population or the newest
+    /// children, with `migrants` from another population, keeping the population size.
+    /// Migrants keep their fitness, so both populations must be scored alike.
+    pub fn immigrate(&mut self, migrants: impl IntoIterator<Item = Candidate<G, G::Score>>) {
+        let migrants: Vec<Candidate<G, G::Score>> =
+            migrants.into_iter().take(self.population.len()).collect();
+        self.population
+            .truncate(self.population.len() - migrants.len());
+        for mut migrant in migrants {
+            // Their ids belong to the other population's lineage.
+            migrant.id = 0;
+            migrant.parents.clear();
+            self.population.push(migrant);
+        }
+        self.register_births();
+    }
+
+    /// Indices of the candidates the last step touched, e.g. the one just scored or a
+    /// child and its parents, for highlighting. The callback receives the same indices.
+    pub fn focus(&self) -> &[usize] {
+        &self.focus
+    }
+
+    pub fn context(&self) -> &G::Context {
+        &self.context
+    }
+
+    pub fn state(&self) -> &STATE {
+        &self.state
+    }
+
+    /// Number of completed generations, i.e. full cycles back to `Init`.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The fitness that ends the run: `Fitness::max_fitness` unless `with_target_fitness`
+    /// lowered it.
+    pub fn max_fitness(&self) -> G::Score {
+        self.target_fitness
+            .unwrap_or_else(|| G::max_fitness(&self.context))
+    }
+
+    /// Whether a candidate matching the target has been found. Once solved the
+    /// iterator stops yielding.
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Whether the run gave up because the best fitness stopped improving, see
+    /// `with_patience`. Once stalled the iterator stops yielding.
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    /// Whether the callback stopped the run by returning `ControlFlow::Break`. Once stopped
+    /// the iterator stops yielding.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// The best distinct candidates ever scored, best first, see `with_hall_of_fame`.
+    pub fn hall_of_fame(&self) -> &[Candidate<G, G::Score>] {
+        &self.hall_of_fame
+    }
+
+    /// Every candidate the run has produced, if tracked, see `with_lineage`.
+    pub fn lineage(&self) -> Option<&Lineage<G>> {
+        self.lineage.as_ref()
+    }
+
+    /// Time spent stepping so far, callbacks included. Time between steps, e.g. drawing
+    /// or pausing in a frontend, does not count. Always zero where there is no clock, as
+    /// in the browser.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Completed generations per second of `elapsed`, or 0 before any time was measured.
+    pub fn generations_per_second(&self) -> f64 {
+        generations_per_second(self.generation, self.elapsed)
+    }
+
+    /// How often the population has been restarted, see `with_restart`.
+    pub fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// The generation cap set with `with_max_generations`, if any.
+    pub fn max_generations(&self) -> Option<usize> {
+        self.max_generations
+    }
+
+    /// Whether the run is over: solved, stalled, stopped or out of generations. Once
+    /// finished the iterator stops yielding.
+    pub fn is_finished(&self) -> bool {
+        self.solved
+            || self.stalled
+            || self.stopped
+            || self
+                .max_generations
+                .is_some_and(|max| self.generation >= max)
+    }
+
+    /// The best fitness of every generation so far, oldest first.
+    pub fn best_fitness_history(&self) -> Vec<G::Score> {
+        self.stats.iter().map(|s| s.best_fitness).collect()
+    }
+
+    /// The mutation probability used for breeding right now, which differs from the
+    /// configured one while adaptive mutation is reacting to a plateau or as a mutation
+    /// schedule cools down.
+    pub fn current_mutation_prob(&self) -> f64 {
+        self.effective_mutation_prob
+    }
+
+    pub fn generations_since_improvement(&self) -> usize {
+        self.generations_since_improvement
+    }
+
+    /// Statistics of every generation so far, oldest first.
+    pub fn stats(&self) -> &[GenerationStats<G, G::Score>] {
+        &self.stats
+    }
+
+    /// The fitness cache, if enabled with `with_fitness_cache`.
+    pub fn fitness_cache(&self) -> Option<&FitnessCache<G>> {
+        self.fitness_cache.as_ref()
+    }
+
+    /// Step through whole generations instead of micro-steps, yielding the statistics of
+    /// each completed generation. Ends once the run is finished.
+    pub fn by_generation(&mut self) -> Generations<'_, F, G> {
+        Generations {
+            genetic_algorithm: self,
+        }
+    }
+
+    /// Update the plateau counter, the mutation rate and the improvement hook once a
+    /// generation is fully scored.
+    fn track_improvement(&mut self) {
+        let objective = self.objective;
+        let best_fitness = self
+            .population
+            .iter()
+            .map(|c| c.fitness)
+            .max_by_key(|&fitness| objective.key(fitness))
+            .flatten();
+        if objective.is_better(best_fitness, self.best_fitness) {
+            self.best_fitness = best_fitness;
+            self.generations_since_improvement = 0;
+            if let Some(on_improvement) = &mut self.on_improvement {
+                let best = self
+                    .population
+                    .iter()
+                    .find(|c| c.fitness == best_fitness)
+                    .unwrap();
+                on_improvement(best, self.generation);
+            }
+        } else {
+            self.generations_since_improvement += 1;
+        }
+        let base_mutation_prob = match &self.mutation_schedule {
+            Some(schedule) => schedule.rate(self.generation),
+            None => self.mutation_prob,
+        };
+        self.effective_mutation_prob = match &self.adaptive_mutation {
+            Some(adaptive_mutation) => adaptive_mutation.adapt(
+                self.effective_mutation_prob,
+                base_mutation_prob,
+                self.generations_since_improvement,
+            ),
+            None => base_mutation_prob,
+        };
+    }
+
+    /// Cuts the population down to the elite if the restart policy says it has converged,
+    /// so that `Init` fills it up with random candidates again.
+    fn restart_if_converged(&mut self) {
+        let Some(restart) = self.restart else {
+            return;
+        };
+        if restart.should_restart(
+            population_diversity(&self.population),
+            self.generations_since_improvement,
+        ) {
+            self.population.truncate(self.carried_over());
+            self.restarts += 1;
+            // Give the fresh candidates as long to improve as the old ones had.
+            self.generations_since_improvement = 0;
+        }
+    }
+
+    /// Gives the candidates that joined the population since the last step an id and
+    /// records them in the lineage, if tracked.
+    fn register_births(&mut self) {
+        // Children bred at the end of a generation are born into the next one.
+        let generation = match self.state {
+            STATE::BreedNew => self.generation + 1,
+            _ => self.generation,
+        };
+        if let Some(lineage) = &mut self.lineage {
+            for candidate in self.population.iter_mut().filter(|c| c.id == 0) {
+                candidate.id =
+                    lineage.register(&candidate.genome, generation, candidate.parents.clone());
+            }
+        }
+    }
+
+    /// Offers the freshly scored candidate at `i` to the hall of fame.
+    fn admit(&mut self, i: usize) {
+        admit_to_hall_of_fame(
+            &mut self.hall_of_fame,
+            &self.population[i],
+            self.hall_of_fame_size,
+            self.objective,
+        );
+    }
+
+    /// `admit` a child scored from its parent as it was bred, see
+    /// `Fitness::fitness_from_parent`; `ComputeFitness` admits the others.
+    fn admit_if_scored(&mut self, i: usize) {
+        if self.population[i].fitness.is_some() {
+            self.admit(i);
+        }
+    }
+
+    /// Focuses the candidates a step touched and hands the step to the callback; `None` if
+    /// it asked to stop the run. The only place the focus is set, so that it always
+    /// belongs to the latest step.
+    fn notify(&mut self, touched: impl IntoIterator<Item = usize>) -> Option<()> {
+        self.register_births();
+        self.focus.extend(touched);
+        self.focus.sort_unstable();
+        self.focus.dedup();
+        let flow = (self.callback)(&self.population, self.state.description(), &self.focus);
+        if flow.is_break() {
+            self.stopped = true;
+            return None;
+        }
+        Some(())
+    }
+
+    /// Start from these genomes instead of only random ones; seeding fills up the rest of
+    /// the population. Only has an effect before the first step, and genomes beyond the
+    /// population size are ignored.
+    pub fn with_seed_genomes(mut self, genomes: impl IntoIterator<Item = G>) -> Self {
+        let room = self.population_size.saturating_sub(self.population.len());
+        self.population
+            .extend(genomes.into_iter().take(room).map(Candidate::new));
+        self
+    }
+
+    /// Remember the `size` best distinct candidates ever scored, even after they have been
+    /// culled, see `hall_of_fame`. Genomes at distance 0 count as the same.
+    pub fn with_hall_of_fame(mut self, size: usize) -> Self {
+        self.hall_of_fame_size = size;
+        self
+    }
+
+    /// Call `on_improvement` with the new best candidate and the generation number whenever
+    /// the best fitness strictly increases. Unlike the callback it stays quiet otherwise.
+    pub fn with_on_improvement(
+        mut self,
+        on_improvement: impl FnMut(&Candidate<G, G::Score>, usize) + 'static,
+    ) -> Self {
+        self.on_improvement = Some(Box::new(on_improvement));
+        self
+    }
+
+    /// Give every candidate an id and remember where each came from, so that the ancestry
+    /// of the winner can be traced back to the initial population, see `lineage`. Costs
+    /// memory for every candidate ever bred.
+    pub fn with_lineage(mut self) -> Self {
+        self.lineage = Some(Lineage::default());
+        self
+    }
+
+    /// Remember the score of every genome and reuse it when the same genome shows up
+    /// again, instead of scoring it once more. Meant for expensive fitness functions.
+    pub fn with_fitness_cache(mut self) -> Self
+    where
+        G: std::hash::Hash + Eq + 'static,
+    {
+        self.fitness_cache = Some(FitnessCache::new());
+        self
+    }
+
+    /// Sort the whole population in one `sort_by` call instead of bubble sorting.
+    pub fn with_fast_sort(mut self, fast_sort: bool) -> Self {
+        self.fast_sort = fast_sort;
+        self
+    }
+
+    /// How candidates of equal fitness are ordered when sorting, see `TieBreak`. Stable by
+    /// default.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Score the whole population and breed all children in one step each instead of one
+    /// candidate per step, scoring in parallel with the `parallel` feature. Meant for runs
+    /// without animation.
+    pub fn with_batch(mut self, batch: bool) -> Self {
+        self.batch = batch;
+        self
+    }
+
+    /// Carry only the best `elite_count` survivors over to the next generation; the other
+    /// survivors still act as parents but are replaced by children. Defaults to all of them.
+    pub fn with_elite_count(mut self, elite_count: usize) -> Self {
+        assert!(
+            elite_count <= self.num_fit_to_keep,
+            "elite count must not exceed the number of survivors"
+        );
+        self.elite_count = elite_count;
+        self
+    }
+
+    /// Replace the fraction `generation_gap` of the population with children every
+    /// generation and carry the best of the rest over, however many survive culling. 1
+    /// breeds a whole new generation, a small gap approaches steady-state replacement.
+    /// Takes the place of the elite count.
+    pub fn with_generation_gap(mut self, generation_gap: f64) -> Self {
+        assert!(
+            generation_gap > 0.0 && generation_gap <= 1.0,
+            "generation gap must be in (0, 1]"
+        );
+        self.generation_gap = Some(generation_gap);
+        self
+    }
+
+    /// How many candidates are carried over unchanged into the next generation.
+    fn carried_over(&self) -> usize {
+        match self.generation_gap {
+            Some(gap) => self.population_size - children_per_generation(self.population_size, gap),
+            None => self.elite_count,
+        }
+    }
+
+    /// Take the mutation probability of every generation from `schedule` instead of the
+    /// fixed one. Adaptive mutation still reacts to plateaus, but never drops below the
+    /// scheduled rate.
+    pub fn with_mutation_schedule(mut self, schedule: impl MutationSchedule + 'static) -> Self {
+        self.effective_mutation_prob = schedule.rate(self.generation);
+        self.mutation_schedule = Some(Box::new(schedule));
+        self
+    }
+
+    pub fn with_adaptive_mutation(mut self, adaptive_mutation: AdaptiveMutation) -> Self {
+        self.adaptive_mutation = Some(adaptive_mutation);
+        self
+    }
+
+    pub fn with_selection(mut self, selection: SelectionStrategy) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Treat lower fitness as better, e.g. for distance-based scores. Sorting, selection,
+    /// replacement, statistics and the improvement tracking all follow the objective.
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    pub fn with_crossover(mut self, crossover: CrossoverMode) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    /// Breed every child from `num_parents` parents, see `Crossover::crossover_many`; 1
+    /// breeds asexually. Defaults to 2.
+    pub fn with_num_parents(mut self, num_parents: usize) -> Self {
+        assert!(num_parents >= 1, "breeding needs at least one parent");
+        self.num_parents = num_parents;
+        self
+    }
+
+    /// Stop after `max_generations` complete generations, which must be at least one.
+    pub fn with_max_generations(mut self, max_generations: usize) -> Self {
+        assert!(max_generations > 0, "at least one generation must run");
+        self.max_generations = Some(max_generations);
+        self
+    }
+
+    /// Count the run as solved at `target_fitness` instead of `Fitness::max_fitness`, e.g.
+    /// when the perfect score cannot be reached.
+    pub fn with_target_fitness(mut self, target_fitness: G::Score) -> Self {
+        self.target_fitness = Some(target_fitness);
+        self
+    }
+
+    /// Stop once the best fitness has not improved for `patience` generations.
+    pub fn with_patience(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Replace the worst candidate one child at a time instead of culling and breeding
+    /// whole generations. Survivor, elite and cull settings only apply to generational
+    /// replacement.
+    pub fn with_replacement(mut self, replacement: ReplacementMode) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Reseed everything but the elite with random candidates whenever the population has
+    /// converged without progress, see `RestartPolicy`.
+    pub fn with_restart(mut self, restart: RestartPolicy) -> Self {
+        self.restart = Some(restart);
+        self
+    }
+
+    /// Rank candidates by their fitness shared with every candidate within the sharing
+    /// radius, so a crowded niche ranks below an isolated candidate of equal fitness.
+    pub fn with_fitness_sharing(mut self, fitness_sharing: FitnessSharing) -> Self {
+        self.fitness_sharing = Some(fitness_sharing);
+        self
+    }
+
+    pub fn with_cull(mut self, cull: CullStrategy) -> Self {
+        assert!(
+            cull.reserved() < self.num_fit_to_keep,
+            "at least one survivor must be chosen by fitness"
+        );
+        self.cull = cull;
+        self
+    }
+}
+
+impl<F> GeneticAlgorithm<F, String>
+where
+    F: Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>,
+{
+    /// `with_seed_genomes` for strings of any length: longer ones are cut to the target
+    /// length and shorter ones padded with random characters from the alphabet.
+    pub fn with_seed_candidates<S: AsRef<str>>(
+        mut self,
+        seeds: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let genomes: Vec<String> = seeds
+            .into_iter()
+            .map(|seed| self.context.fit_to_target(seed.as_ref(), &mut self.rng))
+            .collect();
+        self.with_seed_genomes(genomes)
+    }
+}
+
+impl<F, G> Iterator for GeneticAlgorithm<F, G>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    type Item = ();
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished() {
+            return None;
+        }
+        let started = now();
+        let stepped = self.step();
+        if let Some(started) = started {
+            self.elapsed += started.elapsed();
+        }
+        stepped
+    }
+}
+
+impl<F, G> GeneticAlgorithm<F, G>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    /// The micro-step `next` takes.
+    fn step(&mut self) -> Option<()> {
+        self.focus.clear();
+        use STATE::*;
+        match &self.state {
+            Init => {
+                if seed_population(
+                    &mut self.population,
+                    self.population_size,
+                    &self.context,
+                    &mut self.rng,
+                ) {
+                    return self.notify([self.population.len() - 1]);
+                } else {
+                    self.state = ComputeFitness;
+                }
+            }
+            ComputeFitness => {
+                let unscored: Vec<usize> = self
+                    .population
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.fitness.is_none())
+                    .map(|(i, _)| i)
+                    .take(if self.batch { usize::MAX } else { 1 })
+                    .collect();
+                let scored = match (&mut self.fitness_cache, self.batch) {
+                    (Some(cache), true) => {
+                        compute_fitness_batch_cached(&mut self.population, &self.context, cache)
+                    }
+                    (Some(cache), false) => {
+                        compute_fitness_cached(&mut self.population, &self.context, cache)
+                    }
+                    (None, true) => compute_fitness_batch(&mut self.population, &self.context),
+                    (None, false) => compute_fitness(&mut self.population, &self.context),
+                };
+                for &i in &unscored {
+                    self.admit(i);
+                }
+                if scored {
+                    return self.notify(unscored);
+                } else {
+                    let mut stats = GenerationStats::from_population(
+                        self.generation,
+                        &self.population,
+                        self.objective,
+                    );
+                    stats.cache_hit_rate = self.fitness_cache.as_ref().map(FitnessCache::hit_rate);
+                    stats.restarts = self.restarts;
+                    stats.elapsed = self.elapsed;
+                    #[cfg(feature = "alloc-counts")]
+                    {
+                        let now = AllocCounts::now();
+                        stats.alloc_counts = now.since(self.alloc_counts);
+                        self.alloc_counts = now;
+                    }
+                    self.stats.push(stats);
+                    self.track_improvement();
+                    let max_fitness = self.max_fitness();
+                    self.solved = self
+                        .population
+                        .iter()
+                        .any(|c| c.fitness == Some(max_fitness));
+                    if let Some(patience) = self.patience {
+                        // The plateau check expects higher to be better.
+                        let history: Vec<Key<G::Score>> = self
+                            .stats
+                            .iter()
+                            .filter_map(|s| self.objective.key(Some(s.best_fitness)))
+                            .collect();
+                        self.stalled = !self.solved && is_plateau(&history, patience);
+                    }
+                    if let Some(FitnessSharing { radius, alpha }) = self.fitness_sharing {
+                        apply_fitness_sharing(&mut self.population, radius, alpha);
+                    }
+                    self.state = Reorder;
+                }
+            }
+            Reorder => {
+                let order = match (self.tie_break, self.fast_sort) {
+                    (TieBreak::Stable, true) => {
+                        fitness_order_fast(&self.population, self.objective)
+                    }
+                    (TieBreak::Stable, false) => fitness_order(&self.population, self.objective),
+                    (tie_break, true) => {
+                        fitness_order_fast_with(&self.population, self.objective, tie_break)
+                    }
+                    (tie_break, false) => {
+                        fitness_order_with(&self.population, self.objective, tie_break)
+                    }
+                };
+                if apply_order(&mut self.population, &order) {
+                    // Where each slot's previous candidate went.
+                    let mut moved_to = vec![0; order.len()];
+                    for (slot, &source) in order.iter().enumerate() {
+                        moved_to[source] = slot;
+                    }
+                    // The slots now holding a candidate of different fitness; a stable sort
+                    // never lets equally fit candidates trade places, the other tie breaks
+                    // and fitness sharing can, so they also count slots whose genome
+                    // changed.
+                    let moved: Vec<usize> = (0..order.len())
+                        .filter(|&i| {
+                            let previous = &self.population[moved_to[i]];
+                            self.population[i].fitness != previous.fitness
+                                || ((self.tie_break != TieBreak::Stable
+                                    || self.fitness_sharing.is_some())
+                                    && self.population[i].genome.distance(&previous.genome) != 0)
+                        })
+                        .collect();
+                    return self.notify(moved);
+                } else if self.replacement == ReplacementMode::SteadyState {
+                    self.state = Replace(0);
+                } else {
+                    if let CullStrategy::Age { max_age } = self.cull {
+                        promote_young(&mut self.population, self.objective, max_age);
+                    }
+                    promote_diverse(
+                        &mut self.population,
+                        self.num_fit_to_keep,
+                        self.cull.reserved(),
+                    );
+                    self.state = RemoveUnfit;
+                }
+            }
+            RemoveUnfit => {
+                // Whoever is carried over survives culling even beyond the keep count.
+                let survivors = self.num_fit_to_keep.max(self.carried_over());
+                if remove_unfit(&mut self.population, survivors) {
+                    // The new last candidate is the next to go.
+                    return self.notify(self.population.len().checked_sub(1));
+                }
+                self.state = BreedNew;
+            }
+            BreedNew => {
+                let carried_over = self.carried_over();
+                let survivors = self.num_fit_to_keep.max(carried_over);
+                let num_replaced = survivors - carried_over;
+                if self.batch {
+                    let first_child = self.population.len();
+                    // One child at a time, so that a sibling picked as a parent already has
+                    // its lineage id.
+                    while breed_new(
+                        &mut self.population,
+                        self.population_size + num_replaced,
+                        self.effective_mutation_prob,
+                        &self.selection,
+                        self.objective,
+                        self.crossover,
+                        self.num_parents,
+                        &self.context,
+                        &mut self.rng,
+                    )
+                    .is_some()
+                    {
+                        self.register_births();
+                        self.admit_if_scored(self.population.len() - 1);
+                    }
+                    if self.population.len() > first_child {
+                        return self.notify(first_child..self.population.len());
+                    }
+                } else if let Some(parents) = breed_new(
+                    &mut self.population,
+                    self.population_size + num_replaced,
+                    self.effective_mutation_prob,
+                    &self.selection,
+                    self.objective,
+                    self.crossover,
+                    self.num_parents,
+                    &self.context,
+                    &mut self.rng,
+                ) {
+                    let child = self.population.len() - 1;
+                    self.admit_if_scored(child);
+                    return self.notify(parents.into_iter().chain([child]));
+                }
+                self.population.drain(carried_over..survivors);
+                // The elite is carried into the next generation, the children are born into it.
+                for candidate in &mut self.population[..carried_over] {
+                    candidate.age += 1;
+                }
+                self.restart_if_converged();
+                self.state = Init;
+                self.generation += 1;
+            }
+            Replace(tried) => {
+                let tried = *tried;
+                if tried < self.population_size {
+                    let replacement = replace_worst(
+                        &mut self.population,
+                        self.effective_mutation_prob,
+                        &self.selection,
+                        self.objective,
+                        self.crossover,
+                        self.num_parents,
+                        &self.context,
+                        &mut self.rng,
+                    );
+                    // The child was scored while breeding; one that did not get in is worse
+                    // than everyone already offered to the hall of fame.
+                    if let Some(child) = replacement.child {
+                        self.admit(child);
+                    }
+                    self.state = Replace(tried + 1);
+                    return self.notify(replacement.parents.into_iter().chain(replacement.child));
+                }
+                for candidate in &mut self.population {
+                    candidate.age += 1;
+                }
+                self.restart_if_converged();
+                self.state = Init;
+                self.generation += 1;
+            }
+        }
+        self.register_births();
+        None
+    }
+}
+
+/// Iterator returned by `GeneticAlgorithm::by_generation`.
+pub struct Generations<'a, F, G = String>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    genetic_algorithm: &'a mut GeneticAlgorithm<F, G>,
+}
+
+impl<F, G> Iterator for Generations<'_, F, G>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    type Item = GenerationStats<G, G::Score>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let genetic_algorithm = &mut *self.genetic_algorithm;
+        if genetic_algorithm.is_finished() {
+            return None;
+        }
+        let generation = genetic_algorithm.generation();
+        while genetic_algorithm.generation() == generation && !genetic_algorithm.is_finished() {
+            genetic_algorithm.next();
+        }
+        // A run stopped before scoring this generation has no statistics for it.
+        genetic_algorithm
+            .stats()
+            .last()
+            .filter(|stats| stats.generation == generation)
+            .cloned()
+    }
+}
+
+/// The current time, or `None` where the platform has no clock, e.g. in the browser.
+fn now() -> Option<Instant> {
+    if cfg!(target_arch = "wasm32") {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
+
+/// `generations` divided by `elapsed` in seconds, or 0 if no time has passed.
+pub fn generations_per_second(generations: usize, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        0.0
+    } else {
+        generations as f64 / elapsed.as_secs_f64()
+    }
+}
+
+/// How many children a generation gap of `generation_gap` breeds each generation: at
+/// least one and at most the whole population.
+pub fn children_per_generation(population_size: usize, generation_gap: f64) -> usize {
+    ((population_size as f64 * generation_gap).ceil() as usize).clamp(1, population_size)
+}
+
+pub fn seed_population<G: Genome, S: Score>(
+    population: &mut Vec<Candidate<G, S>>,
+    population_size: usize,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> bool {
+    if population.len() < population_size {
+        population.push(Candidate::new(G::random(context, rng)));
+        true
+    } else {
+        false
+    }
+}
+
+pub fn compute_fitness<G: Fitness>(
+    population: &mut [Candidate<G, G::Score>],
+    context: &G::Context,
+) -> bool {
+    if let Some(ref mut candidate) = population.iter_mut().find(|c| c.fitness.is_none()) {
+        candidate.set_fitness(context);
+        true
+    } else {
+        false
+    }
+}
+
+/// Score every unscored candidate at once, returning whether any needed scoring.
+#[cfg(feature = "parallel")]
+pub fn compute_fitness_batch<G: Fitness>(
+    population: &mut [Candidate<G, G::Score>],
+    context: &G::Context,
+) -> bool {
+    use rayon::prelude::*;
+
+    population
+        .par_iter_mut()
+        .filter(|c| c.fitness.is_none())
+        .map(|c| c.set_fitness(context))
+        .count()
+        > 0
+}
+
+/// Score every unscored candidate at once, returning whether any needed scoring.
+#[cfg(not(feature = "parallel"))]
+pub fn compute_fitness_batch<G: Fitness>(
+    population: &mut [Candidate<G, G::Score>],
+    context: &G::Context,
+) -> bool {
+    let mut scored = false;
+    for candidate in population.iter_mut().filter(|c| c.fitness.is_none()) {
+        candidate.set_fitness(context);
+        scored = true;
+    }
+    scored
+}
+
+/// Bubble sorts the population into best-first order, returning whether anything moved.
+pub fn reorder_by_fitness<G, S: Score>(
+    population: &mut [Candidate<G, S>],
+    objective: Objective,
+) -> bool {
+    let order = fitness_order(population, objective);
+    apply_order(population, &order)
+}
+
+pub fn reorder_by_fitness_fast<G, S: Score>(
+    population: &mut [Candidate<G, S>],
+    objective: Objective,
+) -> bool {
+    let order = fitness_order_fast(population, objective);
+    apply_order(population, &order)
+}
+
+/// The slot of the candidate that `reorder_by_fitness` moves into each slot, found by
+/// bubble sorting the slots.
+pub(crate) fn fitness_order<G, S: Score>(
+    population: &[Candidate<G, S>],
+    objective: Objective,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..population.len()).collect();
+    let n = order.len();
+    for i in 0..n {
+        for j in 0..n - i - 1 {
+            if population[order[j + 1]]
+                .rank_cmp(&population[order[j]], objective)
+                .is_gt()
+            {
+                order.swap(j, j + 1);
+            }
+        }
+    }
+    order
+}
+
+/// `fitness_order` by a stable sort instead.
+pub(crate) fn fitness_order_fast<G, S: Score>(
+    population: &[Candidate<G, S>],
+    objective: Objective,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..population.len()).collect();
+    order.sort_by(|&i, &j| population[j].rank_cmp(&population[i], objective));
+    order
+}
+
+/// Moves the candidate in slot `order[i]` into slot `i` by swapping, returning whether any
+/// moved.
+pub(crate) fn apply_order<T>(items: &mut [T], order: &[usize]) -> bool {
+    let mut moved = false;
+    for slot in 0..order.len() {
+        // Slots before this one are final, so a candidate taken from one of them has since
+        // been swapped to where that slot's own candidate came from.
+        let mut source = order[slot];
+        while source < slot {
+            source = order[source];
+        }
+        if source != slot {
+            items.swap(slot, source);
+            moved = true;
+        }
+    }
+    moved
+}
+
+pub fn remove_unfit<G, S>(population: &mut Vec<Candidate<G, S>>, num_fit_to_keep: usize) -> bool {
+    if population.len() > num_fit_to_keep {
+        population.pop();
+        true
+    } else {
+        false
+    }
+}
+
+/// Breeds one child from `num_parents` parents picked by `selection` and appends it,
+/// returning the parents' indices, or `None` once the population has reached
+/// `population_size`.
+#[allow(clippy::too_many_arguments)]
+pub fn breed_new<G: Crossover + Fitness>(
+    population: &mut Vec<Candidate<G, G::Score>>,
+    population_size: usize,
+    mutation_prob: f64,
+    selection: &SelectionStrategy,
+    objective: Objective,
+    crossover: CrossoverMode,
+    num_parents: usize,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> Option<Vec<usize>> {
+    if population.len() >= population_size {
+        return None;
+    }
+    let parents = breeding_parents(
+        population,
+        selection,
+        num_parents,
+        crossover,
+        objective,
+        rng,
+    );
+    // The child is bred from borrowed parents before it joins the population.
+    let child = breed_at(population, &parents, crossover, mutation_prob, context, rng);
+    population.push(child);
+    Some(parents)
+}
+
+/// `breed_new` until the population reaches `population_size`, returning whether any
+/// child was bred.
+#[allow(clippy::too_many_arguments)]
+pub fn breed_new_batch<G: Crossover + Fitness>(
+    population: &mut Vec<Candidate<G, G::Score>>,
+    population_size: usize,
+    mutation_prob: f64,
+    selection: &SelectionStrategy,
+    objective: Objective,
+    crossover: CrossoverMode,
+    num_parents: usize,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> bool {
+    let mut bred = false;
+    while breed_new(
+        population,
+        population_size,
+        mutation_prob,
+        selection,
+        objective,
+        crossover,
+        num_parents,
+        context,
+        rng,
+    )
+    .is_some()
+    {
+        bred = true;
+    }
+    bred
+}
@@ -0,0 +1,87 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CANDIDATE_CLONES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// How many `Candidate`s were cloned and how often the heap was allocated from, counted
+/// for the whole process, so concurrent runs count toward the same totals. Allocations are
+/// only counted with `CountingAllocator` installed as the global allocator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocCounts {
+    pub candidate_clones: usize,
+    pub allocations: usize,
+    pub bytes: usize,
+}
+
+impl AllocCounts {
+    /// The totals so far.
+    pub fn now() -> Self {
+        Self {
+            candidate_clones: CANDIDATE_CLONES.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// What was counted between `earlier` and `self`.
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            candidate_clones: self.candidate_clones - earlier.candidate_clones,
+            allocations: self.allocations - earlier.allocations,
+            bytes: self.bytes - earlier.bytes,
+        }
+    }
+}
+
+impl fmt::Display for AllocCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} candidate clones, {} allocations ({} bytes)",
+            self.candidate_clones, self.allocations, self.bytes
+        )
+    }
+}
+
+pub(crate) fn count_candidate_clone() {
+    CANDIDATE_CLONES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The system allocator, counting every allocation for `AllocCounts`. Install it in the
+/// binary or test that wants the counts:
+///
+/// ```
+/// #[global_allocator]
+/// static ALLOCATOR: ga::CountingAllocator = ga::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+fn count_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    // Growing a `String` in place still asks the allocator, so it counts as well.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::*;
+
+use crate::genome::StringContext;
+
+/// Where the string operators draw new characters from. A plain `str` makes every
+/// character equally likely; a `StringContext` follows its `alphabet_weights`.
+pub trait Alphabet {
+    /// The characters that can be drawn.
+    fn letters(&self) -> &str;
+
+    /// Draws one character. Panics if there are none.
+    fn random_char(&self, rng: &mut impl Rng) -> char;
+}
+
+impl Alphabet for str {
+    fn letters(&self) -> &str {
+        self
+    }
+
+    fn random_char(&self, rng: &mut impl Rng) -> char {
+        self.chars().choose(rng).expect("alphabet is not empty")
+    }
+}
+
+impl Alphabet for StringContext {
+    fn letters(&self) -> &str {
+        &self.alphabet
+    }
+
+    fn random_char(&self, rng: &mut impl Rng) -> char {
+        match &self.alphabet_weights {
+            Some(AlphabetWeights(weights)) => {
+                let index = WeightedIndex::new(weights).expect("weights are validated");
+                self.alphabet
+                    .chars()
+                    .nth(index.sample(rng))
+                    .expect("one weight per character")
+            }
+            None => self.alphabet.random_char(rng),
+        }
+    }
+}
+
+/// Steps from `a` to `b` moving one neighbor at a time through `alphabet`, wrapping around
+/// at the ends like `shift_to_neighbor`; `None` if either is not in the alphabet.
+pub fn alphabet_distance(alphabet: &str, a: char, b: char) -> Option<usize> {
+    let len = alphabet.chars().count();
+    let i = alphabet.chars().position(|c| c == a)?;
+    let j = alphabet.chars().position(|c| c == b)?;
+    let steps = i.abs_diff(j);
+    Some(steps.min(len - steps))
+}
+
+/// How often each alphabet character is drawn relative to the others, one weight per
+/// character in alphabet order, e.g. to favor the vowels of a target that has many.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlphabetWeights(pub Vec<u32>);
+
+/// Parses comma-separated non-negative integers, e.g. `5,1,1,1,5`.
+impl FromStr for AlphabetWeights {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|weight| {
+                weight.trim().parse().map_err(|_| {
+                    format!(
+                        "alphabet weights must be non-negative integers, got '{}'",
+                        weight
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(AlphabetWeights)
+    }
+}
+
+/// The form `from_str` accepts.
+impl fmt::Display for AlphabetWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weights: Vec<String> = self.0.iter().map(u32::to_string).collect();
+        f.write_str(&weights.join(","))
+    }
+}
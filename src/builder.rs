@@ -0,0 +1,831 @@
+use std::fmt;
+use std::ops::ControlFlow;
+
+use crate::algorithm::GeneticAlgorithm;
+use crate::alphabet::AlphabetWeights;
+use crate::candidate::Candidate;
+#[cfg(feature = "serde")]
+use crate::checkpoint::Checkpoint;
+use crate::crossover::CrossoverMode;
+use crate::diversity::{CullStrategy, FitnessSharing, RestartPolicy};
+use crate::error::GaError;
+use crate::fitness::FitnessMode;
+use crate::genome::{HintCount, StringContext};
+use crate::mutation::{AdaptiveMutation, Cooling, MutationOp};
+use crate::objective::Objective;
+use crate::replacement::ReplacementMode;
+use crate::selection::SelectionStrategy;
+use crate::tie_break::TieBreak;
+use crate::{LETTERS, MUTATION_PROB, NUM_FIT_TO_KEEP, POPULATION_SIZE, TARGET_STR};
+
+/// The callback used when none is set: does nothing.
+pub type NoCallback = fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>;
+
+/// Configures a string-evolving `GeneticAlgorithm`. Every setting defaults to the values the
+/// demo uses, and `build` checks that they are consistent.
+///
+/// ```
+/// let ga = ga::GeneticAlgorithmBuilder::new()
+///     .target("hello world")
+///     .population_size(48)
+///     .mutation_prob(0.15)
+///     .selection(ga::SelectionStrategy::Tournament { k: 3 })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct GeneticAlgorithmBuilder<F = NoCallback> {
+    target: String,
+    alphabet: String,
+    alphabet_weights: Option<AlphabetWeights>,
+    allow_unreachable: bool,
+    fitness_mode: FitnessMode,
+    ignore_case: bool,
+    incremental_fitness: bool,
+    objective: Objective,
+    population_size: usize,
+    num_fit_to_keep: usize,
+    elite_count: Option<usize>,
+    generation_gap: Option<f64>,
+    mutation_prob: f64,
+    hint_prob: f64,
+    init_len_min: Option<usize>,
+    init_len_max: Option<usize>,
+    mutation_op: MutationOp,
+    mutation_schedule: Option<Cooling>,
+    adaptive_mutation: Option<AdaptiveMutation>,
+    seed: Option<u64>,
+    seed_candidates: Vec<String>,
+    patience: Option<usize>,
+    max_generations: Option<usize>,
+    restart: Option<RestartPolicy>,
+    fitness_sharing: Option<FitnessSharing>,
+    hall_of_fame: usize,
+    lineage: bool,
+    fast_sort: bool,
+    batch: bool,
+    fitness_cache: bool,
+    selection: SelectionStrategy,
+    crossover: CrossoverMode,
+    num_parents: usize,
+    cull: CullStrategy,
+    replacement: ReplacementMode,
+    tie_break: TieBreak,
+    #[cfg(feature = "serde")]
+    checkpoint: Option<Checkpoint>,
+    callback: F,
+}
+
+impl GeneticAlgorithmBuilder {
+    pub fn new() -> Self {
+        Self {
+            target: TARGET_STR.to_string(),
+            alphabet: LETTERS.to_string(),
+            alphabet_weights: None,
+            allow_unreachable: false,
+            fitness_mode: FitnessMode::default(),
+            ignore_case: false,
+            incremental_fitness: false,
+            objective: Objective::default(),
+            population_size: POPULATION_SIZE,
+            num_fit_to_keep: NUM_FIT_TO_KEEP,
+            elite_count: None,
+            generation_gap: None,
+            mutation_prob: MUTATION_PROB,
+            hint_prob: 0.0,
+            init_len_min: None,
+            init_len_max: None,
+            mutation_op: MutationOp::default(),
+            mutation_schedule: None,
+            adaptive_mutation: None,
+            seed: None,
+            seed_candidates: Vec::new(),
+            patience: None,
+            max_generations: None,
+            restart: None,
+            fitness_sharing: None,
+            hall_of_fame: 0,
+            lineage: false,
+            fast_sort: false,
+            batch: false,
+            fitness_cache: false,
+            selection: SelectionStrategy::default(),
+            crossover: CrossoverMode::default(),
+            num_parents: 2,
+            cull: CullStrategy::default(),
+            replacement: ReplacementMode::default(),
+            tie_break: TieBreak::default(),
+            #[cfg(feature = "serde")]
+            checkpoint: None,
+            callback: |_, _, _| ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl Default for GeneticAlgorithmBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> GeneticAlgorithmBuilder<F>
+where
+    F: Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>,
+{
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    pub fn alphabet(mut self, alphabet: impl Into<String>) -> Self {
+        self.alphabet = alphabet.into();
+        self
+    }
+
+    /// Draw alphabet characters with these relative weights, one per character, when
+    /// seeding and mutating, instead of uniformly.
+    pub fn alphabet_weights(mut self, alphabet_weights: AlphabetWeights) -> Self {
+        self.alphabet_weights = Some(alphabet_weights);
+        self
+    }
+
+    /// Accept a target with characters missing from the alphabet instead of rejecting it;
+    /// the run then counts as solved at `StringContext::reachable_max_fitness`.
+    pub fn allow_unreachable(mut self, allow_unreachable: bool) -> Self {
+        self.allow_unreachable = allow_unreachable;
+        self
+    }
+
+    pub fn fitness_mode(mut self, fitness_mode: FitnessMode) -> Self {
+        self.fitness_mode = fitness_mode;
+        self
+    }
+
+    /// Count letters as matching the target whatever their case, see
+    /// `StringContext::ignore_case`.
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Score children from their parent's fitness where possible instead of rescanning the
+    /// whole string, see `StringContext::incremental_fitness`. Children scored this way
+    /// skip the fitness cache.
+    pub fn incremental_fitness(mut self, incremental_fitness: bool) -> Self {
+        self.incremental_fitness = incremental_fitness;
+        self
+    }
+
+    /// Score candidates with `score(candidate, target)`, e.g. to weight positions or give
+    /// partial credit. The target scored against itself is the perfect score.
+    pub fn fitness_fn(
+        mut self,
+        score: impl Fn(&str, &str) -> isize + Send + Sync + 'static,
+    ) -> Self {
+        self.fitness_mode = FitnessMode::custom(score);
+        self
+    }
+
+    /// Whether the fitness function rewards high or low scores. Defaults to maximizing;
+    /// minimizing needs a custom distance-like scorer, see `fitness_fn`, and `build`
+    /// rejects it with the built-in fitness modes.
+    pub fn objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        self.population_size = population_size;
+        self
+    }
+
+    pub fn num_fit_to_keep(mut self, num_fit_to_keep: usize) -> Self {
+        self.num_fit_to_keep = num_fit_to_keep;
+        self
+    }
+
+    /// Defaults to `num_fit_to_keep`, i.e. every survivor is carried over.
+    pub fn elite_count(mut self, elite_count: usize) -> Self {
+        self.elite_count = Some(elite_count);
+        self
+    }
+
+    /// Replace this fraction of the population every generation instead of everything but
+    /// the elite, see `GeneticAlgorithm::with_generation_gap`.
+    pub fn generation_gap(mut self, generation_gap: f64) -> Self {
+        self.generation_gap = Some(generation_gap);
+        self
+    }
+
+    pub fn mutation_prob(mut self, mutation_prob: f64) -> Self {
+        self.mutation_prob = mutation_prob;
+        self
+    }
+
+    /// Set mutated characters to the target's with this probability, see
+    /// `StringContext::hint_prob`. Defaults to 0, pure search.
+    pub fn hint_prob(mut self, hint_prob: f64) -> Self {
+        self.hint_prob = hint_prob;
+        self
+    }
+
+    /// The shortest random candidate to seed, see `StringContext::init_len`. Defaults to
+    /// the target's length, as does an unset maximum.
+    pub fn init_len_min(mut self, init_len_min: usize) -> Self {
+        self.init_len_min = Some(init_len_min);
+        self
+    }
+
+    /// The longest random candidate to seed. Defaults to the target's length, as does an
+    /// unset minimum.
+    pub fn init_len_max(mut self, init_len_max: usize) -> Self {
+        self.init_len_max = Some(init_len_max);
+        self
+    }
+
+    /// How genes are mutated; see `MutationOp`. Defaults to replacing characters.
+    pub fn mutation_op(mut self, mutation_op: MutationOp) -> Self {
+        self.mutation_op = mutation_op;
+        self
+    }
+
+    /// Cool the mutation probability down over the generations instead of keeping it
+    /// fixed; `mutation_prob` is then ignored.
+    pub fn mutation_schedule(mut self, mutation_schedule: Cooling) -> Self {
+        self.mutation_schedule = Some(mutation_schedule);
+        self
+    }
+
+    /// Let the mutation probability rise above `mutation_prob` during fitness plateaus.
+    pub fn adaptive_mutation(mut self, adaptive_mutation: AdaptiveMutation) -> Self {
+        self.adaptive_mutation = Some(adaptive_mutation);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Put these strings into the initial population, e.g. a previous run's best, and
+    /// fill the rest randomly. Strings are cut or randomly padded to the target length.
+    /// Ignored when resuming from a checkpoint.
+    pub fn seed_candidates<S: Into<String>>(
+        mut self,
+        seed_candidates: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.seed_candidates = seed_candidates.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Give up once the best fitness has not improved for this many generations.
+    pub fn patience(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Stop after this many generations, see `GeneticAlgorithm::solve`.
+    pub fn max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = Some(max_generations);
+        self
+    }
+
+    /// Reseed everything but the elite once the population has converged without progress,
+    /// see `RestartPolicy`.
+    pub fn restart(mut self, restart: RestartPolicy) -> Self {
+        self.restart = Some(restart);
+        self
+    }
+
+    /// Rank candidates by their fitness shared with their neighbors, see
+    /// `FitnessSharing`.
+    pub fn fitness_sharing(mut self, fitness_sharing: FitnessSharing) -> Self {
+        self.fitness_sharing = Some(fitness_sharing);
+        self
+    }
+
+    /// Keep the `size` best distinct candidates ever scored, see
+    /// `GeneticAlgorithm::hall_of_fame`. 0, the default, keeps none.
+    pub fn hall_of_fame(mut self, size: usize) -> Self {
+        self.hall_of_fame = size;
+        self
+    }
+
+    /// Record where every candidate came from, see `GeneticAlgorithm::with_lineage`.
+    pub fn lineage(mut self, lineage: bool) -> Self {
+        self.lineage = lineage;
+        self
+    }
+
+    pub fn fast_sort(mut self, fast_sort: bool) -> Self {
+        self.fast_sort = fast_sort;
+        self
+    }
+
+    pub fn batch(mut self, batch: bool) -> Self {
+        self.batch = batch;
+        self
+    }
+
+    /// Score each distinct candidate string only once, see
+    /// `GeneticAlgorithm::with_fitness_cache`.
+    pub fn fitness_cache(mut self, fitness_cache: bool) -> Self {
+        self.fitness_cache = fitness_cache;
+        self
+    }
+
+    pub fn selection(mut self, selection: SelectionStrategy) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    pub fn crossover(mut self, crossover: CrossoverMode) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    /// How many parents each child has, see `GeneticAlgorithm::with_num_parents`. More
+    /// than two need unbiased uniform crossover. Defaults to 2.
+    pub fn num_parents(mut self, num_parents: usize) -> Self {
+        self.num_parents = num_parents;
+        self
+    }
+
+    pub fn cull(mut self, cull: CullStrategy) -> Self {
+        self.cull = cull;
+        self
+    }
+
+    pub fn replacement(mut self, replacement: ReplacementMode) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// How candidates of equal fitness are ordered, see `TieBreak`.
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Continue a saved run instead of seeding a new population. The other settings should
+    /// match the ones the checkpoint was taken with; `seed` is ignored.
+    #[cfg(feature = "serde")]
+    pub fn resume(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    pub fn callback<C>(self, callback: C) -> GeneticAlgorithmBuilder<C>
+    where
+        C: Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>,
+    {
+        GeneticAlgorithmBuilder {
+            target: self.target,
+            alphabet: self.alphabet,
+            alphabet_weights: self.alphabet_weights,
+            allow_unreachable: self.allow_unreachable,
+            fitness_mode: self.fitness_mode,
+            ignore_case: self.ignore_case,
+            incremental_fitness: self.incremental_fitness,
+            objective: self.objective,
+            population_size: self.population_size,
+            num_fit_to_keep: self.num_fit_to_keep,
+            elite_count: self.elite_count,
+            generation_gap: self.generation_gap,
+            mutation_prob: self.mutation_prob,
+            hint_prob: self.hint_prob,
+            init_len_min: self.init_len_min,
+            init_len_max: self.init_len_max,
+            mutation_op: self.mutation_op,
+            mutation_schedule: self.mutation_schedule,
+            adaptive_mutation: self.adaptive_mutation,
+            seed: self.seed,
+            seed_candidates: self.seed_candidates,
+            patience: self.patience,
+            max_generations: self.max_generations,
+            restart: self.restart,
+            fitness_sharing: self.fitness_sharing,
+            hall_of_fame: self.hall_of_fame,
+            lineage: self.lineage,
+            fast_sort: self.fast_sort,
+            batch: self.batch,
+            fitness_cache: self.fitness_cache,
+            selection: self.selection,
+            crossover: self.crossover,
+            num_parents: self.num_parents,
+            cull: self.cull,
+            replacement: self.replacement,
+            tie_break: self.tie_break,
+            #[cfg(feature = "serde")]
+            checkpoint: self.checkpoint,
+            callback,
+        }
+    }
+
+    pub fn build(self) -> Result<GeneticAlgorithm<F>, GaError> {
+        let target_len = self.target.chars().count();
+        let init_len = match (self.init_len_min, self.init_len_max) {
+            (None, None) => None,
+            (min, max) => Some((min.unwrap_or(target_len), max.unwrap_or(target_len))),
+        };
+        let context = StringContext {
+            target: self.target,
+            alphabet: self.alphabet,
+            alphabet_weights: self.alphabet_weights,
+            mutation_op: self.mutation_op,
+            fitness_mode: self.fitness_mode,
+            ignore_case: self.ignore_case,
+            incremental_fitness: self.incremental_fitness,
+            hint_prob: self.hint_prob,
+            init_len,
+            hints: HintCount::default(),
+        };
+        match context.validate() {
+            Err(GaError::TargetNotInAlphabet { .. }) if self.allow_unreachable => {}
+            result => result?,
+        }
+        // The built-in modes score higher the closer a candidate is, so only a custom scorer
+        // can be minimized.
+        if self.objective == Objective::Minimize
+            && !matches!(context.fitness_mode, FitnessMode::Custom(_))
+        {
+            return Err(GaError::InvalidParameter(format!(
+                "minimizing needs a custom fitness function, got {} fitness",
+                context.fitness_mode
+            )));
+        }
+        let reachable_max_fitness = context.reachable_max_fitness();
+        if !(0.0..=1.0).contains(&self.mutation_prob) {
+            return Err(GaError::InvalidParameter(format!(
+                "mutation probability must be between 0 and 1, got {}",
+                self.mutation_prob
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.hint_prob) {
+            return Err(GaError::InvalidParameter(format!(
+                "hint probability must be between 0 and 1, got {}",
+                self.hint_prob
+            )));
+        }
+        if let CrossoverMode::Uniform { bias } = self.crossover {
+            if !(0.0..=1.0).contains(&bias) {
+                return Err(GaError::InvalidParameter(format!(
+                    "uniform crossover bias must be between 0 and 1, got {}",
+                    bias
+                )));
+            }
+        }
+        if self.num_parents == 0 {
+            return Err(GaError::InvalidParameter(
+                "breeding needs at least one parent".to_string(),
+            ));
+        }
+        // Only unbiased uniform crossover recombines more than two parents; asexual breeding
+        // takes one whatever the count.
+        if self.num_parents > 2
+            && ![CrossoverMode::default(), CrossoverMode::Asexual].contains(&self.crossover)
+        {
+            return Err(GaError::InvalidParameter(format!(
+                "{} parents need unbiased uniform crossover, got {}",
+                self.num_parents, self.crossover
+            )));
+        }
+        if let Some(mutation_schedule) = &self.mutation_schedule {
+            let (start, floor) = mutation_schedule.range();
+            if !(0.0..=1.0).contains(&start) || !(0.0..=start).contains(&floor) {
+                return Err(GaError::InvalidParameter(format!(
+                    "mutation schedule must cool down from at most 1 to at least 0, got {}",
+                    mutation_schedule
+                )));
+            }
+            if let Cooling::Exponential { decay, .. } = mutation_schedule {
+                if !(0.0..=1.0).contains(decay) {
+                    return Err(GaError::InvalidParameter(format!(
+                        "mutation schedule decay must be between 0 and 1, got {}",
+                        decay
+                    )));
+                }
+            }
+        }
+        if let Some(adaptive_mutation) = &self.adaptive_mutation {
+            if !(self.mutation_prob..=1.0).contains(&adaptive_mutation.max_prob) {
+                return Err(GaError::InvalidParameter(format!(
+                    "adaptive mutation maximum must be between the mutation probability and 1, got {}",
+                    adaptive_mutation.max_prob
+                )));
+            }
+            if adaptive_mutation.factor < 1.0 {
+                return Err(GaError::InvalidParameter(format!(
+                    "adaptive mutation factor must be at least 1, got {}",
+                    adaptive_mutation.factor
+                )));
+            }
+        }
+        if self.num_fit_to_keep < 2 {
+            return Err(GaError::InvalidParameter(format!(
+                "need to keep at least 2 candidates to breed, got {}",
+                self.num_fit_to_keep
+            )));
+        }
+        if self.num_fit_to_keep >= self.population_size {
+            return Err(GaError::InvalidParameter(format!(
+                "keep count ({}) must be smaller than the population size ({})",
+                self.num_fit_to_keep, self.population_size
+            )));
+        }
+        let elite_count = self.elite_count.unwrap_or(self.num_fit_to_keep);
+        if elite_count > self.num_fit_to_keep {
+            return Err(GaError::InvalidParameter(format!(
+                "elite count ({}) must not exceed the keep count ({})",
+                elite_count, self.num_fit_to_keep
+            )));
+        }
+        if let Some(generation_gap) = self.generation_gap {
+            if !(generation_gap > 0.0 && generation_gap <= 1.0) {
+                return Err(GaError::InvalidParameter(format!(
+                    "generation gap must be greater than 0 and at most 1, got {}",
+                    generation_gap
+                )));
+            }
+            if self.elite_count.is_some() {
+                return Err(GaError::InvalidParameter(
+                    "generation gap and elite count cannot be combined".to_string(),
+                ));
+            }
+        }
+        if self.cull.reserved() >= self.num_fit_to_keep {
+            return Err(GaError::InvalidParameter(format!(
+                "reserved diverse slots ({}) must be fewer than the keep count ({})",
+                self.cull.reserved(),
+                self.num_fit_to_keep
+            )));
+        }
+        if self.cull == (CullStrategy::Age { max_age: Some(0) }) {
+            return Err(GaError::InvalidParameter(
+                "maximum age must be positive".to_string(),
+            ));
+        }
+        if self.seed_candidates.len() > self.population_size {
+            return Err(GaError::InvalidParameter(format!(
+                "{} seed candidates do not fit into a population of {}",
+                self.seed_candidates.len(),
+                self.population_size
+            )));
+        }
+        for seed_candidate in &self.seed_candidates {
+            if let Some(character) = seed_candidate
+                .chars()
+                .find(|c| !context.alphabet.contains(*c))
+            {
+                return Err(GaError::InvalidParameter(format!(
+                    "seed candidate {:?} contains {:?}, which is not in the alphabet",
+                    seed_candidate, character
+                )));
+            }
+        }
+        if self.patience == Some(0) {
+            return Err(GaError::InvalidParameter(
+                "patience must be at least 1 generation".to_string(),
+            ));
+        }
+        if self.max_generations == Some(0) {
+            return Err(GaError::InvalidParameter(
+                "at least one generation must run".to_string(),
+            ));
+        }
+        if let Some(restart) = &self.restart {
+            if restart.patience == 0 {
+                return Err(GaError::InvalidParameter(
+                    "restart patience must be at least 1 generation".to_string(),
+                ));
+            }
+            if restart.min_diversity.is_nan() || restart.min_diversity < 0.0 {
+                return Err(GaError::InvalidParameter(format!(
+                    "minimum diversity must not be negative, got {}",
+                    restart.min_diversity
+                )));
+            }
+        }
+        if let Some(fitness_sharing) = &self.fitness_sharing {
+            if fitness_sharing.radius == 0 {
+                return Err(GaError::InvalidParameter(
+                    "sharing radius must be at least 1".to_string(),
+                ));
+            }
+            if !fitness_sharing.alpha.is_finite() || fitness_sharing.alpha <= 0.0 {
+                return Err(GaError::InvalidParameter(format!(
+                    "sharing alpha must be positive, got {}",
+                    fitness_sharing.alpha
+                )));
+            }
+        }
+        if self.selection == (SelectionStrategy::Tournament { k: 0 }) {
+            return Err(GaError::InvalidParameter(
+                "tournament size must be at least 1".to_string(),
+            ));
+        }
+        if let SelectionStrategy::Rank { pressure } = self.selection {
+            if !(1.0..=2.0).contains(&pressure) {
+                return Err(GaError::InvalidParameter(format!(
+                    "rank selection pressure must be between 1 and 2, got {}",
+                    pressure
+                )));
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        let genetic_algorithm = match self.checkpoint {
+            Some(checkpoint) => GeneticAlgorithm::from_checkpoint(
+                context,
+                self.num_fit_to_keep,
+                self.population_size,
+                self.mutation_prob,
+                checkpoint,
+                self.callback,
+            ),
+            None => GeneticAlgorithm::new(
+                context,
+                self.num_fit_to_keep,
+                self.population_size,
+                self.mutation_prob,
+                self.seed,
+                self.callback,
+            ),
+        };
+        #[cfg(not(feature = "serde"))]
+        let genetic_algorithm = GeneticAlgorithm::new(
+            context,
+            self.num_fit_to_keep,
+            self.population_size,
+            self.mutation_prob,
+            self.seed,
+            self.callback,
+        );
+        let mut genetic_algorithm = genetic_algorithm
+            .with_elite_count(elite_count)
+            .with_fast_sort(self.fast_sort)
+            .with_batch(self.batch)
+            .with_selection(self.selection)
+            .with_objective(self.objective)
+            .with_crossover(self.crossover)
+            .with_num_parents(self.num_parents)
+            .with_cull(self.cull)
+            .with_replacement(self.replacement)
+            .with_tie_break(self.tie_break);
+        if let Some(mutation_schedule) = self.mutation_schedule {
+            genetic_algorithm = genetic_algorithm.with_mutation_schedule(mutation_schedule);
+        }
+        if let Some(adaptive_mutation) = self.adaptive_mutation {
+            genetic_algorithm = genetic_algorithm.with_adaptive_mutation(adaptive_mutation);
+        }
+        if let Some(patience) = self.patience {
+            genetic_algorithm = genetic_algorithm.with_patience(patience);
+        }
+        if reachable_max_fitness != genetic_algorithm.max_fitness() {
+            genetic_algorithm = genetic_algorithm.with_target_fitness(reachable_max_fitness);
+        }
+        if let Some(max_generations) = self.max_generations {
+            genetic_algorithm = genetic_algorithm.with_max_generations(max_generations);
+        }
+        if let Some(generation_gap) = self.generation_gap {
+            genetic_algorithm = genetic_algorithm.with_generation_gap(generation_gap);
+        }
+        if let Some(restart) = self.restart {
+            genetic_algorithm = genetic_algorithm.with_restart(restart);
+        }
+        if let Some(fitness_sharing) = self.fitness_sharing {
+            genetic_algorithm = genetic_algorithm.with_fitness_sharing(fitness_sharing);
+        }
+        if self.hall_of_fame > 0 {
+            genetic_algorithm = genetic_algorithm.with_hall_of_fame(self.hall_of_fame);
+        }
+        if self.lineage {
+            genetic_algorithm = genetic_algorithm.with_lineage();
+        }
+        if self.fitness_cache {
+            genetic_algorithm = genetic_algorithm.with_fitness_cache();
+        }
+        // A resumed run already has its population.
+        if genetic_algorithm.population().is_empty() {
+            genetic_algorithm = genetic_algorithm.with_seed_candidates(self.seed_candidates);
+        }
+        Ok(genetic_algorithm)
+    }
+}
+
+/// The resolved settings, one `name: value` per line, in the syntax the command line takes.
+impl<F> fmt::Display for GeneticAlgorithmBuilder<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "target: {:?}", self.target)?;
+        writeln!(f, "alphabet: {:?}", self.alphabet)?;
+        match &self.alphabet_weights {
+            Some(alphabet_weights) => writeln!(f, "alphabet weights: {}", alphabet_weights)?,
+            None => writeln!(f, "alphabet weights: uniform")?,
+        }
+        writeln!(
+            f,
+            "unreachable target: {}",
+            if self.allow_unreachable {
+                "allowed"
+            } else {
+                "error"
+            }
+        )?;
+        writeln!(f, "fitness: {}", self.fitness_mode)?;
+        writeln!(
+            f,
+            "case: {}",
+            if self.ignore_case { "ignored" } else { "exact" }
+        )?;
+        writeln!(
+            f,
+            "incremental fitness: {}",
+            if self.incremental_fitness {
+                "on"
+            } else {
+                "off"
+            }
+        )?;
+        writeln!(
+            f,
+            "fitness cache: {}",
+            if self.fitness_cache { "on" } else { "off" }
+        )?;
+        writeln!(f, "objective: {}", self.objective)?;
+        writeln!(f, "population: {}", self.population_size)?;
+        writeln!(f, "keep: {}", self.num_fit_to_keep)?;
+        writeln!(
+            f,
+            "elite: {}",
+            self.elite_count.unwrap_or(self.num_fit_to_keep)
+        )?;
+        match self.generation_gap {
+            Some(generation_gap) => writeln!(f, "generation gap: {}", generation_gap)?,
+            None => writeln!(f, "generation gap: off")?,
+        }
+        writeln!(f, "mutation: {}", self.mutation_prob)?;
+        writeln!(f, "mutation operator: {}", self.mutation_op)?;
+        writeln!(f, "hint probability: {}", self.hint_prob)?;
+        match (self.init_len_min, self.init_len_max) {
+            (None, None) => writeln!(f, "initial length: target")?,
+            (min, max) => {
+                let bound =
+                    |len: Option<usize>| len.map_or("target".to_string(), |len| len.to_string());
+                writeln!(f, "initial length: {} to {}", bound(min), bound(max))?
+            }
+        }
+        match &self.mutation_schedule {
+            Some(mutation_schedule) => writeln!(f, "mutation schedule: {}", mutation_schedule)?,
+            None => writeln!(f, "mutation schedule: off")?,
+        }
+        match &self.adaptive_mutation {
+            Some(adaptive_mutation) => writeln!(
+                f,
+                "adaptive mutation: patience {}, max {}, factor {}",
+                adaptive_mutation.patience, adaptive_mutation.max_prob, adaptive_mutation.factor
+            )?,
+            None => writeln!(f, "adaptive mutation: off")?,
+        }
+        writeln!(f, "selection: {}", self.selection)?;
+        writeln!(f, "crossover: {}", self.crossover)?;
+        writeln!(f, "parents: {}", self.num_parents)?;
+        writeln!(f, "cull: {}", self.cull)?;
+        writeln!(f, "replacement: {}", self.replacement)?;
+        writeln!(f, "tie break: {}", self.tie_break)?;
+        if self.seed_candidates.is_empty() {
+            writeln!(f, "seed candidates: none")?;
+        } else {
+            let seed_candidates: Vec<String> = self
+                .seed_candidates
+                .iter()
+                .map(|seed_candidate| format!("{:?}", seed_candidate))
+                .collect();
+            writeln!(f, "seed candidates: {}", seed_candidates.join(", "))?;
+        }
+        match self.patience {
+            Some(patience) => writeln!(f, "patience: {}", patience)?,
+            None => writeln!(f, "patience: off")?,
+        }
+        match self.max_generations {
+            Some(max_generations) => writeln!(f, "max generations: {}", max_generations)?,
+            None => writeln!(f, "max generations: unlimited")?,
+        }
+        match &self.restart {
+            Some(restart) => writeln!(f, "restart: {}", restart)?,
+            None => writeln!(f, "restart: off")?,
+        }
+        match &self.fitness_sharing {
+            Some(fitness_sharing) => writeln!(f, "fitness sharing: {}", fitness_sharing)?,
+            None => writeln!(f, "fitness sharing: off")?,
+        }
+        match self.hall_of_fame {
+            0 => writeln!(f, "hall of fame: off")?,
+            size => writeln!(f, "hall of fame: {}", size)?,
+        }
+        writeln!(f, "lineage: {}", if self.lineage { "on" } else { "off" })?;
+        match self.seed {
+            Some(seed) => write!(f, "seed: {}", seed),
+            None => write!(f, "seed: random"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::candidate::Candidate;
+use crate::genome::Fitness;
+
+/// The map behind `FitnessCache`, boxed so the algorithm does not need `Hash` on every
+/// genome, only on those it caches.
+trait ScoreMap<G, S> {
+    fn get(&self, genome: &G) -> Option<S>;
+    fn insert(&mut self, genome: &G, fitness: S);
+    fn len(&self) -> usize;
+}
+
+impl<G: Hash + Eq + Clone, S: Copy> ScoreMap<G, S> for HashMap<G, S> {
+    fn get(&self, genome: &G) -> Option<S> {
+        HashMap::get(self, genome).copied()
+    }
+
+    fn insert(&mut self, genome: &G, fitness: S) {
+        HashMap::insert(self, genome.clone(), fitness);
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+/// Remembers the score of every genome seen so far, so identical genomes, e.g. survivors
+/// or children that inherited everything unchanged, are only scored once. Only worth it
+/// when scoring is more expensive than hashing the genome.
+pub struct FitnessCache<G: Fitness> {
+    scores: Box<dyn ScoreMap<G, G::Score>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<G: Fitness + Hash + Eq + 'static> FitnessCache<G> {
+    pub fn new() -> Self {
+        Self {
+            scores: Box::new(HashMap::<G, G::Score>::new()),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<G: Fitness + Hash + Eq + 'static> Default for FitnessCache<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Fitness> FitnessCache<G> {
+    /// The score of `genome`, computed only if it is not cached yet.
+    pub fn score(&mut self, genome: &G, context: &G::Context) -> G::Score {
+        match self.scores.get(genome) {
+            Some(fitness) => {
+                self.hits += 1;
+                fitness
+            }
+            None => {
+                let fitness = genome.fitness(context);
+                self.misses += 1;
+                self.scores.insert(genome, fitness);
+                fitness
+            }
+        }
+    }
+
+    /// Lookups answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Lookups that had to score the genome.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// The fraction of lookups answered from the cache, or 0 before the first lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+
+    /// Number of distinct genomes cached.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// `compute_fitness` through a cache: scores the first unscored candidate, returning
+/// whether there was one.
+pub fn compute_fitness_cached<G: Fitness>(
+    population: &mut [Candidate<G, G::Score>],
+    context: &G::Context,
+    cache: &mut FitnessCache<G>,
+) -> bool {
+    if let Some(candidate) = population.iter_mut().find(|c| c.fitness.is_none()) {
+        candidate.fitness = Some(cache.score(&candidate.genome, context));
+        true
+    } else {
+        false
+    }
+}
+
+/// `compute_fitness_batch` through a cache: cached genomes are looked up first and only
+/// the rest are scored, in parallel with the `parallel` feature.
+pub fn compute_fitness_batch_cached<G: Fitness>(
+    population: &mut [Candidate<G, G::Score>],
+    context: &G::Context,
+    cache: &mut FitnessCache<G>,
+) -> bool {
+    let mut scored = false;
+    let mut missing = Vec::new();
+    for (i, candidate) in population.iter_mut().enumerate() {
+        if candidate.fitness.is_some() {
+            continue;
+        }
+        scored = true;
+        match cache.scores.get(&candidate.genome) {
+            Some(fitness) => {
+                cache.hits += 1;
+                candidate.fitness = Some(fitness);
+            }
+            None => missing.push(i),
+        }
+    }
+    crate::algorithm::compute_fitness_batch(population, context);
+    for i in missing {
+        cache.misses += 1;
+        let fitness = population[i].fitness.expect("scored above");
+        cache.scores.insert(&population[i].genome, fitness);
+    }
+    scored
+}
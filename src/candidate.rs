@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use rand::prelude::*;
+
+use crate::alphabet::alphabet_distance;
+use crate::crossover::CrossoverMode;
+use crate::fitness::chars_match;
+use crate::genome::{Crossover, Fitness, Score};
+use crate::objective::Objective;
+
+// Cloned by hand with `alloc-counts`, to count the clones.
+#[cfg_attr(not(feature = "alloc-counts"), derive(Clone))]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candidate<G = String, S = isize> {
+    pub genome: G,
+    /// `None` until the candidate has been scored.
+    // Checkpoints saved before this was optional mark unscored candidates with -1; a
+    // genuine score of -1 read back is simply scored again.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "fitness_or_sentinel"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(deserialize = "S: Score + serde::Deserialize<'de>"))
+    )]
+    pub fitness: Option<S>,
+    /// Generations this candidate has been carried into; 0 for newborns.
+    // Missing from checkpoints saved before ages existed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub age: usize,
+    /// Identifies the candidate in the run's `Lineage`; 0 unless lineage is tracked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: u64,
+    /// Ids of the parents, the one crossover started from first; empty for seeds and
+    /// when lineage is not tracked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub parents: Vec<u64>,
+    /// How crowded the candidate's neighborhood is, see `apply_fitness_sharing`; 1 unless
+    /// fitness sharing is on.
+    #[cfg_attr(feature = "serde", serde(default = "no_crowding"))]
+    pub niche_count: f64,
+}
+
+#[cfg(feature = "serde")]
+fn no_crowding() -> f64 {
+    1.0
+}
+
+#[cfg(feature = "alloc-counts")]
+impl<G: Clone, S: Clone> Clone for Candidate<G, S> {
+    fn clone(&self) -> Self {
+        crate::alloc_counts::count_candidate_clone();
+        Self {
+            genome: self.genome.clone(),
+            fitness: self.fitness.clone(),
+            age: self.age,
+            id: self.id,
+            parents: self.parents.clone(),
+            niche_count: self.niche_count,
+        }
+    }
+}
+
+impl<G, S> Candidate<G, S> {
+    pub fn new(genome: G) -> Self {
+        Self {
+            genome,
+            fitness: None,
+            age: 0,
+            id: 0,
+            parents: Vec::new(),
+            niche_count: 1.0,
+        }
+    }
+}
+
+impl<G, S: Score> Candidate<G, S> {
+    /// What selection and culling rank by, higher being better: `Objective::key` of the
+    /// fitness, shared out by `niche_count` so that crowded candidates rank lower. `None`
+    /// until scored.
+    pub fn rank_key(&self, objective: Objective) -> Option<f64> {
+        objective.key(self.fitness).map(|key| {
+            let key = key.to_f64();
+            if key >= 0.0 {
+                key / self.niche_count
+            } else {
+                key * self.niche_count
+            }
+        })
+    }
+
+    /// `Greater` if this candidate ranks above `other` for selection and culling.
+    pub fn rank_cmp(&self, other: &Self, objective: Objective) -> Ordering {
+        self.rank_key(objective)
+            .partial_cmp(&other.rank_key(objective))
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<G: Fitness> Candidate<G, G::Score> {
+    pub fn set_fitness(&mut self, context: &G::Context) {
+        self.fitness = Some(self.genome.fitness(context));
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn fitness_or_sentinel<'de, D, S>(deserializer: D) -> Result<Option<S>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    S: Score + serde::Deserialize<'de>,
+{
+    let fitness: Option<S> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(fitness.filter(|&fitness| fitness.to_f64() != -1.0))
+}
+
+impl Candidate<String> {
+    /// The genome, with matching characters green and the others red once scored. With
+    /// `ignore_case` a letter in the other case counts as matching but is shown as evolved.
+    pub fn display_str(&self, target_str: &str, ignore_case: bool, color: bool) -> String {
+        if self.fitness.is_none() || !color {
+            return self.genome.clone();
+        }
+        let mut out = String::new();
+        let mut target_chars = target_str.chars();
+        for char in self.genome.chars() {
+            let matches = target_chars
+                .next()
+                .is_some_and(|target_char| chars_match(char, target_char, ignore_case));
+            if !matches {
+                out.push_str(&format!("\x1b[91m{}\x1b[0m", char));
+            } else {
+                out.push_str(&format!("\x1b[92m{}\x1b[0m", char));
+            }
+        }
+        out
+    }
+
+    /// Like `display_str` with color, but mismatches are shaded by how many steps through
+    /// `alphabet` they are from the target character: yellow when close, then orange, and
+    /// red when far away or not in the alphabet.
+    pub fn display_str_shaded(
+        &self,
+        target_str: &str,
+        alphabet: &str,
+        ignore_case: bool,
+    ) -> String {
+        if self.fitness.is_none() {
+            return self.genome.clone();
+        }
+        // The furthest any two characters can be apart when wrapping around.
+        let max_distance = (alphabet.chars().count() / 2).max(1);
+        let mut out = String::new();
+        let mut target_chars = target_str.chars();
+        for char in self.genome.chars() {
+            let color = match target_chars.next() {
+                Some(target_char) if chars_match(char, target_char, ignore_case) => "92",
+                Some(target_char) => match alphabet_distance(alphabet, char, target_char) {
+                    Some(distance) => shade(distance, max_distance),
+                    None => "91",
+                },
+                None => "91",
+            };
+            out.push_str(&format!("\x1b[{}m{}\x1b[0m", color, char));
+        }
+        out
+    }
+}
+
+/// The color of a mismatch `distance` steps from its target character: yellow for the
+/// nearest third of `max_distance`, orange for the middle one and red beyond.
+fn shade(distance: usize, max_distance: usize) -> &'static str {
+    if distance * 3 <= max_distance {
+        "93"
+    } else if distance * 3 <= max_distance * 2 {
+        "38;5;208"
+    } else {
+        "91"
+    }
+}
+
+/// Breeds a child of `parents`, see `Crossover::crossover_many`; one parent breeds
+/// asexually. The child is left unscored unless `Fitness::fitness_from_parent` can derive
+/// its fitness from the first parent, and records the distinct parents' ids if they have
+/// any. Panics without parents.
+pub fn breed<G: Crossover + Fitness>(
+    parents: &[&Candidate<G, G::Score>],
+    mode: CrossoverMode,
+    mutation_prob: f64,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> Candidate<G, G::Score> {
+    // One or two parents, by far the most common, need no list of their genomes.
+    let genome = match parents {
+        [only] => G::crossover_many(&[&only.genome], mode, mutation_prob, context, rng),
+        [first, second] => G::crossover_many(
+            &[&first.genome, &second.genome],
+            mode,
+            mutation_prob,
+            context,
+            rng,
+        ),
+        _ => {
+            let genomes: Vec<&G> = parents.iter().map(|parent| &parent.genome).collect();
+            G::crossover_many(&genomes, mode, mutation_prob, context, rng)
+        }
+    };
+    let mut child = Candidate::new(genome);
+    let first = parents[0];
+    if first.id != 0 {
+        for parent in parents {
+            if !child.parents.contains(&parent.id) {
+                child.parents.push(parent.id);
+            }
+        }
+    }
+    if let Some(parent_fitness) = first.fitness {
+        child.fitness = child
+            .genome
+            .fitness_from_parent(&first.genome, parent_fitness, context);
+    }
+    child
+}
+
+/// `breed` from the candidates at the `parents` indices of `population`.
+pub(crate) fn breed_at<G: Crossover + Fitness>(
+    population: &[Candidate<G, G::Score>],
+    parents: &[usize],
+    mode: CrossoverMode,
+    mutation_prob: f64,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> Candidate<G, G::Score> {
+    match *parents {
+        [i] => breed(&[&population[i]], mode, mutation_prob, context, rng),
+        [i, j] => breed(
+            &[&population[i], &population[j]],
+            mode,
+            mutation_prob,
+            context,
+            rng,
+        ),
+        _ => {
+            let borrowed: Vec<&Candidate<G, G::Score>> =
+                parents.iter().map(|&i| &population[i]).collect();
+            breed(&borrowed, mode, mutation_prob, context, rng)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand_chacha::ChaCha12Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::STATE;
+use crate::candidate::Candidate;
+use crate::genome::Score;
+
+/// A paused run, created by `GeneticAlgorithm::checkpoint` and resumed with
+/// `GeneticAlgorithm::from_checkpoint`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "G: Serialize, S: Serialize",
+    deserialize = "G: Deserialize<'de>, S: Score + Deserialize<'de>"
+))]
+pub struct Checkpoint<G = String, S = isize> {
+    pub(crate) population: Vec<Candidate<G, S>>,
+    pub(crate) state: STATE,
+    pub(crate) rng: ChaCha12Rng,
+    pub(crate) generation: usize,
+    // -1 in checkpoints saved before it was optional.
+    #[serde(deserialize_with = "crate::candidate::fitness_or_sentinel")]
+    pub(crate) best_fitness: Option<S>,
+    pub(crate) generations_since_improvement: usize,
+    pub(crate) effective_mutation_prob: f64,
+    // Missing from checkpoints saved before restarts existed.
+    #[serde(default)]
+    pub(crate) restarts: usize,
+    #[serde(default = "Vec::new")]
+    pub(crate) hall_of_fame: Vec<Candidate<G, S>>,
+}
+
+impl<G, S> Checkpoint<G, S> {
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+impl<G: Serialize, S: Serialize> Checkpoint<G, S> {
+    /// Writes the checkpoint as JSON, replacing `path` if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+}
+
+impl<G: DeserializeOwned, S: Score + DeserializeOwned> Checkpoint<G, S> {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
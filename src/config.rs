@@ -0,0 +1,234 @@
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::fs;
+#[cfg(feature = "serde")]
+use std::io;
+use std::ops::ControlFlow;
+#[cfg(feature = "serde")]
+use std::path::Path;
+#[cfg(feature = "serde")]
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer};
+
+use crate::alphabet::AlphabetWeights;
+use crate::builder::GeneticAlgorithmBuilder;
+use crate::candidate::Candidate;
+use crate::crossover::CrossoverMode;
+use crate::diversity::{CullStrategy, FitnessSharing, RestartPolicy};
+use crate::fitness::FitnessMode;
+use crate::mutation::{Cooling, MutationOp};
+use crate::objective::Objective;
+use crate::replacement::ReplacementMode;
+use crate::selection::SelectionStrategy;
+use crate::tie_break::TieBreak;
+
+/// Settings read from a TOML file. Keys are named like the command line options and
+/// take the same values, e.g.
+///
+/// ```toml
+/// target = "hello world"
+/// population = 100
+/// mutation = 0.1
+/// selection = "tournament:3"
+/// seed = 42
+/// ```
+///
+/// Every key is optional; `apply` only overrides the settings that are present. Reading
+/// files needs the `serde` feature.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(default, deny_unknown_fields, rename_all = "kebab-case")
+)]
+pub struct Config {
+    pub target: Option<String>,
+    pub alphabet: Option<String>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub alphabet_weights: Option<AlphabetWeights>,
+    pub allow_unreachable: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub fitness: Option<FitnessMode>,
+    pub ignore_case: Option<bool>,
+    pub incremental_fitness: Option<bool>,
+    pub fitness_cache: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub objective: Option<Objective>,
+    pub population: Option<usize>,
+    pub keep: Option<usize>,
+    pub elite: Option<usize>,
+    pub generation_gap: Option<f64>,
+    pub mutation: Option<f64>,
+    pub hint_prob: Option<f64>,
+    pub init_len_min: Option<usize>,
+    pub init_len_max: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub mutation_op: Option<MutationOp>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub mutation_schedule: Option<Cooling>,
+    pub seed: Option<u64>,
+    pub seed_candidates: Option<Vec<String>>,
+    pub patience: Option<usize>,
+    pub max_generations: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub restart: Option<RestartPolicy>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub fitness_sharing: Option<FitnessSharing>,
+    pub hall_of_fame: Option<usize>,
+    pub track_lineage: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub selection: Option<SelectionStrategy>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub crossover: Option<CrossoverMode>,
+    pub parents: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub cull: Option<CullStrategy>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub replacement: Option<ReplacementMode>,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "parsed"))]
+    pub tie_break: Option<TieBreak>,
+}
+
+/// Reads a string and parses it with `FromStr`, so that the file takes the same syntax as
+/// the command line.
+#[cfg(feature = "serde")]
+fn parsed<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+/// Parses the contents of a TOML file.
+#[cfg(feature = "serde")]
+impl FromStr for Config {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|err| err.to_string())
+    }
+}
+
+impl Config {
+    /// Reads a TOML file; unknown keys and invalid values are errors.
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The builder with every setting present in the file applied to it.
+    pub fn apply<F>(&self, builder: GeneticAlgorithmBuilder<F>) -> GeneticAlgorithmBuilder<F>
+    where
+        F: Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>,
+    {
+        let mut builder = builder;
+        if let Some(target) = &self.target {
+            builder = builder.target(target.as_str());
+        }
+        if let Some(alphabet) = &self.alphabet {
+            builder = builder.alphabet(alphabet.as_str());
+        }
+        if let Some(alphabet_weights) = &self.alphabet_weights {
+            builder = builder.alphabet_weights(alphabet_weights.clone());
+        }
+        if let Some(allow_unreachable) = self.allow_unreachable {
+            builder = builder.allow_unreachable(allow_unreachable);
+        }
+        if let Some(fitness) = &self.fitness {
+            builder = builder.fitness_mode(fitness.clone());
+        }
+        if let Some(ignore_case) = self.ignore_case {
+            builder = builder.ignore_case(ignore_case);
+        }
+        if let Some(incremental_fitness) = self.incremental_fitness {
+            builder = builder.incremental_fitness(incremental_fitness);
+        }
+        if let Some(fitness_cache) = self.fitness_cache {
+            builder = builder.fitness_cache(fitness_cache);
+        }
+        if let Some(objective) = self.objective {
+            builder = builder.objective(objective);
+        }
+        if let Some(population) = self.population {
+            builder = builder.population_size(population);
+        }
+        if let Some(keep) = self.keep {
+            builder = builder.num_fit_to_keep(keep);
+        }
+        if let Some(elite) = self.elite {
+            builder = builder.elite_count(elite);
+        }
+        if let Some(generation_gap) = self.generation_gap {
+            builder = builder.generation_gap(generation_gap);
+        }
+        if let Some(mutation) = self.mutation {
+            builder = builder.mutation_prob(mutation);
+        }
+        if let Some(hint_prob) = self.hint_prob {
+            builder = builder.hint_prob(hint_prob);
+        }
+        if let Some(init_len_min) = self.init_len_min {
+            builder = builder.init_len_min(init_len_min);
+        }
+        if let Some(init_len_max) = self.init_len_max {
+            builder = builder.init_len_max(init_len_max);
+        }
+        if let Some(mutation_op) = self.mutation_op {
+            builder = builder.mutation_op(mutation_op);
+        }
+        if let Some(mutation_schedule) = self.mutation_schedule {
+            builder = builder.mutation_schedule(mutation_schedule);
+        }
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(seed_candidates) = &self.seed_candidates {
+            builder = builder.seed_candidates(seed_candidates.iter().cloned());
+        }
+        if let Some(patience) = self.patience {
+            builder = builder.patience(patience);
+        }
+        if let Some(max_generations) = self.max_generations {
+            builder = builder.max_generations(max_generations);
+        }
+        if let Some(restart) = self.restart {
+            builder = builder.restart(restart);
+        }
+        if let Some(fitness_sharing) = self.fitness_sharing {
+            builder = builder.fitness_sharing(fitness_sharing);
+        }
+        if let Some(hall_of_fame) = self.hall_of_fame {
+            builder = builder.hall_of_fame(hall_of_fame);
+        }
+        if let Some(track_lineage) = self.track_lineage {
+            builder = builder.lineage(track_lineage);
+        }
+        if let Some(selection) = self.selection {
+            builder = builder.selection(selection);
+        }
+        if let Some(crossover) = self.crossover {
+            builder = builder.crossover(crossover);
+        }
+        if let Some(parents) = self.parents {
+            builder = builder.num_parents(parents);
+        }
+        if let Some(cull) = self.cull {
+            builder = builder.cull(cull);
+        }
+        if let Some(replacement) = self.replacement {
+            builder = builder.replacement(replacement);
+        }
+        if let Some(tie_break) = self.tie_break {
+            builder = builder.tie_break(tie_break);
+        }
+        builder
+    }
+}
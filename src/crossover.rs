@@ -0,0 +1,169 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::prelude::*;
+
+use crate::alphabet::Alphabet;
+use crate::objective::Objective;
+use crate::strategy::Strategy;
+
+/// How `breed` recombines the genes of two parents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CrossoverMode {
+    /// Every gene comes from parent A with probability `bias` and from parent B otherwise.
+    /// With a bias other than 0.5, parent A is the fitter of the two.
+    Uniform { bias: f64 },
+    /// The prefix before a random cut comes from parent A, the rest from parent B.
+    SinglePoint,
+    /// A random middle segment comes from parent B, the rest from parent A.
+    TwoPoint,
+    /// No recombination: the child is a mutated copy of a single parent.
+    Asexual,
+}
+
+/// Unbiased uniform crossover.
+impl Default for CrossoverMode {
+    fn default() -> Self {
+        CrossoverMode::Uniform { bias: 0.5 }
+    }
+}
+
+impl Strategy for CrossoverMode {
+    fn variants() -> Vec<Self> {
+        vec![
+            CrossoverMode::Uniform { bias: 0.7 },
+            CrossoverMode::SinglePoint,
+            CrossoverMode::TwoPoint,
+            CrossoverMode::Asexual,
+        ]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            CrossoverMode::Uniform { .. } => {
+                "Every gene comes from the fitter parent with that probability (default 0.5)"
+            }
+            CrossoverMode::SinglePoint => {
+                "The prefix before a random cut comes from one parent, the rest from the other"
+            }
+            CrossoverMode::TwoPoint => {
+                "A random middle segment comes from one parent, the rest from the other"
+            }
+            CrossoverMode::Asexual => {
+                "No recombination: the child is a mutated copy of a single parent"
+            }
+        }
+    }
+}
+
+impl CrossoverMode {
+    /// Whether parents selected with fitness `fitness_a` and `fitness_b` should trade
+    /// places, so that a biased uniform crossover favors the fitter one. Other modes keep
+    /// the order they were selected in.
+    pub fn swaps_parents<S: Ord>(
+        &self,
+        fitness_a: Option<S>,
+        fitness_b: Option<S>,
+        objective: Objective,
+    ) -> bool {
+        matches!(*self, CrossoverMode::Uniform { bias } if bias != 0.5)
+            && objective.is_better(fitness_b, fitness_a)
+    }
+}
+
+/// Parses `uniform`, `uniform:B` with a bias B between 0 and 1 toward the fitter parent,
+/// `single-point`, `two-point` or `asexual`.
+impl FromStr for CrossoverMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => Ok(CrossoverMode::default()),
+            "single-point" => Ok(CrossoverMode::SinglePoint),
+            "two-point" => Ok(CrossoverMode::TwoPoint),
+            "asexual" => Ok(CrossoverMode::Asexual),
+            _ => match s.strip_prefix("uniform:") {
+                Some(bias) => match bias.parse() {
+                    Ok(bias) if (0.0..=1.0).contains(&bias) => Ok(CrossoverMode::Uniform { bias }),
+                    _ => Err(format!(
+                        "uniform crossover bias must be between 0 and 1, got '{}'",
+                        bias
+                    )),
+                },
+                None => Err(format!("unknown crossover mode '{}'", s)),
+            },
+        }
+    }
+}
+
+/// The form `from_str` accepts, `uniform` without a bias.
+impl fmt::Display for CrossoverMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossoverMode::Uniform { bias } if *bias == 0.5 => f.write_str("uniform"),
+            CrossoverMode::Uniform { bias } => write!(f, "uniform:{}", bias),
+            CrossoverMode::SinglePoint => f.write_str("single-point"),
+            CrossoverMode::TwoPoint => f.write_str("two-point"),
+            CrossoverMode::Asexual => f.write_str("asexual"),
+        }
+    }
+}
+
+/// Takes the first `cut` characters of `parent_a` and the rest of `parent_b`.
+pub fn single_point(parent_a: &str, parent_b: &str, cut: usize) -> String {
+    parent_a
+        .chars()
+        .take(cut)
+        .chain(parent_b.chars().skip(cut))
+        .collect()
+}
+
+/// Takes the characters in `start..end` from `parent_b` and the rest from `parent_a`.
+pub fn two_point(parent_a: &str, parent_b: &str, start: usize, end: usize) -> String {
+    parent_a
+        .chars()
+        .take(start)
+        .chain(parent_b.chars().skip(start).take(end - start))
+        .chain(parent_a.chars().skip(end))
+        .collect()
+}
+
+/// Takes each character from one of the `parents` chosen uniformly at random among those
+/// long enough to have one there, as long as a randomly chosen parent.
+pub fn uniform_many(parents: &[impl AsRef<str>], rng: &mut impl Rng) -> String {
+    let genes: Vec<Vec<char>> = parents
+        .iter()
+        .map(|parent| parent.as_ref().chars().collect())
+        .collect();
+    let child_len = genes.choose(rng).map_or(0, Vec::len);
+    (0..child_len)
+        .map(|i| {
+            let num_donors = genes.iter().filter(|genes| genes.len() > i).count();
+            let donor = rng.gen_range(0..num_donors);
+            genes
+                .iter()
+                .filter_map(|genes| genes.get(i))
+                .nth(donor)
+                .copied()
+                .expect("the donor has a gene here")
+        })
+        .collect()
+}
+
+/// Replaces each character with a random one from `alphabet` with probability `mutation_prob`.
+pub fn mutate(
+    text: &str,
+    mutation_prob: f64,
+    alphabet: &(impl Alphabet + ?Sized),
+    rng: &mut impl Rng,
+) -> String {
+    text.chars()
+        .map(|c| {
+            if rng.gen_bool(mutation_prob) {
+                alphabet.random_char(rng)
+            } else {
+                c
+            }
+        })
+        .collect()
+}
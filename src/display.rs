@@ -0,0 +1,296 @@
+use std::io::{self, Write};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::candidate::Candidate;
+
+/// Number of terminal columns `text` takes up; wide characters such as CJK count twice
+/// and combining marks not at all.
+pub fn text_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Width of `text` as shown in a terminal, ignoring ANSI escape sequences such as the
+/// colors added by `display_str`.
+pub fn visible_len(text: &str) -> usize {
+    let mut visible = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // A CSI sequence ends with its first byte in `@`..=`~` after the `[`.
+            if chars.next() == Some('[') {
+                chars.by_ref().find(|c| ('@'..='~').contains(c));
+            }
+        } else {
+            visible.push(c);
+        }
+    }
+    text_width(&visible)
+}
+
+/// Pads `text` with spaces on both sides to `width` terminal columns. Text that is
+/// already as wide or wider is returned unchanged; see `fit_text` to cut it instead.
+pub fn center_text(text: &str, width: usize) -> String {
+    let text_width = visible_len(text);
+    if text_width >= width {
+        text.to_string()
+    } else {
+        let padding = width - text_width;
+        let pad_left = padding / 2;
+        let pad_right = padding - pad_left;
+        format!("{}{}{}", " ".repeat(pad_left), text, " ".repeat(pad_right))
+    }
+}
+
+/// `center_text` for plain text that never exceeds `width` columns: text that is too wide
+/// is cut and ends in `…`.
+pub fn fit_text(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return center_text(text, width);
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut fitted = String::new();
+    let mut fitted_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // Leave one column for the ellipsis.
+        if fitted_width + char_width > width - 1 {
+            break;
+        }
+        fitted.push(c);
+        fitted_width += char_width;
+    }
+    fitted.push('…');
+    // A wide character that did not fit may leave a column over.
+    center_text(&fitted, width)
+}
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+const GRID_INDENT: usize = 3;
+const GRID_GAP: usize = 2;
+
+/// A summary line like `best: 7/11 [############........]` for the fittest scored
+/// candidate out of `max_fitness`, or `best: -/11` with an empty bar before anything has
+/// been scored.
+pub fn render_progress(population: &[Candidate], max_fitness: isize) -> String {
+    let best = population.iter().filter_map(|c| c.fitness).max();
+    let filled = match (best, max_fitness) {
+        (Some(best), max) if max > 0 => {
+            (best.max(0) as usize * PROGRESS_BAR_WIDTH / max as usize).min(PROGRESS_BAR_WIDTH)
+        }
+        _ => 0,
+    };
+    format!(
+        "best: {}/{} [{}{}]",
+        best.map_or("-".to_string(), |best| best.to_string()),
+        max_fitness,
+        "#".repeat(filled),
+        ".".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+/// How many scored candidates fall in each of at most `max_buckets` equal ranges of
+/// fitness from 0 to `max_fitness`, e.g. `GeneticAlgorithm::max_fitness` split into one
+/// bucket per target character plus one. Fitness beyond `max_fitness` counts toward the
+/// last bucket, and negative fitness toward the first.
+pub fn fitness_histogram<G>(
+    population: &[Candidate<G>],
+    max_fitness: usize,
+    max_buckets: usize,
+) -> Vec<usize> {
+    let last = max_fitness.min(max_buckets.saturating_sub(1));
+    let mut counts = vec![0; last + 1];
+    for fitness in population.iter().filter_map(|c| c.fitness) {
+        let fitness = (fitness.max(0) as usize).min(max_fitness);
+        counts[fitness * last / max_fitness.max(1)] += 1;
+    }
+    counts
+}
+
+/// One line per bucket of `fitness_histogram` for `max_fitness`, highest fitness first and
+/// labeled with the lowest fitness it holds, like `11 |########     4`, indented like the
+/// grid rows. The longest bar fills what is left of `width` columns after the labels.
+pub fn render_histogram(counts: &[usize], max_fitness: usize, width: usize) -> Vec<String> {
+    let last = counts.len().saturating_sub(1).max(1);
+    let label_width = max_fitness.to_string().len();
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    let count_width = max_count.to_string().len();
+    let bar_width = width.saturating_sub(label_width + count_width + 3).max(1);
+    counts
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(bucket, &count)| {
+            // Round up so that every non-empty bucket shows.
+            let filled = if max_count == 0 {
+                0
+            } else {
+                (count * bar_width).div_ceil(max_count)
+            };
+            format!(
+                "{}{:>label_width$} |{:<bar_width$} {:>count_width$}",
+                " ".repeat(GRID_INDENT),
+                (bucket * max_fitness).div_ceil(last),
+                "#".repeat(filled),
+                count
+            )
+        })
+        .collect()
+}
+
+/// How many cells of `column_width` fit next to each other in `width` terminal columns when
+/// `num_grids` grids share the line, after indentation and gaps. Always at least one.
+pub fn columns_for_width(width: usize, column_width: usize, num_grids: usize) -> usize {
+    let num_grids = num_grids.max(1);
+    let grid_width = width.saturating_sub(GRID_GAP * (num_grids - 1)) / num_grids;
+    (grid_width.saturating_sub(GRID_INDENT) / column_width.max(1)).max(1)
+}
+
+/// The lines of one labeled grid, without printing them. Candidates at the `focus`
+/// indices are marked with `➤`; `ignore_case` colors letters matching in either case green.
+/// The progress bar counts toward `max_fitness`. With `shading`, the alphabet, mismatches are colored by their distance to the target
+/// character, see `Candidate::display_str_shaded`, instead of plain red. A `leader` gets a
+/// highlighted line of its own between the progress bar and the cells.
+#[allow(clippy::too_many_arguments)]
+pub fn render_grid(
+    population: &[Candidate],
+    focus: &[usize],
+    label: &str,
+    column_width: usize,
+    target_str: &str,
+    max_fitness: isize,
+    ignore_case: bool,
+    population_size: usize,
+    num_columns: usize,
+    color: bool,
+    shading: Option<&str>,
+    leader: Option<&Candidate>,
+) -> Vec<String> {
+    let show = |candidate: &Candidate| match shading {
+        Some(alphabet) if color => candidate.display_str_shaded(target_str, alphabet, ignore_case),
+        _ => candidate.display_str(target_str, ignore_case, color),
+    };
+    let mut lines = Vec::new();
+    let header = fit_text(label, column_width * num_columns);
+    if color {
+        lines.push(format!("\x1b[1m\x1b[96m{}\x1b[0m", header));
+    } else {
+        lines.push(header);
+    }
+    lines.push(String::new());
+    let progress = center_text(
+        &render_progress(population, max_fitness),
+        column_width * num_columns,
+    );
+    if color {
+        lines.push(format!("\x1b[92m{}\x1b[0m", progress));
+    } else {
+        lines.push(progress);
+    }
+    lines.push(String::new());
+    if let Some(leader) = leader {
+        let fitness = leader
+            .fitness
+            .map_or("-".to_string(), |fitness| fitness.to_string());
+        let line = if color {
+            format!(
+                "\x1b[1m\x1b[93mleader:\x1b[0m {} \x1b[1m{}/{}\x1b[0m",
+                show(leader),
+                fitness,
+                max_fitness
+            )
+        } else {
+            format!("leader: {} {}/{}", show(leader), fitness, max_fitness)
+        };
+        lines.push(center_text(&line, column_width * num_columns));
+        lines.push(String::new());
+    }
+    // Breeding can briefly grow the population past its nominal size; show those too.
+    let num_cells = population_size.max(population.len());
+    let num_rows = num_cells.div_ceil(num_columns);
+    let mut cells = vec![vec![String::new(); num_columns]; num_rows];
+
+    for i in 0..num_cells {
+        let row_idx = i % num_rows;
+        let col_idx = i / num_rows;
+
+        if i >= population.len() {
+            cells[row_idx][col_idx] = " ".repeat(column_width);
+            continue;
+        }
+
+        let prefix = if focus.contains(&i) { "➤ " } else { "  " };
+        let cell = format!("{}{}", prefix, show(&population[i]));
+        let padding = column_width.saturating_sub(visible_len(&cell));
+        cells[row_idx][col_idx] = format!("{}{}", cell, " ".repeat(padding));
+    }
+
+    for row in cells {
+        lines.push(format!("{}{}", " ".repeat(GRID_INDENT), row.join("")));
+    }
+    lines
+}
+
+/// Lays out grids from `render_grid` next to each other as one frame of text.
+pub fn render_grids(grids: &[Vec<String>]) -> String {
+    let mut frame = String::from("\n\n\n");
+    let widths: Vec<usize> = grids
+        .iter()
+        .map(|grid| grid.iter().map(|line| visible_len(line)).max().unwrap_or(0))
+        .collect();
+    let num_lines = grids.iter().map(Vec::len).max().unwrap_or(0);
+    for i in 0..num_lines {
+        let line: Vec<String> = grids
+            .iter()
+            .zip(&widths)
+            .map(|(grid, &width)| {
+                let text = grid.get(i).map_or("", String::as_str);
+                format!("{}{}", text, " ".repeat(width - visible_len(text)))
+            })
+            .collect();
+        frame.push_str(line.join(&" ".repeat(GRID_GAP)).trim_end());
+        frame.push('\n');
+    }
+    frame.push_str("\n\n");
+    frame
+}
+
+/// Writes a single grid to `out`, e.g. `std::io::stdout().lock()`; the terminal
+/// frontend's shortcut for `render_grid` and `render_grids`.
+#[allow(clippy::too_many_arguments)]
+pub fn display(
+    out: &mut impl Write,
+    population: &[Candidate],
+    focus: &[usize],
+    label: &str,
+    column_width: usize,
+    target_str: &str,
+    max_fitness: isize,
+    ignore_case: bool,
+    population_size: usize,
+    num_columns: usize,
+    color: bool,
+    shading: Option<&str>,
+    leader: Option<&Candidate>,
+) -> io::Result<()> {
+    write!(
+        out,
+        "{}",
+        render_grids(&[render_grid(
+            population,
+            focus,
+            label,
+            column_width,
+            target_str,
+            max_fitness,
+            ignore_case,
+            population_size,
+            num_columns,
+            color,
+            shading,
+            leader,
+        )])
+    )
+}
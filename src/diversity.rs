@@ -0,0 +1,290 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::candidate::Candidate;
+use crate::genome::{Distance, Score};
+use crate::objective::Objective;
+use crate::strategy::Strategy;
+
+/// Mean distance over all pairs of candidates; 0 when the population has converged on a
+/// single genome or has fewer than two candidates.
+pub fn population_diversity<G: Distance, S: Score>(population: &[Candidate<G, S>]) -> f64 {
+    let n = population.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let total: usize = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .map(|(i, j)| population[i].genome.distance(&population[j].genome))
+        .sum();
+    total as f64 / (n * (n - 1) / 2) as f64
+}
+
+/// Fitness sharing: a candidate's fitness is shared with every other candidate within
+/// `radius`, so that crowded regions of the search space rank lower.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FitnessSharing {
+    /// Distance below which two candidates share their fitness.
+    pub radius: usize,
+    /// How fast sharing falls off with distance: 1 is linear, higher shares more with
+    /// candidates that are further away.
+    pub alpha: f64,
+}
+
+/// Sets every candidate's `niche_count` to `1 + sum of sh(d)` over the other candidates,
+/// where `d` is their distance and `sh(d) = 1 - (d / radius)^alpha` for `d < radius`,
+/// 0 beyond. Selection and culling then rank by fitness divided by the niche count, see
+/// `Candidate::rank_key`, while the fitness itself stays the raw score.
+pub fn apply_fitness_sharing<G: Distance, S: Score>(
+    population: &mut [Candidate<G, S>],
+    radius: usize,
+    alpha: f64,
+) {
+    let n = population.len();
+    let mut niche_counts = vec![1.0; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let distance = population[i].genome.distance(&population[j].genome);
+            if distance < radius {
+                let share = 1.0 - (distance as f64 / radius as f64).powf(alpha);
+                niche_counts[i] += share;
+                niche_counts[j] += share;
+            }
+        }
+    }
+    for (candidate, niche_count) in population.iter_mut().zip(niche_counts) {
+        candidate.niche_count = niche_count;
+    }
+}
+
+/// Parses `R:A`, e.g. `3:1` to share fitness linearly between candidates less than 3
+/// apart.
+impl FromStr for FitnessSharing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (radius, alpha) = s
+            .split_once(':')
+            .ok_or_else(|| format!("fitness sharing must look like RADIUS:ALPHA, got '{}'", s))?;
+        let radius = match radius.parse() {
+            Ok(radius) if radius > 0 => radius,
+            _ => {
+                return Err(format!(
+                    "sharing radius must be a positive integer, got '{}'",
+                    radius
+                ))
+            }
+        };
+        let alpha = match alpha.parse::<f64>() {
+            Ok(alpha) if alpha > 0.0 => alpha,
+            _ => {
+                return Err(format!(
+                    "sharing alpha must be a positive number, got '{}'",
+                    alpha
+                ))
+            }
+        };
+        Ok(FitnessSharing { radius, alpha })
+    }
+}
+
+/// The form `from_str` accepts.
+impl fmt::Display for FitnessSharing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.radius, self.alpha)
+    }
+}
+
+/// Which candidates survive `RemoveUnfit`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CullStrategy {
+    /// The fittest candidates survive.
+    #[default]
+    Fitness,
+    /// The fittest candidates survive except for `reserved` slots, which go to the
+    /// candidates most different from the ones already kept, whatever their fitness.
+    Diversity { reserved: usize },
+    /// The fittest candidates survive, the younger ones first among equally fit ones.
+    /// With `max_age`, candidates that have reached it only survive if there are too few
+    /// younger ones.
+    Age { max_age: Option<usize> },
+}
+
+impl Strategy for CullStrategy {
+    fn variants() -> Vec<Self> {
+        vec![
+            CullStrategy::Fitness,
+            CullStrategy::Diversity { reserved: 2 },
+            CullStrategy::Age { max_age: Some(10) },
+        ]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            CullStrategy::Fitness => "The fittest candidates survive",
+            CullStrategy::Diversity { .. } => {
+                "The fittest survive, except for that many slots kept for the most distinct candidates"
+            }
+            CullStrategy::Age { .. } => {
+                "The fittest survive, younger first; those that reached the age only if needed"
+            }
+        }
+    }
+}
+
+impl CullStrategy {
+    /// Number of survivor slots not chosen by fitness.
+    pub fn reserved(&self) -> usize {
+        match *self {
+            CullStrategy::Fitness | CullStrategy::Age { .. } => 0,
+            CullStrategy::Diversity { reserved } => reserved,
+        }
+    }
+}
+
+/// Parses `fitness`, `diversity:N`, `age` or `age:N`, e.g. `diversity:2` or `age:10`.
+impl FromStr for CullStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "fitness" => Ok(CullStrategy::Fitness),
+            None if s == "age" => Ok(CullStrategy::Age { max_age: None }),
+            Some(("age", max_age)) => match max_age.parse() {
+                Ok(max_age) if max_age > 0 => Ok(CullStrategy::Age {
+                    max_age: Some(max_age),
+                }),
+                _ => Err(format!(
+                    "maximum age must be a positive integer, got '{}'",
+                    max_age
+                )),
+            },
+            Some(("diversity", reserved)) => match reserved.parse() {
+                Ok(reserved) => Ok(CullStrategy::Diversity { reserved }),
+                Err(_) => Err(format!(
+                    "number of reserved slots must be a non-negative integer, got '{}'",
+                    reserved
+                )),
+            },
+            _ => Err(format!("unknown cull strategy '{}'", s)),
+        }
+    }
+}
+
+/// The form `from_str` accepts.
+impl fmt::Display for CullStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CullStrategy::Fitness => write!(f, "fitness"),
+            CullStrategy::Diversity { reserved } => write!(f, "diversity:{}", reserved),
+            CullStrategy::Age { max_age: None } => write!(f, "age"),
+            CullStrategy::Age {
+                max_age: Some(max_age),
+            } => write!(f, "age:{}", max_age),
+        }
+    }
+}
+
+/// Moves the `reserved` candidates most distinct from the fittest survivors into the last
+/// survivor slots of a population sorted by fitness, so that culling from the back keeps
+/// them. Each slot goes to the candidate whose nearest already kept candidate is furthest
+/// away; ties go to the fitter one.
+pub fn promote_diverse<G: Distance, S: Score>(
+    population: &mut [Candidate<G, S>],
+    num_fit_to_keep: usize,
+    reserved: usize,
+) {
+    let num_fit_to_keep = num_fit_to_keep.min(population.len());
+    let first_reserved = num_fit_to_keep.saturating_sub(reserved);
+    for slot in first_reserved..num_fit_to_keep {
+        let most_distinct = (slot..population.len())
+            .max_by_key(|&i| {
+                let nearest = population[..slot]
+                    .iter()
+                    .map(|kept| kept.genome.distance(&population[i].genome))
+                    .min()
+                    .unwrap_or(usize::MAX);
+                (nearest, std::cmp::Reverse(i))
+            })
+            .unwrap();
+        population[slot..=most_distinct].rotate_right(1);
+    }
+}
+
+/// Reorders a population sorted by fitness so that culling from the back prefers young
+/// candidates: among equally fit candidates the younger come first, and those that have
+/// reached `max_age` go behind all others.
+pub fn promote_young<G, S: Score>(
+    population: &mut [Candidate<G, S>],
+    objective: Objective,
+    max_age: Option<usize>,
+) {
+    let expired = |c: &Candidate<G, S>| max_age.is_some_and(|max_age| c.age >= max_age);
+    population.sort_by(|a, b| {
+        expired(a)
+            .cmp(&expired(b))
+            .then_with(|| b.rank_cmp(a, objective))
+            .then(a.age.cmp(&b.age))
+    });
+}
+
+/// When to give up on a converged population: once its diversity is below `min_diversity`
+/// and the best fitness has not improved for `patience` generations, everything but the
+/// elite is replaced with fresh random candidates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RestartPolicy {
+    /// Mean pairwise distance, as measured by `population_diversity`, below which the
+    /// population counts as converged.
+    pub min_diversity: f64,
+    /// Generations without improvement before a converged population is restarted.
+    pub patience: usize,
+}
+
+impl RestartPolicy {
+    /// Whether a population with `diversity` should be restarted after
+    /// `generations_since_improvement` generations without progress.
+    pub fn should_restart(&self, diversity: f64, generations_since_improvement: usize) -> bool {
+        generations_since_improvement >= self.patience && diversity < self.min_diversity
+    }
+}
+
+/// Parses `D:P`, e.g. `1.5:20` to restart once the diversity is below 1.5 and nothing
+/// improved for 20 generations.
+impl FromStr for RestartPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (min_diversity, patience) = s.split_once(':').ok_or_else(|| {
+            format!(
+                "restart policy must look like DIVERSITY:PATIENCE, got '{}'",
+                s
+            )
+        })?;
+        let min_diversity = match min_diversity.parse::<f64>() {
+            Ok(min_diversity) if min_diversity >= 0.0 => min_diversity,
+            _ => {
+                return Err(format!(
+                    "minimum diversity must be a non-negative number, got '{}'",
+                    min_diversity
+                ))
+            }
+        };
+        let patience = patience.parse().map_err(|_| {
+            format!(
+                "restart patience must be a non-negative integer, got '{}'",
+                patience
+            )
+        })?;
+        Ok(RestartPolicy {
+            min_diversity,
+            patience,
+        })
+    }
+}
+
+/// The form `from_str` accepts.
+impl fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.min_diversity, self.patience)
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Why a configuration was rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GaError {
+    EmptyTarget,
+    EmptyAlphabet,
+    TargetNotInAlphabet {
+        character: char,
+        alphabet: String,
+    },
+    /// A numeric setting out of range or inconsistent with another one.
+    InvalidParameter(String),
+}
+
+impl fmt::Display for GaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GaError::EmptyTarget => write!(f, "target string must not be empty"),
+            GaError::EmptyAlphabet => write!(f, "alphabet must not be empty"),
+            GaError::TargetNotInAlphabet {
+                character,
+                alphabet,
+            } => write!(
+                f,
+                "target contains '{}', which is not in the alphabet \"{}\"",
+                character, alphabet
+            ),
+            GaError::InvalidParameter(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for GaError {}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::strategy::Strategy;
+
+/// A user-supplied scorer, called as `score(candidate, target)`.
+pub type FitnessFn = Arc<dyn Fn(&str, &str) -> isize + Send + Sync>;
+
+/// How a string candidate is scored against the target.
+#[derive(Clone, Default)]
+pub enum FitnessMode {
+    /// One point for every position where the candidate matches the target.
+    #[default]
+    PositionMatch,
+    /// Target length minus the edit distance to the target, so candidates of any length
+    /// can be compared and near-matches shifted by a character still score well.
+    Levenshtein,
+    /// Like `PositionMatch`, but a match at position `i` is worth `weights[i]` instead of
+    /// one point. Needs one non-negative weight per target character.
+    Weighted(Vec<isize>),
+    /// A custom scorer. The target scored against itself is taken as the perfect score
+    /// that ends the run.
+    Custom(FitnessFn),
+}
+
+impl Strategy for FitnessMode {
+    /// One value of every variant that can be parsed, with example parameters, to list
+    /// the choices.
+    fn variants() -> Vec<Self> {
+        vec![
+            FitnessMode::PositionMatch,
+            FitnessMode::Levenshtein,
+            FitnessMode::Weighted(vec![3, 2, 1]),
+        ]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            FitnessMode::PositionMatch => "One point for every position matching the target",
+            FitnessMode::Levenshtein => "Target length minus the edit distance to the target",
+            FitnessMode::Weighted(_) => "Like position, with one weight per target position",
+            FitnessMode::Custom(_) => "A scorer supplied through the library",
+        }
+    }
+}
+
+impl FitnessMode {
+    pub fn custom(score: impl Fn(&str, &str) -> isize + Send + Sync + 'static) -> Self {
+        FitnessMode::Custom(Arc::new(score))
+    }
+}
+
+impl fmt::Debug for FitnessMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitnessMode::PositionMatch => write!(f, "PositionMatch"),
+            FitnessMode::Levenshtein => write!(f, "Levenshtein"),
+            FitnessMode::Weighted(weights) => f.debug_tuple("Weighted").field(weights).finish(),
+            FitnessMode::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Custom scorers are equal only if they are the same closure.
+impl PartialEq for FitnessMode {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FitnessMode::PositionMatch, FitnessMode::PositionMatch)
+            | (FitnessMode::Levenshtein, FitnessMode::Levenshtein) => true,
+            (FitnessMode::Weighted(a), FitnessMode::Weighted(b)) => a == b,
+            (FitnessMode::Custom(a), FitnessMode::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Parses `position`, `levenshtein` or `weighted:W,W,...` with one weight per target
+/// character, e.g. `weighted:5,4,3,2,1`.
+impl FromStr for FitnessMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "position" => Ok(FitnessMode::PositionMatch),
+            None if s == "levenshtein" => Ok(FitnessMode::Levenshtein),
+            Some(("weighted", weights)) => weights
+                .split(',')
+                .map(|weight| match weight.trim().parse() {
+                    Ok(weight) if weight >= 0 => Ok(weight),
+                    _ => Err(format!(
+                        "weights must be non-negative integers, got '{}'",
+                        weight
+                    )),
+                })
+                .collect::<Result<_, _>>()
+                .map(FitnessMode::Weighted),
+            _ => Err(format!("unknown fitness mode '{}'", s)),
+        }
+    }
+}
+
+/// The form `from_str` accepts, or `custom`, which it does not.
+impl fmt::Display for FitnessMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitnessMode::PositionMatch => write!(f, "position"),
+            FitnessMode::Levenshtein => write!(f, "levenshtein"),
+            FitnessMode::Weighted(weights) => {
+                let weights: Vec<String> = weights.iter().map(|w| w.to_string()).collect();
+                write!(f, "weighted:{}", weights.join(","))
+            }
+            FitnessMode::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
+
+/// Whether `a` and `b` are the same character, or the same letter in either case if
+/// `ignore_case`.
+pub fn chars_match(a: char, b: char, ignore_case: bool) -> bool {
+    a == b || ignore_case && fold_char(a) == fold_char(b)
+}
+
+/// `text` in lowercase, one character per character so that positions still line up;
+/// characters whose lowercase form is longer are kept as they are.
+pub fn fold_case(text: &str) -> String {
+    text.chars().map(fold_char).collect()
+}
+
+fn fold_char(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+/// The fitness of `text` against `target_str` under `mode`.
+pub(crate) fn score(text: &str, target_str: &str, mode: &FitnessMode) -> isize {
+    match mode {
+        FitnessMode::PositionMatch => position_match(text, target_str),
+        FitnessMode::Levenshtein => {
+            let distance = levenshtein(text, target_str) as isize;
+            (target_str.chars().count() as isize - distance).max(0)
+        }
+        FitnessMode::Weighted(weights) => weighted_position_match(text, target_str, weights),
+        FitnessMode::Custom(score) => score(text, target_str),
+    }
+}
+
+/// The best fitness `mode` can give any text spelled from `alphabet`: the score of the
+/// target with every character missing from the alphabet replaced by one from it. For
+/// positional matching that is the target length less the positions the alphabet cannot
+/// spell. 0 for an empty alphabet.
+pub fn max_fitness(target: &str, mode: &FitnessMode, alphabet: &str) -> isize {
+    let Some(substitute) = alphabet.chars().next() else {
+        return 0;
+    };
+    let closest: String = target
+        .chars()
+        .map(|c| if alphabet.contains(c) { c } else { substitute })
+        .collect();
+    score(&closest, target, mode)
+}
+
+pub fn position_match(text: &str, target_str: &str) -> isize {
+    text.chars()
+        .zip(target_str.chars())
+        .filter(|(c, t)| c == t)
+        .count() as isize
+}
+
+/// The sum of `weights[i]` over the positions `i` where `text` matches `target_str`.
+/// With every weight 1 this is `position_match`.
+pub fn weighted_position_match(text: &str, target_str: &str, weights: &[isize]) -> isize {
+    text.chars()
+        .zip(target_str.chars())
+        .zip(weights)
+        .filter(|((c, t), _)| c == t)
+        .map(|(_, weight)| weight)
+        .sum()
+}
+
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, char_a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &char_b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(char_a != char_b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
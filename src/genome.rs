@@ -0,0 +1,509 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+use rand::prelude::*;
+
+use crate::alphabet::{Alphabet, AlphabetWeights};
+use crate::crossover::{single_point, two_point, uniform_many, CrossoverMode};
+use crate::error::GaError;
+use crate::fitness::{chars_match, fold_case, max_fitness, score, FitnessMode};
+use crate::mutation::{hint, MutationOp};
+use crate::LETTERS;
+
+/// `Send + Sync` when the `parallel` feature is enabled, so genomes and their context can
+/// be scored from several threads; a no-op bound otherwise.
+#[cfg(feature = "parallel")]
+pub trait ThreadSafe: Send + Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync> ThreadSafe for T {}
+
+#[cfg(not(feature = "parallel"))]
+pub trait ThreadSafe {}
+#[cfg(not(feature = "parallel"))]
+impl<T> ThreadSafe for T {}
+
+/// A genome the algorithm can evolve. `Context` is whatever the operators need to know
+/// about the problem, e.g. the target string.
+pub trait Genome: Clone + ThreadSafe {
+    type Context: ThreadSafe;
+
+    /// Create a random genome to seed the population with.
+    fn random(context: &Self::Context, rng: &mut impl Rng) -> Self;
+
+    /// A fixed order among genomes, used by `TieBreak::Lexical`. Genomes without a natural
+    /// one compare equal and keep their order.
+    fn lexical_cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+/// How two parents are combined into a child, including mutation. Genomes that only
+/// support one kind of recombination may ignore `mode`.
+pub trait Crossover: Genome {
+    fn crossover(
+        &self,
+        other: &Self,
+        mode: CrossoverMode,
+        mutation_prob: f64,
+        context: &Self::Context,
+        rng: &mut impl Rng,
+    ) -> Self;
+
+    /// Combines any number of parents, crossover starting from the first. A single parent
+    /// is copied and mutated as with `CrossoverMode::Asexual`; genomes that cannot
+    /// recombine more than two parents breed from the first two.
+    fn crossover_many(
+        parents: &[&Self],
+        mode: CrossoverMode,
+        mutation_prob: f64,
+        context: &Self::Context,
+        rng: &mut impl Rng,
+    ) -> Self {
+        crossover_first_two(parents, mode, mutation_prob, context, rng)
+    }
+}
+
+/// What `Crossover::crossover_many` does unless a genome knows better.
+fn crossover_first_two<G: Crossover>(
+    parents: &[&G],
+    mode: CrossoverMode,
+    mutation_prob: f64,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> G {
+    match parents {
+        [only] => only.crossover(only, CrossoverMode::Asexual, mutation_prob, context, rng),
+        [first, second, ..] => first.crossover(second, mode, mutation_prob, context, rng),
+        [] => panic!("breeding needs at least one parent"),
+    }
+}
+
+/// What fitness is measured in. Only the order matters for finding the best candidate;
+/// `to_f64` gives the magnitude that statistics, roulette selection and fitness sharing
+/// need.
+pub trait Score: Ord + Copy + fmt::Debug + fmt::Display + ThreadSafe {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_score {
+    ($($int:ty),*) => {
+        $(impl Score for $int {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_score!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// How a genome is scored. Higher is better unless the algorithm minimizes, see
+/// `Objective`.
+pub trait Fitness: Genome {
+    type Score: Score;
+
+    fn fitness(&self, context: &Self::Context) -> Self::Score;
+
+    /// The score of a perfect solution; the run stops once it is reached.
+    fn max_fitness(context: &Self::Context) -> Self::Score;
+
+    /// The fitness of `self`, a child of `parent`, worked out from the parent's
+    /// `parent_fitness` by looking only at what changed, or `None` when that is not
+    /// possible and the child has to be scored in full.
+    fn fitness_from_parent(
+        &self,
+        _parent: &Self,
+        _parent_fitness: Self::Score,
+        _context: &Self::Context,
+    ) -> Option<Self::Score> {
+        None
+    }
+}
+
+/// How different two genomes are, used to keep the population diverse. Zero means equal.
+pub trait Distance: Genome {
+    fn distance(&self, other: &Self) -> usize;
+}
+
+/// What the string operators need to know: the target, the characters genes are drawn
+/// from, how to mutate them and how to score against the target.
+#[derive(Clone, Debug)]
+pub struct StringContext {
+    pub target: String,
+    pub alphabet: String,
+    /// How likely each alphabet character is to be drawn; `None` draws them uniformly.
+    pub alphabet_weights: Option<AlphabetWeights>,
+    pub mutation_op: MutationOp,
+    pub fitness_mode: FitnessMode,
+    /// Score letters as matching whatever their case; custom scorers see both strings
+    /// lowercased.
+    pub ignore_case: bool,
+    /// Score children from their first parent's fitness where the fitness mode allows it,
+    /// see `Fitness::fitness_from_parent`.
+    pub incremental_fitness: bool,
+    /// Chance that a mutated character is set to the target character at its position
+    /// instead of what the mutation operator chose. A shortcut for demos: 0, the default,
+    /// is pure search.
+    pub hint_prob: f64,
+    /// Lengths, from the first to the second inclusive, that random genomes are drawn
+    /// from, so variable-length search starts out with lengths to choose from; `None`
+    /// seeds the target's length.
+    pub init_len: Option<(usize, usize)>,
+    /// Characters set by hinting so far.
+    pub hints: HintCount,
+}
+
+/// Counts the characters `StringContext::hint_prob` set to the target. A clone starts at
+/// the count of the original and counts on its own from there.
+#[derive(Debug, Default)]
+pub struct HintCount(AtomicUsize);
+
+impl HintCount {
+    pub fn get(&self) -> usize {
+        self.0.load(AtomicOrdering::Relaxed)
+    }
+
+    fn add(&self, hints: usize) {
+        self.0.fetch_add(hints, AtomicOrdering::Relaxed);
+    }
+}
+
+impl Clone for HintCount {
+    fn clone(&self) -> Self {
+        HintCount(AtomicUsize::new(self.get()))
+    }
+}
+
+impl StringContext {
+    pub fn new(target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            alphabet: LETTERS.to_string(),
+            alphabet_weights: None,
+            mutation_op: MutationOp::default(),
+            fitness_mode: FitnessMode::default(),
+            ignore_case: false,
+            incremental_fitness: false,
+            hint_prob: 0.0,
+            init_len: None,
+            hints: HintCount::default(),
+        }
+    }
+
+    /// Score candidates with `score(candidate, target)` instead of a built-in mode.
+    pub fn with_fitness_fn(
+        mut self,
+        score: impl Fn(&str, &str) -> isize + Send + Sync + 'static,
+    ) -> Self {
+        self.fitness_mode = FitnessMode::custom(score);
+        self
+    }
+
+    pub fn with_alphabet(mut self, alphabet: impl Into<String>) -> Self {
+        self.alphabet = alphabet.into();
+        self
+    }
+
+    pub fn with_alphabet_weights(mut self, alphabet_weights: AlphabetWeights) -> Self {
+        self.alphabet_weights = Some(alphabet_weights);
+        self
+    }
+
+    pub fn with_mutation_op(mut self, mutation_op: MutationOp) -> Self {
+        self.mutation_op = mutation_op;
+        self
+    }
+
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn with_incremental_fitness(mut self, incremental_fitness: bool) -> Self {
+        self.incremental_fitness = incremental_fitness;
+        self
+    }
+
+    pub fn with_hint_prob(mut self, hint_prob: f64) -> Self {
+        self.hint_prob = hint_prob;
+        self
+    }
+
+    pub fn with_init_len(mut self, min: usize, max: usize) -> Self {
+        self.init_len = Some((min, max));
+        self
+    }
+
+    /// Applies the mutation operator to `text`, then hints if `hint_prob` is set.
+    fn mutate(&self, text: &str, mutation_prob: f64, rng: &mut impl Rng) -> String {
+        let mutated = self.mutation_op.apply(text, mutation_prob, self, rng);
+        if self.hint_prob == 0.0 {
+            return mutated;
+        }
+        let (hinted, hints) = hint(text, &mutated, &self.target, self.hint_prob, rng);
+        self.hints.add(hints);
+        hinted
+    }
+
+    /// Whether some character of the alphabet matches `target_char`.
+    fn can_spell(&self, target_char: char) -> bool {
+        self.alphabet
+            .chars()
+            .any(|c| chars_match(c, target_char, self.ignore_case))
+    }
+
+    /// `text` cut or padded to the target length, padding with random characters from the
+    /// alphabet.
+    pub fn fit_to_target(&self, text: &str, rng: &mut impl Rng) -> String {
+        let target_len = self.target.chars().count();
+        let mut fitted: String = text.chars().take(target_len).collect();
+        for _ in text.chars().count()..target_len {
+            fitted.push(self.random_char(rng));
+        }
+        fitted
+    }
+
+    /// Target characters missing from the alphabet, which no candidate can ever match.
+    pub fn unreachable_chars(&self) -> Vec<char> {
+        let mut missing = Vec::new();
+        for c in self.target.chars() {
+            if !self.can_spell(c) && !missing.contains(&c) {
+                missing.push(c);
+            }
+        }
+        missing
+    }
+
+    /// The best fitness any candidate can reach: that of the target with every character
+    /// missing from the alphabet replaced by one from it. Equals `String::max_fitness`
+    /// when the whole target can be spelled.
+    pub fn reachable_max_fitness(&self) -> isize {
+        if self.ignore_case {
+            max_fitness(
+                &fold_case(&self.target),
+                &self.fitness_mode,
+                &fold_case(&self.alphabet),
+            )
+        } else {
+            max_fitness(&self.target, &self.fitness_mode, &self.alphabet)
+        }
+    }
+
+    /// Checks that the target is non-empty and reachable, since the string operators draw
+    /// genes from the alphabet and panic if it is empty, that fitness and alphabet weights
+    /// fit it, and that the initial length range is not empty.
+    pub fn validate(&self) -> Result<(), GaError> {
+        if self.target.is_empty() {
+            return Err(GaError::EmptyTarget);
+        }
+        if self.alphabet.is_empty() {
+            return Err(GaError::EmptyAlphabet);
+        }
+        if let Some(AlphabetWeights(weights)) = &self.alphabet_weights {
+            let alphabet_len = self.alphabet.chars().count();
+            if weights.len() != alphabet_len {
+                return Err(GaError::InvalidParameter(format!(
+                    "need one alphabet weight per alphabet character ({}), got {}",
+                    alphabet_len,
+                    weights.len()
+                )));
+            }
+            if weights.iter().all(|&weight| weight == 0) {
+                return Err(GaError::InvalidParameter(
+                    "alphabet weights must not all be zero".to_string(),
+                ));
+            }
+        }
+        if let FitnessMode::Weighted(weights) = &self.fitness_mode {
+            let target_len = self.target.chars().count();
+            if weights.len() != target_len {
+                return Err(GaError::InvalidParameter(format!(
+                    "need one fitness weight per target character ({}), got {}",
+                    target_len,
+                    weights.len()
+                )));
+            }
+            if weights.iter().any(|&weight| weight < 0) {
+                return Err(GaError::InvalidParameter(
+                    "fitness weights must not be negative".to_string(),
+                ));
+            }
+        }
+        if let Some((min, max)) = self.init_len {
+            if min == 0 || min > max {
+                return Err(GaError::InvalidParameter(format!(
+                    "initial length must be at least 1 with the minimum ({}) at most the maximum ({})",
+                    min, max
+                )));
+            }
+        }
+        // Checked last, so that callers tolerating an unreachable target know everything
+        // else is valid when this is the error.
+        if let Some(character) = self.target.chars().find(|&c| !self.can_spell(c)) {
+            return Err(GaError::TargetNotInAlphabet {
+                character,
+                alphabet: self.alphabet.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Genome for String {
+    type Context = StringContext;
+
+    fn random(context: &StringContext, rng: &mut impl Rng) -> Self {
+        let len = match context.init_len {
+            Some((min, max)) => rng.gen_range(min..=max),
+            None => context.target.chars().count(),
+        };
+        (0..len).map(|_| context.random_char(rng)).collect()
+    }
+
+    fn lexical_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+impl Crossover for String {
+    fn crossover(
+        &self,
+        other: &Self,
+        mode: CrossoverMode,
+        mutation_prob: f64,
+        context: &StringContext,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let genes_a: Vec<char> = self.chars().collect();
+        let genes_b: Vec<char> = other.chars().collect();
+        let shared_len = genes_a.len().min(genes_b.len());
+        let bias = match mode {
+            CrossoverMode::Uniform { bias } => bias,
+            CrossoverMode::SinglePoint => {
+                let cut = rng.gen_range(0..=shared_len);
+                let child = single_point(self, other, cut);
+                return context.mutate(&child, mutation_prob, rng);
+            }
+            CrossoverMode::TwoPoint => {
+                let a = rng.gen_range(0..=shared_len);
+                let b = rng.gen_range(0..=shared_len);
+                let child = two_point(self, other, a.min(b), a.max(b));
+                return context.mutate(&child, mutation_prob, rng);
+            }
+            CrossoverMode::Asexual => return context.mutate(self, mutation_prob, rng),
+        };
+        // Parents of different lengths pass the length of one of them on to the child.
+        let child_len = if genes_a.len() == genes_b.len() || rng.gen_bool(0.5) {
+            genes_a.len()
+        } else {
+            genes_b.len()
+        };
+        // Every gene is inherited first and only then mutated, so `mutation_prob` is
+        // the chance that a gene is redrawn, whatever the parents carry.
+        let child: String = (0..child_len)
+            .map(|i| match (genes_a.get(i), genes_b.get(i)) {
+                (Some(&char_a), Some(&char_b)) => {
+                    if rng.gen_bool(bias) {
+                        char_a
+                    } else {
+                        char_b
+                    }
+                }
+                (Some(&char_only), None) | (None, Some(&char_only)) => char_only,
+                (None, None) => unreachable!(),
+            })
+            .collect();
+        context.mutate(&child, mutation_prob, rng)
+    }
+
+    /// Uniform crossover of more than two parents samples every gene from any of them
+    /// alike, see `uniform_many`, whatever its bias.
+    fn crossover_many(
+        parents: &[&Self],
+        mode: CrossoverMode,
+        mutation_prob: f64,
+        context: &StringContext,
+        rng: &mut impl Rng,
+    ) -> Self {
+        match mode {
+            CrossoverMode::Uniform { .. } if parents.len() > 2 => {
+                let child = uniform_many(parents, rng);
+                context.mutate(&child, mutation_prob, rng)
+            }
+            _ => crossover_first_two(parents, mode, mutation_prob, context, rng),
+        }
+    }
+}
+
+/// Hamming distance; characters past the end of the shorter string all count as different.
+impl Distance for String {
+    fn distance(&self, other: &Self) -> usize {
+        let mismatches = self
+            .chars()
+            .zip(other.chars())
+            .filter(|(a, b)| a != b)
+            .count();
+        mismatches + self.chars().count().abs_diff(other.chars().count())
+    }
+}
+
+impl Fitness for String {
+    type Score = isize;
+
+    fn fitness(&self, context: &StringContext) -> isize {
+        let (text, target) = if context.ignore_case {
+            (
+                Cow::Owned(fold_case(self)),
+                Cow::Owned(fold_case(&context.target)),
+            )
+        } else {
+            (
+                Cow::Borrowed(self.as_str()),
+                Cow::Borrowed(context.target.as_str()),
+            )
+        };
+        score(&text, &target, &context.fitness_mode)
+    }
+
+    /// Positional and weighted matching only change where the child differs from its
+    /// parent, as long as both have the same length.
+    fn fitness_from_parent(
+        &self,
+        parent: &Self,
+        parent_fitness: isize,
+        context: &StringContext,
+    ) -> Option<isize> {
+        if !context.incremental_fitness {
+            return None;
+        }
+        let weights = match &context.fitness_mode {
+            FitnessMode::PositionMatch => None,
+            FitnessMode::Weighted(weights) => Some(weights),
+            FitnessMode::Levenshtein | FitnessMode::Custom(_) => return None,
+        };
+        if self.chars().count() != parent.chars().count() {
+            return None;
+        }
+        let mut fitness = parent_fitness;
+        let changes = self
+            .chars()
+            .zip(parent.chars())
+            .zip(context.target.chars())
+            .enumerate()
+            .filter(|(_, ((c, p), _))| c != p);
+        for (i, ((c, p), t)) in changes {
+            let weight = weights.map_or(1, |weights| weights[i]);
+            let gained = isize::from(chars_match(c, t, context.ignore_case));
+            let lost = isize::from(chars_match(p, t, context.ignore_case));
+            fitness += weight * (gained - lost);
+        }
+        Some(fitness)
+    }
+
+    fn max_fitness(context: &StringContext) -> isize {
+        // The target spells itself.
+        max_fitness(&context.target, &context.fitness_mode, &context.target)
+    }
+}
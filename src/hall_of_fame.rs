@@ -0,0 +1,31 @@
+use crate::candidate::Candidate;
+use crate::genome::{Distance, Score};
+use crate::objective::Objective;
+
+/// Adds a scored `candidate` to a hall of fame kept best first, unless an equal genome is
+/// already in it, and drops whatever no longer fits into `size` places. Among equally fit
+/// candidates the one admitted first stays ahead. Returns whether the candidate got in.
+pub fn admit_to_hall_of_fame<G: Clone + Distance, S: Score>(
+    hall_of_fame: &mut Vec<Candidate<G, S>>,
+    candidate: &Candidate<G, S>,
+    size: usize,
+    objective: Objective,
+) -> bool {
+    if candidate.fitness.is_none()
+        || hall_of_fame
+            .iter()
+            .any(|famous| famous.genome.distance(&candidate.genome) == 0)
+    {
+        return false;
+    }
+    let rank = hall_of_fame
+        .iter()
+        .position(|famous| objective.is_better(candidate.fitness, famous.fitness))
+        .unwrap_or(hall_of_fame.len());
+    if rank >= size {
+        return false;
+    }
+    hall_of_fame.insert(rank, candidate.clone());
+    hall_of_fame.truncate(size);
+    true
+}
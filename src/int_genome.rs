@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+
+use rand::prelude::*;
+
+use crate::crossover::CrossoverMode;
+use crate::genome::{Crossover, Distance, Fitness, Genome};
+
+/// A vector of unsigned integers evolved bit by bit toward `IntContext::target`. Its
+/// fitness is the total absolute difference from the target, so a perfect genome scores 0
+/// and the algorithm should run with `Objective::Minimize`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntGenome(pub Vec<u32>);
+
+/// What the integer operators need to know: the target values, how many bits each value
+/// has and whether mutation and crossover work on their Gray code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntContext {
+    pub target: Vec<u32>,
+    pub bits: u32,
+    pub gray_code: bool,
+}
+
+impl IntContext {
+    /// Values of `bits` bits, between 1 and 32, encoded in Gray code.
+    pub fn new(target: impl Into<Vec<u32>>, bits: u32) -> Self {
+        assert!((1..=32).contains(&bits), "bits must lie between 1 and 32");
+        let context = Self {
+            target: target.into(),
+            bits,
+            gray_code: true,
+        };
+        assert!(
+            context.target.iter().all(|&value| value <= context.mask()),
+            "target values must fit in {} bits",
+            bits
+        );
+        context
+    }
+
+    /// With plain binary, neighboring values can differ in many bits, so a single flip
+    /// rarely moves a value by one; Gray code always can.
+    pub fn with_gray_code(mut self, gray_code: bool) -> Self {
+        self.gray_code = gray_code;
+        self
+    }
+
+    /// The largest value that fits in `bits`.
+    pub fn mask(&self) -> u32 {
+        u32::MAX >> (32 - self.bits)
+    }
+
+    fn encode(&self, value: u32) -> u32 {
+        if self.gray_code {
+            to_gray(value)
+        } else {
+            value
+        }
+    }
+
+    fn decode(&self, code: u32) -> u32 {
+        if self.gray_code {
+            from_gray(code)
+        } else {
+            code
+        }
+    }
+}
+
+/// The reflected binary Gray code of `value`.
+pub fn to_gray(value: u32) -> u32 {
+    value ^ (value >> 1)
+}
+
+/// The value whose Gray code is `code`.
+pub fn from_gray(code: u32) -> u32 {
+    let mut value = code;
+    let mut shift = code >> 1;
+    while shift != 0 {
+        value ^= shift;
+        shift >>= 1;
+    }
+    value
+}
+
+/// Flips each of the low `bits` bits of `code` with probability `mutation_prob`.
+pub fn flip_bits(code: u32, bits: u32, mutation_prob: f64, rng: &mut impl Rng) -> u32 {
+    (0..bits).fold(code, |code, bit| {
+        if rng.gen_bool(mutation_prob) {
+            code ^ (1 << bit)
+        } else {
+            code
+        }
+    })
+}
+
+impl Genome for IntGenome {
+    type Context = IntContext;
+
+    fn random(context: &IntContext, rng: &mut impl Rng) -> Self {
+        IntGenome(
+            context
+                .target
+                .iter()
+                .map(|_| rng.gen_range(0..=context.mask()))
+                .collect(),
+        )
+    }
+
+    fn lexical_cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Recombines the encoded values as one string of bits, the first value's most
+/// significant bit first, then flips bits.
+impl Crossover for IntGenome {
+    fn crossover(
+        &self,
+        other: &Self,
+        mode: CrossoverMode,
+        mutation_prob: f64,
+        context: &IntContext,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let bits = context.bits as usize;
+        let total_bits = self.0.len().min(other.0.len()) * bits;
+        // Which bits of the string come from `other`.
+        let from_other: Vec<bool> = match mode {
+            CrossoverMode::Uniform { bias } => {
+                (0..total_bits).map(|_| rng.gen_bool(1.0 - bias)).collect()
+            }
+            CrossoverMode::SinglePoint => {
+                let cut = rng.gen_range(0..=total_bits);
+                (0..total_bits).map(|position| position >= cut).collect()
+            }
+            CrossoverMode::TwoPoint => {
+                let a = rng.gen_range(0..=total_bits);
+                let b = rng.gen_range(0..=total_bits);
+                let segment = a.min(b)..a.max(b);
+                (0..total_bits)
+                    .map(|position| segment.contains(&position))
+                    .collect()
+            }
+            CrossoverMode::Asexual => vec![false; total_bits],
+        };
+        let values = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let mut code = context.encode(value);
+                if let Some(&other_value) = other.0.get(i) {
+                    let other_code = context.encode(other_value);
+                    for bit in 0..bits {
+                        let mask = 1 << (bits - 1 - bit);
+                        if from_other[i * bits + bit] {
+                            code = (code & !mask) | (other_code & mask);
+                        }
+                    }
+                }
+                context.decode(flip_bits(code, context.bits, mutation_prob, rng))
+            })
+            .collect();
+        IntGenome(values)
+    }
+}
+
+/// The number of differing bits.
+impl Distance for IntGenome {
+    fn distance(&self, other: &Self) -> usize {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum()
+    }
+}
+
+impl Fitness for IntGenome {
+    type Score = isize;
+
+    fn fitness(&self, context: &IntContext) -> isize {
+        self.0
+            .iter()
+            .zip(&context.target)
+            .map(|(&value, &target)| value.abs_diff(target) as isize)
+            .sum()
+    }
+
+    fn max_fitness(_context: &IntContext) -> isize {
+        0
+    }
+}
@@ -0,0 +1,163 @@
+use std::ops::ControlFlow;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::algorithm::GeneticAlgorithm;
+use crate::candidate::Candidate;
+use crate::genome::{Crossover, Distance, Fitness};
+use crate::solve::SolveResult;
+
+/// Runs several populations, the islands, side by side in their own threads and every
+/// `migration_interval` generations copies each island's `migrants` best candidates to the
+/// next island in a ring, where they replace the worst. Islands explore on their own
+/// between migrations, which keeps them from converging on the same local optimum.
+///
+/// Island `i` is seeded with `seed + i` and migration happens at fixed generations, so a
+/// run is reproducible no matter how the threads are scheduled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IslandModel {
+    pub islands: usize,
+    pub seed: u64,
+    pub migration_interval: usize,
+    pub migrants: usize,
+}
+
+/// What an island reports at the end of each epoch, the generations between migrations.
+struct Epoch<G: Fitness> {
+    island: usize,
+    emigrants: Vec<Candidate<G, G::Score>>,
+    solved: bool,
+    finished: bool,
+}
+
+/// The coordinator's answer to an epoch.
+enum Order<G: Fitness> {
+    Migrate(Vec<Candidate<G, G::Score>>),
+    Stop,
+}
+
+impl IslandModel {
+    /// `islands` islands, at least one, migrating 2 candidates every 10 generations.
+    pub fn new(islands: usize, seed: u64) -> Self {
+        assert!(islands > 0, "there must be at least one island");
+        Self {
+            islands,
+            seed,
+            migration_interval: 10,
+            migrants: 2,
+        }
+    }
+
+    /// Migrate `migrants` candidates every `interval` generations; 0 migrants keeps the
+    /// islands apart.
+    pub fn with_migration(mut self, interval: usize, migrants: usize) -> Self {
+        assert!(interval > 0, "the migration interval must be at least 1");
+        self.migration_interval = interval;
+        self.migrants = migrants;
+        self
+    }
+
+    /// The seed of island `island`.
+    pub fn island_seed(&self, island: usize) -> u64 {
+        self.seed.wrapping_add(island as u64)
+    }
+
+    /// Builds every island with `make_island(seed)` in its own thread and runs them until
+    /// one reaches the target or all of them are finished, checking after every epoch.
+    /// Returns each island's result; islands halted because another one got there first
+    /// count as `Outcome::Stopped`. Without a generation cap, patience or a reachable
+    /// target this never returns, like `GeneticAlgorithm::solve`.
+    pub fn run<F, G, M>(&self, make_island: M) -> Vec<SolveResult<G, G::Score>>
+    where
+        F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+        G: Crossover + Fitness + Distance + Send,
+        G::Score: Send,
+        M: Fn(u64) -> GeneticAlgorithm<F, G> + Sync,
+    {
+        let (report, epochs) = mpsc::channel();
+        thread::scope(|scope| {
+            let (orders, islands): (Vec<_>, Vec<_>) = (0..self.islands)
+                .map(|island| {
+                    let (order, orders) = mpsc::channel();
+                    let report = report.clone();
+                    let make_island = &make_island;
+                    let handle = scope.spawn(move || {
+                        self.run_island(
+                            island,
+                            make_island(self.island_seed(island)),
+                            report,
+                            orders,
+                        )
+                    });
+                    (order, handle)
+                })
+                .unzip();
+            // From here on only the islands report.
+            drop(report);
+            loop {
+                let mut emigrants = vec![Vec::new(); self.islands];
+                let mut solved = false;
+                let mut finished = true;
+                for _ in 0..self.islands {
+                    let epoch: Epoch<G> = epochs.recv().expect("islands report every epoch");
+                    emigrants[epoch.island] = epoch.emigrants;
+                    solved |= epoch.solved;
+                    finished &= epoch.finished;
+                }
+                if solved || finished {
+                    for order in &orders {
+                        let _ = order.send(Order::Stop);
+                    }
+                    break;
+                }
+                // Each island sends its best to the next one.
+                emigrants.rotate_right(1);
+                for (order, migrants) in orders.iter().zip(emigrants) {
+                    let _ = order.send(Order::Migrate(migrants));
+                }
+            }
+            islands
+                .into_iter()
+                .map(|island| island.join().expect("island panicked"))
+                .collect()
+        })
+    }
+
+    fn run_island<F, G>(
+        &self,
+        island: usize,
+        mut genetic_algorithm: GeneticAlgorithm<F, G>,
+        report: mpsc::Sender<Epoch<G>>,
+        orders: mpsc::Receiver<Order<G>>,
+    ) -> SolveResult<G, G::Score>
+    where
+        F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+        G: Crossover + Fitness + Distance,
+    {
+        loop {
+            for _ in 0..self.migration_interval {
+                if genetic_algorithm.by_generation().next().is_none() {
+                    break;
+                }
+            }
+            let migrants = if self.islands > 1 { self.migrants } else { 0 };
+            let epoch = Epoch {
+                island,
+                emigrants: genetic_algorithm
+                    .fittest(migrants)
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+                solved: genetic_algorithm.is_solved(),
+                finished: genetic_algorithm.is_finished(),
+            };
+            report
+                .send(epoch)
+                .expect("the coordinator waits for every island");
+            match orders.recv().expect("the coordinator answers every epoch") {
+                Order::Migrate(migrants) => genetic_algorithm.immigrate(migrants),
+                Order::Stop => return genetic_algorithm.result(),
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::candidate::Candidate;
+use crate::genome::Score;
+
+/// One line of a JSONL log: the population as the callback saw it, the state label and the
+/// indices of the candidates in focus.
+#[derive(Serialize)]
+struct Snapshot<'a, G, S> {
+    state: &'a str,
+    population: &'a [Candidate<G, S>],
+    focus: &'a [usize],
+}
+
+/// Serializes a callback invocation as a single line of JSON, without a trailing newline.
+pub fn json_line<G: Serialize, S: Serialize>(
+    population: &[Candidate<G, S>],
+    label: &str,
+    focus: &[usize],
+) -> String {
+    serde_json::to_string(&Snapshot {
+        state: label,
+        population,
+        focus,
+    })
+    .expect("population is always serializable")
+}
+
+/// One line of a JSONL log read back, e.g. to replay a recorded run.
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "G: Deserialize<'de>, S: Score + Deserialize<'de>"))]
+pub struct RecordedStep<G = String, S = isize> {
+    pub state: String,
+    pub population: Vec<Candidate<G, S>>,
+    pub focus: Vec<usize>,
+}
+
+/// Parses a line written by `json_line`.
+pub fn parse_json_line<G: DeserializeOwned, S: Score + DeserializeOwned>(
+    line: &str,
+) -> Result<RecordedStep<G, S>, serde_json::Error> {
+    serde_json::from_str(line)
+}
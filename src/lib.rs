@@ -0,0 +1,102 @@
+//! A simple genetic algorithm that evolves random strings toward a target string, or any
+//! other genome implementing the traits in `genome`, such as `IntGenome`.
+
+mod algorithm;
+#[cfg(feature = "alloc-counts")]
+mod alloc_counts;
+mod alphabet;
+mod builder;
+mod cache;
+mod candidate;
+#[cfg(feature = "serde")]
+mod checkpoint;
+mod config;
+mod crossover;
+mod display;
+mod diversity;
+mod error;
+mod fitness;
+mod genome;
+mod hall_of_fame;
+mod int_genome;
+mod islands;
+#[cfg(feature = "serde")]
+mod json;
+mod lineage;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod mutation;
+mod objective;
+mod replacement;
+mod selection;
+mod solve;
+mod stats;
+mod strategy;
+#[cfg(feature = "serde")]
+mod stream;
+mod tie_break;
+mod trace;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use algorithm::{
+    breed_new, breed_new_batch, children_per_generation, compute_fitness, compute_fitness_batch,
+    generations_per_second, remove_unfit, reorder_by_fitness, reorder_by_fitness_fast,
+    seed_population, Generations, GeneticAlgorithm, ImprovementHook, STATE,
+};
+#[cfg(feature = "alloc-counts")]
+pub use alloc_counts::{AllocCounts, CountingAllocator};
+pub use alphabet::{alphabet_distance, Alphabet, AlphabetWeights};
+pub use builder::{GeneticAlgorithmBuilder, NoCallback};
+pub use cache::{compute_fitness_batch_cached, compute_fitness_cached, FitnessCache};
+pub use candidate::{breed, Candidate};
+#[cfg(feature = "serde")]
+pub use checkpoint::Checkpoint;
+pub use config::Config;
+pub use crossover::{mutate, single_point, two_point, uniform_many, CrossoverMode};
+pub use display::{
+    center_text, columns_for_width, display, fit_text, fitness_histogram, render_grid,
+    render_grids, render_histogram, render_progress, text_width, visible_len,
+};
+pub use diversity::{
+    apply_fitness_sharing, population_diversity, promote_diverse, promote_young, CullStrategy,
+    FitnessSharing, RestartPolicy,
+};
+pub use error::GaError;
+pub use fitness::{
+    chars_match, fold_case, levenshtein, max_fitness, position_match, weighted_position_match,
+    FitnessFn, FitnessMode,
+};
+pub use genome::{
+    Crossover, Distance, Fitness, Genome, HintCount, Score, StringContext, ThreadSafe,
+};
+pub use hall_of_fame::admit_to_hall_of_fame;
+pub use int_genome::{flip_bits, from_gray, to_gray, IntContext, IntGenome};
+pub use islands::IslandModel;
+#[cfg(feature = "serde")]
+pub use json::{json_line, parse_json_line, RecordedStep};
+pub use lineage::{Ancestor, Lineage};
+#[cfg(feature = "metrics")]
+pub use metrics::{Metrics, MetricsServer};
+pub use mutation::{
+    hint, insert_delete, scramble, shift_to_neighbor, swap_adjacent, AdaptiveMutation, Cooling,
+    MutationOp, MutationSchedule,
+};
+pub use objective::{Key, Objective};
+pub use replacement::{replace_worst, Replacement, ReplacementMode};
+pub use selection::{select_rank, select_roulette, select_tournament, SelectionStrategy};
+pub use solve::{Outcome, SolveResult};
+pub use stats::{is_plateau, GenerationStats, CSV_HEADER};
+pub use strategy::Strategy;
+#[cfg(feature = "serde")]
+pub use stream::{write_frame, FrameStream, OnDisconnect};
+pub use tie_break::{reorder_by_fitness_fast_with, reorder_by_fitness_with, TieBreak};
+pub use trace::Trace;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGeneticAlgorithm;
+
+pub const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz ";
+pub const TARGET_STR: &str = "hello world";
+pub const POPULATION_SIZE: usize = 48;
+pub const NUM_FIT_TO_KEEP: usize = 5;
+pub const MUTATION_PROB: f64 = 0.15;
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+/// A candidate as it was born, kept by `Lineage` after it has left the population.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ancestor<G = String> {
+    pub id: u64,
+    pub genome: G,
+    /// The generation it was bred in, 0 for the initial population.
+    pub generation: usize,
+    /// Ids of its parents, the one crossover started from first; empty for seeds.
+    pub parents: Vec<u64>,
+}
+
+/// Every candidate a run has produced, by id, so that the ancestry of any of them can be
+/// traced back to the initial population. Grows with the number of candidates bred.
+#[derive(Clone, Debug)]
+pub struct Lineage<G = String> {
+    next_id: u64,
+    ancestors: HashMap<u64, Ancestor<G>>,
+}
+
+impl<G> Default for Lineage<G> {
+    fn default() -> Self {
+        Self {
+            // 0 marks candidates that have not been registered.
+            next_id: 1,
+            ancestors: HashMap::new(),
+        }
+    }
+}
+
+impl<G: Clone> Lineage<G> {
+    /// Records a newborn with the ids of its parents and returns its id.
+    pub fn register(&mut self, genome: &G, generation: usize, parents: Vec<u64>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.ancestors.insert(
+            id,
+            Ancestor {
+                id,
+                genome: genome.clone(),
+                generation,
+                parents,
+            },
+        );
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Ancestor<G>> {
+        self.ancestors.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ancestors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ancestors.is_empty()
+    }
+
+    /// The chain of first parents leading to `id`, oldest first and ending with `id`
+    /// itself; empty if `id` is unknown.
+    pub fn ancestry(&self, id: u64) -> Vec<&Ancestor<G>> {
+        let mut chain = Vec::new();
+        let mut next = self.get(id);
+        while let Some(ancestor) = next {
+            chain.push(ancestor);
+            next = ancestor
+                .parents
+                .first()
+                .and_then(|&parent| self.get(parent));
+        }
+        chain.reverse();
+        chain
+    }
+}
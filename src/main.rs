@@ -1,337 +1,1410 @@
-use rand::prelude::*;
+#[cfg(feature = "serde")]
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::io::{IsTerminal, Write};
+use std::ops::ControlFlow;
+#[cfg(feature = "serde")]
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
-const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz ";
-const TARGET_STR: &str = "hello world";
-const POPULATION_SIZE: usize = 48;
-const NUM_FIT_TO_KEEP: usize = 5;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+use ga::{
+    columns_for_width, fitness_histogram, render_grid, render_grids, render_histogram, text_width,
+    AlphabetWeights, Candidate, Config, Cooling, CrossoverMode, CullStrategy, Fitness, FitnessMode,
+    FitnessSharing, GeneticAlgorithm, GeneticAlgorithmBuilder, MutationOp, Objective,
+    ReplacementMode, RestartPolicy, SelectionStrategy, Strategy, TieBreak, Trace, CSV_HEADER,
+    LETTERS, MUTATION_PROB, NUM_FIT_TO_KEEP, POPULATION_SIZE, STATE, TARGET_STR,
+};
+#[cfg(feature = "serde")]
+use ga::{FrameStream, OnDisconnect};
+
+/// Exit status when a run gave up on a plateau, as opposed to 1 for hitting
+/// --max-generations, so scripts can tell the two apart.
+const EXIT_STALLED: i32 = 3;
+
+/// Exit status after Ctrl-C or `q`, the shell convention for SIGINT.
+const EXIT_INTERRUPTED: i32 = 130;
+
+/// Columns used when the terminal width is unknown, e.g. when output is piped.
 const NUM_COLUMNS: usize = 4;
-const MUTATION_PROB: f64 = 0.15;
 
-#[derive(Clone, Debug)]
-struct Candidate {
-    text: String,
-    fitness: isize,
-    in_focus: bool,
+#[derive(Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Grid,
+    Csv,
+    #[cfg(feature = "serde")]
+    Json,
 }
 
-impl Candidate {
-    fn new(text: String) -> Self {
-        Self {
-            text,
-            fitness: -1,
-            in_focus: false,
-        }
-    }
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
 
-    fn display_str(&self, target_str: &str) -> String {
-        let prefix = if self.in_focus { "➤ " } else { "  " };
-        if self.fitness < 0 {
-            return format!("{}{}", prefix, self.text);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "grid" => Ok(OutputFormat::Grid),
+            "csv" => Ok(OutputFormat::Csv),
+            #[cfg(feature = "serde")]
+            "json" => Ok(OutputFormat::Json),
+            #[cfg(not(feature = "serde"))]
+            "json" => Err("json output needs the serde feature".to_string()),
+            _ => Err(format!("unknown output format '{}'", s)),
         }
-        let mut out = prefix.to_string();
-        for (char, target_char) in self.text.chars().zip(target_str.chars()) {
-            if char != target_char {
-                out.push_str(&format!("\x1b[91m{}\x1b[0m", char));
-            } else {
-                out.push_str(&format!("\x1b[92m{}\x1b[0m", char));
-            }
-        }
-        out
-    }
-
-    fn set_fitness(&mut self, target_str: &str) {
-        self.fitness = self
-            .text
-            .chars()
-            .zip(target_str.chars())
-            .filter(|(c, t)| c == t)
-            .count() as isize;
     }
 }
 
-fn reset_focus(population: &mut [Candidate]) {
-    for candidate in population.iter_mut() {
-        candidate.in_focus = false;
-    }
-}
+// Counts every allocation of the process for `--alloc-counts`.
+#[cfg(feature = "alloc-counts")]
+#[global_allocator]
+static ALLOCATOR: ga::CountingAllocator = ga::CountingAllocator;
 
-fn breed(parent_a: &Candidate, parent_b: &Candidate, mutation_prob: f64) -> Candidate {
-    let mut rng = rand::thread_rng();
-    let text: String = parent_a
-        .text
-        .chars()
-        .zip(parent_b.text.chars())
-        .map(|(char_a, char_b)| {
-            if rng.gen_bool(mutation_prob) {
-                LETTERS.chars().choose(&mut rng).unwrap()
-            } else if rng.gen_bool(0.5) {
-                char_a
-            } else {
-                char_b
-            }
-        })
-        .collect();
-    Candidate::new(text)
-}
+const USAGE: &str = "\
+usage: ga [OPTIONS] [TARGET...]
 
-enum STATE {
-    Init,
-    ComputeFitness,
-    Reorder,
-    RemoveUnfit,
-    BreedNew,
-}
+options:
+  --config PATH          read settings from a TOML file, with keys named like these
+                         options (e.g. population = 100); flags override the file
+  --population N         number of candidates per generation (default 48)
+  --keep N               candidates surviving each generation (default 5)
+  --elite N              survivors carried over unchanged, the rest only breed (default: all)
+  --generation-gap G     fraction of the population replaced by children each generation,
+                         the best of the rest carried over (replaces --elite)
+  --mutation P           per-character mutation probability (default 0.15)
+  --mutation-schedule S  cool the mutation probability down: linear:START:FLOOR:GENERATIONS
+                         or exponential:START:FLOOR:DECAY (replaces --mutation)
+  --mutation-op OP       mutation: replace (default), swap (adjacent characters),
+                         neighbor (next or previous alphabet character), insert-delete or
+                         scramble:N (shuffle up to N characters in a row, with the
+                         mutation probability per candidate)
+  --hint-prob P          set a mutated character to the target's with probability P, a
+                         shortcut that guarantees convergence (default 0, pure search)
+  --init-len-min N       shortest random candidate to seed (default: the target's length),
+                         for variable-length search with levenshtein or insert-delete
+  --init-len-max N       longest random candidate to seed (default: the target's length)
+  --alphabet CHARS       characters genes are drawn from (default: lowercase letters and space)
+  --alphabet-weights W   how often each alphabet character is drawn relative to the others:
+                         one comma-separated weight per character, e.g. 5,1,1 for abc
+  --allow-unreachable    run even if the alphabet lacks target characters, stopping at the
+                         best fitness that can be reached
+  --selection S          parent selection: random (default), tournament:K, roulette
+                         or rank:P with selection pressure P between 1 and 2
+  --crossover C          recombination: uniform (default), uniform:B taking each gene from
+                         the fitter parent with probability B, single-point, two-point
+                         or asexual (mutated copies of one parent)
+  --parents K            parents of each child (default 2); with more, uniform crossover
+                         takes each gene from any of them alike, and 1 is asexual
+  --cull C               survivors: fitness (default), diversity:N to keep N distinct ones,
+                         age to prefer the youngest among equally fit ones or age:N to
+                         also cull candidates that survived N generations
+  --replacement R        generational (default) or steady-state, one child at a time
+                         replacing the worst candidate
+  --tie-break T          order of equally fit candidates: stable (default, keep their
+                         order), lexical or diverse (farthest from the rest first)
+  --fitness F            scoring: position (default), levenshtein or weighted:W,W,...
+                         with one weight per target character
+  --ignore-case          count letters as matching the target in either case
+  --incremental-fitness  score children from their parent's fitness, only looking at
+                         the characters that changed (position and weighted fitness)
+  --fitness-cache        score each distinct candidate only once and report the hit rate
+  --seed N               seed the random number generator for reproducible runs
+  --seed-candidate TEXT  start with TEXT in the population, cut or randomly padded to
+                         the target length; may be repeated
+  --fast-sort            sort in one step instead of bubble sorting
+  --format F             output: grid (default), json (one line per step) or csv
+                         (one row of statistics per generation)
+  --no-color             disable ANSI colors
+  --shade                color mismatched characters by how far they are from the target
+                         character in the alphabet instead of plain red
+  --histogram            show how many candidates have each fitness below the grid, in at
+                         most one range per target character plus one
+  --leader               show the best candidate in a highlighted line above the grid,
+                         updated whenever the population has been sorted
+  --display-top N        show only the first N candidates, the best ones once sorted,
+                         while the whole population keeps evolving
+  --scroll               print the grid below the last one once each generation is scored
+                         instead of redrawing it in place, for logs (ignores --fps and
+                         --skip)
+  --headless             skip the animation and run at full speed
+  --result-only          run headless and print nothing but one line per target with the
+                         best candidate and the generations it took, e.g. hello world
+                         (solved in 69 generations); the exit status tells whether it was
+                         solved
+  --fps N                draw at most N frames per second (default 60)
+  --skip K               draw only every K-th step, to speed up large populations
+  --max-generations N    stop after N generations
+  --patience N           give up (exit status 3) after N generations without improvement
+  --restart D:P          reseed all but the elite once the mean distance between candidates
+                         drops below D and nothing improved for P generations
+  --fitness-sharing R:A  rank candidates by fitness shared with everyone less than R apart,
+                         falling off with distance to the power A (1 is linear)
+  --hall-of-fame N       remember the N best distinct candidates ever scored and list
+                         them at the end
+  --track-lineage        remember every candidate's parents and print the ancestry of
+                         the best candidate at the end
+  --compare-crossover    run each target twice from the same seed, with uniform crossover
+                         and asexually, and print both runs side by side
+  --dry-run              print the resolved configuration and exit
+  --list-strategies      print every selection, crossover, mutation, replacement, cull,
+                         tie break and fitness choice with a short description and exit
+  --once                 run a single generation, print the resulting population in the
+                         chosen format and exit
+  --trace                log every state transition and its timing to stderr, then
+                         how the time was split between phases
+  --checkpoint PATH      save the run to PATH after every generation
+  --resume PATH          continue a run saved with --checkpoint
+  --replay PATH          animate a run recorded with --format json instead of running one,
+                         drawn against the first target at --fps; lines that cannot be
+                         read are skipped with a warning
+  --stream ADDR          connect to a client listening on ADDR (e.g. 127.0.0.1:7878) and
+                         send it every step as JSON, each prefixed with its length as a
+                         4-byte big-endian integer
+  --stream-disconnect D  when the client goes away: continue (default) or stop the run
+  --metrics ADDR         answer HTTP requests on ADDR (e.g. 127.0.0.1:9090) with the
+                         generation and best candidate as JSON, updated every generation
+                         (needs the metrics feature)
+  --alloc-counts         print how many candidates were cloned and how often memory was
+                         allocated for every generation to stderr (needs the
+                         alloc-counts feature)
 
-impl STATE {
-    fn description(&self) -> &'static str {
-        match *self {
-            STATE::Init => "Seeding the population",
-            STATE::ComputeFitness => "Computing fitness",
-            STATE::Reorder => "Sorting by fitness",
-            STATE::RemoveUnfit => "Removing unfit candidates",
-            STATE::BreedNew => "Breeding new candidates",
-        }
-    }
-}
+while the grid is animating, space pauses and resumes, n advances one frame while
+paused and q stops the run. Ctrl-C stops any run early and still reports the best
+candidate found so far (exit status 130).";
 
-struct GeneticAlgorithm<'a, F>
-where
-    F: Fn(&Vec<Candidate>, &str) + 'a,
-{
-    population: &'a mut Vec<Candidate>,
-    target_str: &'a str,
-    state: STATE,
-    num_fit_to_keep: usize,
+struct Args {
+    targets: Vec<String>,
     population_size: usize,
+    num_fit_to_keep: usize,
+    elite_count: Option<usize>,
+    generation_gap: Option<f64>,
     mutation_prob: f64,
-    callback: F,
+    hint_prob: f64,
+    init_len_min: Option<usize>,
+    init_len_max: Option<usize>,
+    mutation_op: MutationOp,
+    mutation_schedule: Option<Cooling>,
+    objective: Objective,
+    seed: Option<u64>,
+    seed_candidates: Vec<String>,
+    fast_sort: bool,
+    format: OutputFormat,
+    color: bool,
+    histogram: bool,
+    leader: bool,
+    shade: bool,
+    display_top: Option<usize>,
+    scroll: bool,
+    headless: bool,
+    fps: u32,
+    skip: usize,
+    max_generations: Option<usize>,
+    patience: Option<usize>,
+    restart: Option<RestartPolicy>,
+    fitness_sharing: Option<FitnessSharing>,
+    hall_of_fame: usize,
+    track_lineage: bool,
+    compare_crossover: bool,
+    dry_run: bool,
+    result_only: bool,
+    list_strategies: bool,
+    once: bool,
+    trace: bool,
+    selection: SelectionStrategy,
+    crossover: CrossoverMode,
+    num_parents: usize,
+    cull: CullStrategy,
+    replacement: ReplacementMode,
+    tie_break: TieBreak,
+    fitness_mode: FitnessMode,
+    ignore_case: bool,
+    incremental_fitness: bool,
+    fitness_cache: bool,
+    alphabet: String,
+    alphabet_weights: Option<AlphabetWeights>,
+    allow_unreachable: bool,
+    #[cfg(feature = "serde")]
+    checkpoint: Option<std::path::PathBuf>,
+    #[cfg(feature = "serde")]
+    resume: Option<std::path::PathBuf>,
+    #[cfg(feature = "serde")]
+    replay: Option<std::path::PathBuf>,
+    #[cfg(feature = "serde")]
+    stream: Option<String>,
+    #[cfg(feature = "serde")]
+    stream_disconnect: OnDisconnect,
+    #[cfg(feature = "metrics")]
+    metrics: Option<String>,
+    #[cfg(feature = "alloc-counts")]
+    alloc_counts: bool,
 }
 
-impl<'a, F> GeneticAlgorithm<'a, F>
-where
-    F: Fn(&Vec<Candidate>, &str) + 'a,
-{
-    fn new(
-        population: &'a mut Vec<Candidate>,
-        target_str: &'a str,
-        num_fit_to_keep: usize,
-        population_size: usize,
-        mutation_prob: f64,
-        callback: F,
-    ) -> Self {
-        Self {
-            population,
-            target_str,
-            state: STATE::Init,
-            num_fit_to_keep,
-            population_size,
-            mutation_prob,
-            callback,
-        }
-    }
+#[cfg(feature = "serde")]
+fn load_config(path: Option<String>) -> Result<Config, String> {
+    let path: std::path::PathBuf = parse_value("--config", path)?;
+    Config::load(&path).map_err(|err| format!("cannot read {}: {}", path.display(), err))
 }
 
-impl<'a, F> Iterator for GeneticAlgorithm<'a, F>
+#[cfg(not(feature = "serde"))]
+fn load_config(_: Option<String>) -> Result<Config, String> {
+    Err("--config needs the serde feature".to_string())
+}
+
+fn parse_value<T>(flag: &str, value: Option<String>) -> Result<T, String>
 where
-    F: Fn(&Vec<Candidate>, &str),
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
 {
-    type Item = ();
-
-    fn next(&mut self) -> Option<Self::Item> {
-        reset_focus(self.population);
-        use STATE::*;
-        match &self.state {
-            Init => {
-                if seed_population(self.population, self.population_size, self.target_str.len()) {
-                    (self.callback)(self.population, self.state.description());
-                    return Some(());
-                } else {
-                    self.state = ComputeFitness;
+    let value = value.ok_or_else(|| format!("{} expects a value", flag))?;
+    value
+        .parse()
+        .map_err(|err| format!("invalid value '{}' for {}: {}", value, flag, err))
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let args: Vec<String> = args.collect();
+        // The file only replaces the defaults, so flags win wherever they appear.
+        let config = match args.iter().position(|arg| arg == "--config") {
+            Some(i) => load_config(args.get(i + 1).cloned())?,
+            None => Config::default(),
+        };
+        let mut args = args.into_iter();
+
+        let mut targets = Vec::new();
+        let mut population_size = config.population.unwrap_or(POPULATION_SIZE);
+        let mut num_fit_to_keep = config.keep.unwrap_or(NUM_FIT_TO_KEEP);
+        let mut elite_count = config.elite;
+        let mut generation_gap = config.generation_gap;
+        let mut mutation_prob = config.mutation.unwrap_or(MUTATION_PROB);
+        let mut hint_prob = config.hint_prob.unwrap_or(0.0);
+        let mut init_len_min = config.init_len_min;
+        let mut init_len_max = config.init_len_max;
+        let mut mutation_op = config.mutation_op.unwrap_or_default();
+        let mut mutation_schedule = config.mutation_schedule;
+        let objective = config.objective.unwrap_or_default();
+        let mut seed = config.seed;
+        let mut seed_candidates = Vec::new();
+        let mut fast_sort = false;
+        let mut format = OutputFormat::Grid;
+        let mut color = std::io::stdout().is_terminal();
+        let mut histogram = false;
+        let mut leader = false;
+        let mut shade = false;
+        let mut display_top = None;
+        let mut scroll = false;
+        let mut headless = false;
+        let mut fps = 60;
+        let mut skip = 1;
+        let mut max_generations = config.max_generations;
+        let mut patience = config.patience;
+        let mut restart = config.restart;
+        let mut fitness_sharing = config.fitness_sharing;
+        let mut hall_of_fame = config.hall_of_fame.unwrap_or(0);
+        let mut track_lineage = config.track_lineage.unwrap_or(false);
+        let mut compare_crossover = false;
+        let mut dry_run = false;
+        let mut result_only = false;
+        let mut list_strategies = false;
+        let mut once = false;
+        let mut trace = false;
+        let mut selection = config.selection.unwrap_or_default();
+        let mut crossover = config.crossover.unwrap_or_default();
+        let mut num_parents = config.parents.unwrap_or(2);
+        let mut cull = config.cull.unwrap_or_default();
+        let mut replacement = config.replacement.unwrap_or_default();
+        let mut tie_break = config.tie_break.unwrap_or_default();
+        let mut fitness_mode = config.fitness.clone().unwrap_or_default();
+        let mut ignore_case = config.ignore_case.unwrap_or(false);
+        let mut incremental_fitness = config.incremental_fitness.unwrap_or(false);
+        let mut fitness_cache = config.fitness_cache.unwrap_or(false);
+        let mut alphabet = config.alphabet.clone().unwrap_or(LETTERS.to_string());
+        let mut alphabet_weights = config.alphabet_weights.clone();
+        let mut allow_unreachable = config.allow_unreachable.unwrap_or(false);
+        #[cfg(feature = "serde")]
+        let mut checkpoint = None;
+        #[cfg(feature = "serde")]
+        let mut resume = None;
+        #[cfg(feature = "serde")]
+        let mut replay = None;
+        #[cfg(feature = "serde")]
+        let mut stream = None;
+        #[cfg(feature = "serde")]
+        let mut stream_disconnect = OnDisconnect::default();
+        #[cfg(feature = "metrics")]
+        let mut metrics = None;
+        #[cfg(feature = "alloc-counts")]
+        let mut alloc_counts = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                // Already read above.
+                "--config" => {
+                    args.next();
                 }
-            }
-            ComputeFitness => {
-                if compute_fitness(self.population, self.target_str) {
-                    (self.callback)(self.population, self.state.description());
-                    return Some(());
-                } else {
-                    self.state = Reorder;
+                "--population" => population_size = parse_value(&arg, args.next())?,
+                "--keep" => num_fit_to_keep = parse_value(&arg, args.next())?,
+                "--elite" => elite_count = Some(parse_value(&arg, args.next())?),
+                "--generation-gap" => generation_gap = Some(parse_value(&arg, args.next())?),
+                "--mutation" => mutation_prob = parse_value(&arg, args.next())?,
+                "--hint-prob" => hint_prob = parse_value(&arg, args.next())?,
+                "--init-len-min" => init_len_min = Some(parse_value(&arg, args.next())?),
+                "--init-len-max" => init_len_max = Some(parse_value(&arg, args.next())?),
+                "--mutation-op" => mutation_op = parse_value(&arg, args.next())?,
+                "--mutation-schedule" => mutation_schedule = Some(parse_value(&arg, args.next())?),
+                "--selection" => selection = parse_value(&arg, args.next())?,
+                "--crossover" => crossover = parse_value(&arg, args.next())?,
+                "--parents" => num_parents = parse_value(&arg, args.next())?,
+                "--cull" => cull = parse_value(&arg, args.next())?,
+                "--replacement" => replacement = parse_value(&arg, args.next())?,
+                "--tie-break" => tie_break = parse_value(&arg, args.next())?,
+                "--fitness" => fitness_mode = parse_value(&arg, args.next())?,
+                "--ignore-case" => ignore_case = true,
+                "--incremental-fitness" => incremental_fitness = true,
+                "--fitness-cache" => fitness_cache = true,
+                "--alphabet" => alphabet = parse_value(&arg, args.next())?,
+                "--alphabet-weights" => alphabet_weights = Some(parse_value(&arg, args.next())?),
+                "--allow-unreachable" => allow_unreachable = true,
+                "--seed" => seed = Some(parse_value(&arg, args.next())?),
+                "--seed-candidate" => seed_candidates.push(parse_value(&arg, args.next())?),
+                "--fast-sort" => fast_sort = true,
+                "--format" => format = parse_value(&arg, args.next())?,
+                "--no-color" => color = false,
+                "--histogram" => histogram = true,
+                "--leader" => leader = true,
+                "--shade" => shade = true,
+                "--display-top" => display_top = Some(parse_value(&arg, args.next())?),
+                "--scroll" => scroll = true,
+                "--headless" => headless = true,
+                "--fps" => fps = parse_value(&arg, args.next())?,
+                "--skip" => skip = parse_value(&arg, args.next())?,
+                "--max-generations" => max_generations = Some(parse_value(&arg, args.next())?),
+                "--patience" => patience = Some(parse_value(&arg, args.next())?),
+                "--restart" => restart = Some(parse_value(&arg, args.next())?),
+                "--fitness-sharing" => fitness_sharing = Some(parse_value(&arg, args.next())?),
+                "--hall-of-fame" => hall_of_fame = parse_value(&arg, args.next())?,
+                "--track-lineage" => track_lineage = true,
+                "--compare-crossover" => compare_crossover = true,
+                "--dry-run" => dry_run = true,
+                "--result-only" => result_only = true,
+                "--list-strategies" => list_strategies = true,
+                "--once" => once = true,
+                "--trace" => trace = true,
+                #[cfg(feature = "serde")]
+                "--checkpoint" => checkpoint = Some(parse_value(&arg, args.next())?),
+                #[cfg(feature = "serde")]
+                "--resume" => resume = Some(parse_value(&arg, args.next())?),
+                #[cfg(feature = "serde")]
+                "--replay" => replay = Some(parse_value(&arg, args.next())?),
+                #[cfg(feature = "serde")]
+                "--stream" => stream = Some(parse_value(&arg, args.next())?),
+                #[cfg(feature = "serde")]
+                "--stream-disconnect" => stream_disconnect = parse_value(&arg, args.next())?,
+                #[cfg(not(feature = "serde"))]
+                "--checkpoint" | "--resume" | "--replay" | "--stream" | "--stream-disconnect" => {
+                    return Err(format!("{} needs the serde feature", arg));
                 }
-            }
-            Reorder => {
-                if reorder_by_fitness(self.population) {
-                    (self.callback)(self.population, self.state.description());
-                    return Some(());
-                } else {
-                    self.state = RemoveUnfit;
+                #[cfg(feature = "metrics")]
+                "--metrics" => metrics = Some(parse_value(&arg, args.next())?),
+                #[cfg(not(feature = "metrics"))]
+                "--metrics" => return Err(format!("{} needs the metrics feature", arg)),
+                #[cfg(feature = "alloc-counts")]
+                "--alloc-counts" => alloc_counts = true,
+                #[cfg(not(feature = "alloc-counts"))]
+                "--alloc-counts" => {
+                    return Err(format!("{} needs the alloc-counts feature", arg));
                 }
-            }
-            RemoveUnfit => {
-                if remove_unfit(self.population, self.num_fit_to_keep) {
-                    (self.callback)(self.population, self.state.description());
-                    return Some(());
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unknown option '{}'", flag));
                 }
-                self.state = BreedNew;
+                _ => targets.push(arg),
             }
-            BreedNew => {
-                if breed_new(self.population, self.population_size, self.mutation_prob) {
-                    (self.callback)(self.population, self.state.description());
-                    return Some(());
-                }
-                self.state = Init;
+        }
+
+        if fps == 0 {
+            return Err("--fps must be at least 1".to_string());
+        }
+        if skip == 0 {
+            return Err("--skip must be at least 1".to_string());
+        }
+        if display_top == Some(0) {
+            return Err("--display-top must be at least 1".to_string());
+        }
+        if result_only && (format != OutputFormat::Grid || once || dry_run || compare_crossover) {
+            return Err(
+                "--result-only cannot be combined with --format, --once, --dry-run or --compare-crossover"
+                    .to_string(),
+            );
+        }
+        if targets.is_empty() {
+            targets.push(config.target.unwrap_or(TARGET_STR.to_string()));
+        }
+        if seed_candidates.is_empty() {
+            seed_candidates = config.seed_candidates.unwrap_or_default();
+        }
+        #[cfg(feature = "serde")]
+        if targets.len() > 1 && (checkpoint.is_some() || resume.is_some()) {
+            return Err("--checkpoint and --resume only work with a single target".to_string());
+        }
+        Ok(Self {
+            targets,
+            population_size,
+            num_fit_to_keep,
+            elite_count,
+            generation_gap,
+            mutation_prob,
+            hint_prob,
+            init_len_min,
+            init_len_max,
+            mutation_op,
+            mutation_schedule,
+            objective,
+            seed,
+            seed_candidates,
+            fast_sort,
+            format,
+            color,
+            histogram,
+            leader,
+            shade,
+            display_top,
+            scroll,
+            headless,
+            fps,
+            skip,
+            max_generations,
+            patience,
+            restart,
+            fitness_sharing,
+            hall_of_fame,
+            track_lineage,
+            compare_crossover,
+            dry_run,
+            result_only,
+            list_strategies,
+            once,
+            trace,
+            selection,
+            crossover,
+            num_parents,
+            cull,
+            replacement,
+            tie_break,
+            fitness_mode,
+            ignore_case,
+            incremental_fitness,
+            fitness_cache,
+            alphabet,
+            alphabet_weights,
+            allow_unreachable,
+            #[cfg(feature = "serde")]
+            checkpoint,
+            #[cfg(feature = "serde")]
+            resume,
+            #[cfg(feature = "serde")]
+            replay,
+            #[cfg(feature = "serde")]
+            stream,
+            #[cfg(feature = "serde")]
+            stream_disconnect,
+            #[cfg(feature = "metrics")]
+            metrics,
+            #[cfg(feature = "alloc-counts")]
+            alloc_counts,
+        })
+    }
+}
+
+/// Prints the choices of every pluggable component, under the option that picks it, for
+/// `--list-strategies`.
+fn list_strategies(out: &mut impl Write) {
+    fn section<T: Strategy>(out: &mut impl Write, option: &str) {
+        writeln!(out, "{}", option).unwrap();
+        for variant in T::variants() {
+            writeln!(
+                out,
+                "  {:<28}{}",
+                variant.to_string(),
+                variant.description()
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    section::<SelectionStrategy>(out, "--selection");
+    section::<CrossoverMode>(out, "--crossover");
+    section::<MutationOp>(out, "--mutation-op");
+    section::<Cooling>(out, "--mutation-schedule");
+    section::<ReplacementMode>(out, "--replacement");
+    section::<CullStrategy>(out, "--cull");
+    section::<TieBreak>(out, "--tie-break");
+    section::<FitnessMode>(out, "--fitness");
+    section::<Objective>(out, "objective (config file only)");
+}
+
+fn exit_with_usage(err: &str) -> ! {
+    eprintln!("error: {}", err);
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+/// Keyboard controls for the grid animation: space pauses and resumes, `n` draws one more
+/// frame while paused and `q` stops the run. Raw mode no longer turns Ctrl-C into a signal,
+/// so it stops the run too.
+struct Controls {
+    paused: bool,
+    step: bool,
+    quit: bool,
+    redraw: bool,
+}
+
+impl Controls {
+    /// Puts the terminal in raw mode so keys arrive without Enter, or returns `None` when
+    /// stdin is not a terminal. Dropping the controls restores the terminal.
+    fn enable() -> Option<Self> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        crossterm::terminal::enable_raw_mode().ok()?;
+        Some(Self {
+            paused: false,
+            step: false,
+            quit: false,
+            redraw: false,
+        })
+    }
+
+    /// Handles every key pressed since the last call, waiting up to `timeout` for the first.
+    fn poll(&mut self, timeout: Duration) {
+        let mut timeout = timeout;
+        while event::poll(timeout).unwrap_or(false) {
+            timeout = Duration::ZERO;
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char(' ') => {
+                        self.paused = !self.paused;
+                        self.redraw = true;
+                    }
+                    KeyCode::Char('n') => self.step = self.paused,
+                    KeyCode::Char('q') => self.quit = true,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.quit = true
+                    }
+                    _ => {}
+                },
+                Ok(Event::Resize(..)) => self.redraw = true,
+                _ => {}
             }
         }
-        None
     }
 }
 
-fn seed_population(
-    population: &mut Vec<Candidate>,
-    population_size: usize,
-    target_str_len: usize,
-) -> bool {
-    if population.len() < population_size {
-        population.push(Candidate::new(
-            (0..target_str_len)
-                .map(|_| LETTERS.chars().choose(&mut rand::thread_rng()).unwrap())
-                .collect(),
-        ));
-        population.last_mut().unwrap().in_focus = true;
-        true
-    } else {
-        false
+impl Drop for Controls {
+    fn drop(&mut self) {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+/// Writes the statistics of the latest generation as a CSV row, prefixed with the quoted
+/// target when several runs share the output.
+fn write_csv_row<F>(out: &mut impl Write, genetic_algorithm: &GeneticAlgorithm<F>, multiple: bool)
+where
+    F: Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>,
+{
+    let target_str = &genetic_algorithm.context().target;
+    let Some(stats) = genetic_algorithm.stats().last() else {
+        return;
+    };
+    let row = stats.csv_row(String::max_fitness(genetic_algorithm.context()));
+    if multiple {
+        write!(out, "\"{}\",", target_str.replace('"', "\"\"")).unwrap();
     }
+    writeln!(out, "{}", row).unwrap();
+    // Flush every row so the file can be followed with `tail -f`.
+    out.flush().unwrap();
 }
 
-fn compute_fitness<'a>(population: &'a mut [Candidate], target_str: &'a str) -> bool {
-    if let Some(ref mut candidate) = population.iter_mut().find(|c| c.fitness < 0) {
-        candidate.set_fitness(target_str);
-        candidate.in_focus = true;
-        true
+/// Writes one frame of the grid in a single write: after clearing the screen, or with
+/// `scroll` after an empty line below the previous frame. Raw mode leaves the cursor in
+/// its column on a bare newline, so `raw` ends lines with `\r\n`.
+fn write_frame(out: &mut impl Write, frame: &str, scroll: bool, raw: bool) {
+    let mut frame = if scroll {
+        format!("\n{}", frame)
     } else {
-        false
+        format!("\x1b[H\x1b[J{}", frame)
+    };
+    if raw {
+        frame = frame.replace('\n', "\r\n");
     }
+    out.write_all(frame.as_bytes()).unwrap();
+    out.flush().unwrap();
 }
 
-fn reorder_by_fitness(population: &mut [Candidate]) -> bool {
-    let mut made_swap = false;
+/// Animates a run recorded with `--format json`, one frame per line, with `display`. The
+/// log does not say what the target was, so the first target is taken to be it.
+#[cfg(feature = "serde")]
+fn replay(args: &Args, path: &std::path::Path) {
+    use std::io::BufRead;
 
-    let n = population.len();
-    for i in 0..n {
-        for j in 0..n - i - 1 {
-            if population[j].fitness < population[j + 1].fitness {
-                population.swap(j, j + 1);
-                made_swap = true;
+    let file = std::fs::File::open(path).unwrap_or_else(|err| {
+        eprintln!("error: cannot replay {}: {}", path.display(), err);
+        std::process::exit(1);
+    });
+    let target_str = args.targets[0].as_str();
+    let context = ga::StringContext {
+        fitness_mode: args.fitness_mode.clone(),
+        ..ga::StringContext::new(target_str)
+            .with_alphabet(args.alphabet.as_str())
+            .with_ignore_case(args.ignore_case)
+    };
+    let max_fitness = String::max_fitness(&context);
+    let column_width = text_width(target_str) + 6;
+    let frame_time = Duration::from_secs_f64(1.0 / args.fps as f64);
+    let mut last_frame = std::time::Instant::now();
+    for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let step = match line.map_err(|err| err.to_string()).and_then(|line| {
+            ga::parse_json_line::<String, isize>(&line).map_err(|err| err.to_string())
+        }) {
+            Ok(step) => step,
+            Err(err) => {
+                eprintln!(
+                    "warning: skipping line {} of {}: {}",
+                    i + 1,
+                    path.display(),
+                    err
+                );
+                continue;
             }
+        };
+        let num_cells = step.population.len();
+        let num_columns = terminal_size::terminal_size().map_or(NUM_COLUMNS, |(width, _)| {
+            columns_for_width(width.0 as usize, column_width, 1).min(num_cells.max(1))
+        });
+        let mut frame = Vec::new();
+        ga::display(
+            &mut frame,
+            &step.population,
+            &step.focus,
+            &step.state,
+            column_width,
+            target_str,
+            max_fitness,
+            args.ignore_case,
+            num_cells,
+            num_columns,
+            args.color,
+            args.shade.then_some(args.alphabet.as_str()),
+            None,
+        )
+        .expect("writing to a Vec cannot fail");
+        if let Some(wait) = frame_time
+            .checked_sub(last_frame.elapsed())
+            .filter(|_| !args.scroll)
+        {
+            std::thread::sleep(wait);
         }
+        last_frame = std::time::Instant::now();
+        write_frame(
+            &mut std::io::stdout().lock(),
+            &String::from_utf8_lossy(&frame),
+            args.scroll,
+            false,
+        );
     }
-    made_swap
 }
 
-fn remove_unfit(population: &mut Vec<Candidate>, num_fit_to_keep: usize) -> bool {
-    if population.len() > num_fit_to_keep {
-        population.pop();
-        if let Some(last) = population.last_mut() {
-            last.in_focus = true;
-        }
-        true
+/// How a run ended in a few words for `--result-only`, e.g. `solved in 69 generations`.
+fn outcome_str<F>(genetic_algorithm: &GeneticAlgorithm<F>) -> String
+where
+    F: Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()>,
+{
+    let generation = genetic_algorithm.generation();
+    if genetic_algorithm.is_solved()
+        && genetic_algorithm.max_fitness() != String::max_fitness(genetic_algorithm.context())
+    {
+        format!("best possible in {} generations", generation)
+    } else if genetic_algorithm.is_solved() {
+        format!("solved in {} generations", generation)
+    } else if genetic_algorithm.is_stalled() {
+        format!("stalled after {} generations", generation)
     } else {
-        false
+        format!("not solved in {} generations", generation)
     }
 }
 
-fn breed_new(population: &mut Vec<Candidate>, population_size: usize, mutation_prob: f64) -> bool {
-    let num_fit = population.len();
-    if population.len() < population_size {
-        let i = rand::thread_rng().gen_range(0..num_fit);
-        let j = (i + rand::thread_rng().gen_range(1..num_fit)) % num_fit;
+/// A fitness for the report, `-` if the candidate is not scored.
+fn fitness_str(fitness: Option<isize>) -> String {
+    fitness.map_or("-".to_string(), |fitness| fitness.to_string())
+}
 
-        reset_focus(population);
+/// Rows of the convergence table printed by `--compare-crossover`.
+const COMPARISON_ROWS: usize = 10;
 
-        let parent_a = population[i].clone();
-        let parent_b = population[j].clone();
-        population[i].in_focus = true;
-        population[j].in_focus = true;
+/// Solves the target once with uniform crossover and once asexually, both from `seed`,
+/// and prints how many generations each took and how their best fitness grew.
+fn compare_crossover(out: &mut impl Write, builder: GeneticAlgorithmBuilder, seed: u64) {
+    let modes = [CrossoverMode::default(), CrossoverMode::Asexual];
+    let mut genetic_algorithms: Vec<_> = modes
+        .iter()
+        .map(|&mode| {
+            builder
+                .clone()
+                .crossover(mode)
+                .build()
+                .unwrap_or_else(|err| exit_with_usage(&err.to_string()))
+        })
+        .collect();
+    let results: Vec<_> = genetic_algorithms
+        .iter_mut()
+        .map(|genetic_algorithm| genetic_algorithm.solve())
+        .collect();
+    let context = genetic_algorithms[0].context();
+    let max_fitness = String::max_fitness(context);
 
-        let child = breed(&parent_a, &parent_b, mutation_prob);
-        population.push(child);
+    writeln!(out, "\"{}\" with seed {}:", context.target, seed).unwrap();
+    writeln!(
+        out,
+        "{:<16}{:>12}{:>12}",
+        "",
+        modes[0].to_string(),
+        modes[1].to_string()
+    )
+    .unwrap();
+    let mut row = |name: &str, cell: &dyn Fn(usize) -> String| {
+        writeln!(out, "{:<16}{:>12}{:>12}", name, cell(0), cell(1)).unwrap();
+    };
+    row("solved", &|i| {
+        if results[i].is_solved() { "yes" } else { "no" }.to_string()
+    });
+    row("generations", &|i| results[i].generations.to_string());
+    row("best fitness", &|i| {
+        format!("{}/{}", fitness_str(results[i].best.fitness), max_fitness)
+    });
+    row("elapsed", &|i| format!("{:.2?}", results[i].elapsed));
 
-        if let Some(last) = population.last_mut() {
-            last.in_focus = true;
-        }
-        true
-    } else {
-        false
+    // Sample the best fitness at evenly spaced generations and where each run ended; a
+    // run that already finished leaves its column empty.
+    let last = |genetic_algorithm: &GeneticAlgorithm<_>| {
+        genetic_algorithm
+            .stats()
+            .last()
+            .map_or(0, |stats| stats.generation)
+    };
+    let generations = genetic_algorithms.iter().map(last).max().unwrap_or(0);
+    let every = generations.div_ceil(COMPARISON_ROWS).max(1);
+    let mut sampled: BTreeSet<usize> = (0..=generations).step_by(every).collect();
+    sampled.extend(genetic_algorithms.iter().map(last));
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "{:<16}{:>12}{:>12}",
+        "best at gen",
+        modes[0].to_string(),
+        modes[1].to_string()
+    )
+    .unwrap();
+    let best_at = |i: usize, generation: usize| {
+        genetic_algorithms[i]
+            .stats()
+            .iter()
+            .find(|stats| stats.generation == generation)
+            .map_or(String::new(), |stats| stats.best_fitness.to_string())
+    };
+    for generation in sampled {
+        writeln!(
+            out,
+            "{:<16}{:>12}{:>12}",
+            generation,
+            best_at(0, generation),
+            best_at(1, generation)
+        )
+        .unwrap();
     }
+    writeln!(out).unwrap();
 }
 
-fn center_text(text: &str, width: usize) -> String {
-    if text.len() >= width {
-        text.to_string()
-    } else {
-        let padding = width - text.len();
-        let pad_left = padding / 2;
-        let pad_right = padding - pad_left;
-        format!("{}{}{}", " ".repeat(pad_left), text, " ".repeat(pad_right))
+fn main() {
+    let args = Args::parse(std::env::args().skip(1)).unwrap_or_else(|err| exit_with_usage(&err));
+    if args.list_strategies {
+        list_strategies(&mut std::io::stdout().lock());
+        return;
     }
-}
+    #[cfg(feature = "serde")]
+    if let Some(path) = &args.replay {
+        replay(&args, path);
+        return;
+    }
+    let population_size = args.population_size;
+    let format = args.format;
+    let color = args.color;
+    let histogram = args.histogram;
+    // A single generation is printed once it is done, not animated.
+    let headless = args.headless || args.once || args.result_only;
+    let multiple = args.targets.len() > 1;
 
-fn display(population: &[Candidate], label: &str, column_width: usize, target_str: &str) {
-    println!("\n\n");
-    println!(
-        "\x1b[1m\x1b[96m{}\x1b[0m\n",
-        center_text(label, column_width * NUM_COLUMNS)
-    );
-    let num_rows = POPULATION_SIZE / NUM_COLUMNS;
-    let mut cells = vec![vec![String::new(); NUM_COLUMNS]; num_rows];
-
-    for i in 0..POPULATION_SIZE {
-        let row_idx = i % num_rows;
-        let col_idx = i / num_rows;
-
-        if i >= population.len() {
-            cells[row_idx][col_idx] = " ".repeat(column_width);
-            continue;
+    // With several targets, each label says which run it belongs to.
+    let label_for = move |target_str: &str, label: &str| {
+        if multiple {
+            format!("{}: {}", target_str, label)
+        } else {
+            label.to_string()
         }
+    };
+    // All runs share the one connection; their labels tell them apart.
+    #[cfg(feature = "serde")]
+    let stream = args.stream.as_ref().filter(|_| !args.dry_run).map(|addr| {
+        let stream =
+            FrameStream::connect(addr.as_str(), args.stream_disconnect).unwrap_or_else(|err| {
+                eprintln!("error: cannot connect to {}: {}", addr, err);
+                std::process::exit(1);
+            });
+        Rc::new(RefCell::new(stream))
+    });
+    // The grid shows all runs at once, so it is drawn by the loop below; JSON lines and
+    // frames are written by each run as it steps.
+    #[cfg(feature = "serde")]
+    let json_callback = |target_str: String| {
+        let stream = stream.clone();
+        move |population: &Vec<Candidate>, label: &str, focus: &[usize]| {
+            if !headless && format == OutputFormat::Json {
+                println!(
+                    "{}",
+                    ga::json_line(population, &label_for(&target_str, label), focus)
+                );
+            }
+            match &stream {
+                Some(stream) => {
+                    stream
+                        .borrow_mut()
+                        .send(population, &label_for(&target_str, label), focus)
+                }
+                None => ControlFlow::Continue(()),
+            }
+        }
+    };
+    #[cfg(not(feature = "serde"))]
+    let json_callback =
+        |_: String| |_: &Vec<Candidate>, _: &str, _: &[usize]| ControlFlow::Continue(());
 
-        let padding = column_width - target_str.len() - 2;
-        cells[row_idx][col_idx] = format!(
-            "{}{}",
-            population[i].display_str(target_str),
-            " ".repeat(padding)
-        );
+    let mut genetic_algorithms = Vec::new();
+    for (i, target_str) in args.targets.iter().enumerate() {
+        let mut builder = GeneticAlgorithmBuilder::new()
+            .target(target_str.as_str())
+            .alphabet(args.alphabet.as_str())
+            .allow_unreachable(args.allow_unreachable)
+            .fitness_mode(args.fitness_mode.clone())
+            .ignore_case(args.ignore_case)
+            .incremental_fitness(args.incremental_fitness)
+            .fitness_cache(args.fitness_cache)
+            .seed_candidates(args.seed_candidates.clone())
+            .population_size(population_size)
+            .num_fit_to_keep(args.num_fit_to_keep)
+            .mutation_prob(args.mutation_prob)
+            .hint_prob(args.hint_prob)
+            .mutation_op(args.mutation_op)
+            .objective(args.objective)
+            .fast_sort(args.fast_sort || headless || format != OutputFormat::Grid)
+            .batch(headless || format == OutputFormat::Csv)
+            .selection(args.selection)
+            .crossover(args.crossover)
+            .num_parents(args.num_parents)
+            .cull(args.cull)
+            .replacement(args.replacement)
+            .tie_break(args.tie_break);
+        if let Some(alphabet_weights) = &args.alphabet_weights {
+            builder = builder.alphabet_weights(alphabet_weights.clone());
+        }
+        if let Some(init_len_min) = args.init_len_min {
+            builder = builder.init_len_min(init_len_min);
+        }
+        if let Some(init_len_max) = args.init_len_max {
+            builder = builder.init_len_max(init_len_max);
+        }
+        if let Some(mutation_schedule) = args.mutation_schedule {
+            builder = builder.mutation_schedule(mutation_schedule);
+        }
+        if let Some(elite_count) = args.elite_count {
+            builder = builder.elite_count(elite_count);
+        }
+        if let Some(generation_gap) = args.generation_gap {
+            builder = builder.generation_gap(generation_gap);
+        }
+        if let Some(patience) = args.patience {
+            builder = builder.patience(patience);
+        }
+        if let Some(max_generations) = args.max_generations {
+            builder = builder.max_generations(max_generations);
+        }
+        if let Some(restart) = args.restart {
+            builder = builder.restart(restart);
+        }
+        if let Some(fitness_sharing) = args.fitness_sharing {
+            builder = builder.fitness_sharing(fitness_sharing);
+        }
+        builder = builder.hall_of_fame(args.hall_of_fame);
+        builder = builder.lineage(args.track_lineage);
+        if let Some(seed) = args.seed {
+            builder = builder.seed(seed.wrapping_add(i as u64));
+        }
+        #[cfg(feature = "serde")]
+        if let Some(path) = &args.resume {
+            let checkpoint = ga::Checkpoint::load(path).unwrap_or_else(|err| {
+                eprintln!("error: cannot resume from {}: {}", path.display(), err);
+                std::process::exit(1);
+            });
+            builder = builder.resume(checkpoint);
+        }
+        if args.compare_crossover && !args.dry_run {
+            // Both runs need the same seed, so pick one to report if none was given.
+            let seed = args
+                .seed
+                .map_or_else(rand::random, |seed| seed.wrapping_add(i as u64));
+            let builder = builder.seed(seed).fast_sort(true).batch(true);
+            compare_crossover(&mut std::io::stdout().lock(), builder, seed);
+            continue;
+        }
+        let builder = builder.callback(json_callback(target_str.clone()));
+        if args.dry_run {
+            // Validate before printing, so a dry run also catches bad settings.
+            if let Err(err) = builder.clone().build() {
+                exit_with_usage(&err.to_string());
+            }
+            if i > 0 {
+                println!();
+            }
+            println!("{}", builder);
+            continue;
+        }
+        let genetic_algorithm = builder
+            .build()
+            .unwrap_or_else(|err| exit_with_usage(&err.to_string()));
+        let unreachable = genetic_algorithm.context().unreachable_chars();
+        if !unreachable.is_empty() {
+            eprintln!(
+                "warning: the alphabet lacks {:?}, so \"{}\" can reach at most {} of {}",
+                unreachable,
+                target_str,
+                genetic_algorithm.max_fitness(),
+                String::max_fitness(genetic_algorithm.context())
+            );
+        }
+        genetic_algorithms.push(genetic_algorithm);
     }
 
-    for row in cells {
-        println!("   {}", row.join(""));
+    if args.dry_run || args.compare_crossover {
+        return;
     }
 
-    println!("\n");
-}
+    if format == OutputFormat::Csv {
+        if multiple {
+            println!("target,{}", CSV_HEADER);
+        } else {
+            println!("{}", CSV_HEADER);
+        }
+    }
 
-fn main() {
-    let target_str_len = TARGET_STR.len();
-    let column_width = target_str_len + 6;
-    let mut population: Vec<Candidate> = Vec::new();
+    let animate = !headless && format == OutputFormat::Grid;
+    // The grid has room for the whole population unless --display-top limits it.
+    let num_cells = args
+        .display_top
+        .map_or(population_size, |top| top.min(population_size));
+    let render_frame = |genetic_algorithms: &[GeneticAlgorithm<_>],
+                        leaders: &[Option<Candidate>],
+                        paused: bool| {
+        // Measured every frame so the layout follows terminal resizes.
+        let terminal_width = terminal_size::terminal_size().map(|(width, _)| width.0 as usize);
+        let grids: Vec<Vec<String>> = genetic_algorithms
+            .iter()
+            .zip(leaders)
+            .map(|(genetic_algorithm, leader)| {
+                let target_str = genetic_algorithm.context().target.as_str();
+                let column_width = text_width(target_str) + 6;
+                let num_columns = terminal_width.map_or(NUM_COLUMNS, |width| {
+                    columns_for_width(width, column_width, genetic_algorithms.len()).min(num_cells)
+                });
+                let mut population = genetic_algorithm.population();
+                let mut focus = genetic_algorithm.focus().to_vec();
+                if let Some(top) = args.display_top {
+                    population = &population[..population.len().min(top)];
+                    focus.retain(|&i| i < top);
+                }
+                let mut grid = render_grid(
+                    population,
+                    &focus,
+                    &label_for(target_str, genetic_algorithm.state().description()),
+                    column_width,
+                    target_str,
+                    genetic_algorithm.max_fitness(),
+                    genetic_algorithm.context().ignore_case,
+                    num_cells,
+                    num_columns,
+                    color,
+                    args.shade
+                        .then_some(genetic_algorithm.context().alphabet.as_str()),
+                    leader.as_ref(),
+                );
+                if histogram {
+                    let max_fitness = genetic_algorithm.max_fitness().max(0) as usize;
+                    let counts = fitness_histogram(
+                        genetic_algorithm.population(),
+                        max_fitness,
+                        target_str.chars().count() + 1,
+                    );
+                    grid.push(String::new());
+                    grid.extend(render_histogram(
+                        &counts,
+                        max_fitness,
+                        column_width * num_columns,
+                    ));
+                }
+                grid
+            })
+            .collect();
+        let mut frame = render_grids(&grids);
+        if paused {
+            frame.push_str("paused: space resumes, n steps, q quits\n");
+        }
+        frame
+    };
 
-    let display_callback = move |population: &Vec<Candidate>, label: &str| {
-        use core::time::Duration;
-        use std::thread::sleep;
+    if args.once {
+        for genetic_algorithm in &mut genetic_algorithms {
+            genetic_algorithm.by_generation().next();
+        }
+        let leaders: Vec<Option<Candidate>> = genetic_algorithms
+            .iter()
+            .map(|genetic_algorithm| genetic_algorithm.best().filter(|_| args.leader).cloned())
+            .collect();
+        match format {
+            OutputFormat::Grid => print!("{}", render_frame(&genetic_algorithms, &leaders, false)),
+            OutputFormat::Csv => {
+                for genetic_algorithm in &genetic_algorithms {
+                    write_csv_row(&mut std::io::stdout().lock(), genetic_algorithm, multiple);
+                }
+            }
+            #[cfg(feature = "serde")]
+            OutputFormat::Json => {
+                for genetic_algorithm in &genetic_algorithms {
+                    let target_str = genetic_algorithm.context().target.as_str();
+                    println!(
+                        "{}",
+                        ga::json_line(
+                            genetic_algorithm.population(),
+                            &label_for(target_str, genetic_algorithm.state().description()),
+                            genetic_algorithm.focus(),
+                        )
+                    );
+                }
+            }
+        }
+        return;
+    }
 
-        sleep(Duration::from_millis(16));
-        print!("\x1b[H\x1b[J");
-        display(population, label, column_width, TARGET_STR);
+    let mut controls = if animate { Controls::enable() } else { None };
+    let draw = |frame: String, raw: bool| {
+        write_frame(&mut std::io::stdout().lock(), &frame, args.scroll, raw)
     };
 
-    let mut genetic_algorithm = GeneticAlgorithm::new(
-        &mut population,
-        TARGET_STR,
-        NUM_FIT_TO_KEEP,
-        POPULATION_SIZE,
-        MUTATION_PROB,
-        display_callback,
-    );
+    // Ctrl-C only stops the loop, so the terminal is restored and the best candidates so
+    // far are still reported.
+    let interrupted = Arc::new(AtomicBool::new(false));
+    {
+        let interrupted = Arc::clone(&interrupted);
+        if let Err(err) = ctrlc::set_handler(move || interrupted.store(true, Ordering::SeqCst)) {
+            eprintln!("warning: cannot handle Ctrl-C: {}", err);
+        }
+    }
+
+    // Frames are drawn every `--skip` steps and no sooner than `--fps` allows; the time
+    // spent stepping counts toward the wait.
+    let frame_time = Duration::from_secs_f64(1.0 / args.fps as f64);
+    let mut last_frame = std::time::Instant::now();
+    let mut steps_since_frame = 0;
 
+    // One run is served as an object, several as an array in the order of the targets.
+    #[cfg(feature = "metrics")]
+    let metrics = args.metrics.as_ref().map(|addr| {
+        ga::MetricsServer::bind(addr.as_str()).unwrap_or_else(|err| {
+            eprintln!("error: cannot serve metrics on {}: {}", addr, err);
+            std::process::exit(1);
+        })
+    });
+    #[cfg(feature = "metrics")]
+    let publish_metrics = |genetic_algorithms: &[GeneticAlgorithm<_>]| {
+        if let Some(metrics) = &metrics {
+            if multiple {
+                let runs: Vec<_> = genetic_algorithms.iter().map(ga::Metrics::of).collect();
+                metrics.publish(&runs);
+            } else {
+                metrics.publish(&ga::Metrics::of(&genetic_algorithms[0]));
+            }
+        }
+    };
+    #[cfg(feature = "metrics")]
+    publish_metrics(&genetic_algorithms);
+
+    let mut traces: Vec<Trace> = genetic_algorithms.iter().map(|_| Trace::new()).collect();
+    // The best candidate as of each run's last finished sort, for --leader.
+    let mut leaders: Vec<Option<Candidate>> = genetic_algorithms.iter().map(|_| None).collect();
     loop {
-        for _ in genetic_algorithm.by_ref() {}
+        if let Some(controls) = &mut controls {
+            controls.poll(Duration::ZERO);
+            while controls.paused && !controls.step && !controls.quit {
+                if controls.redraw {
+                    controls.redraw = false;
+                    draw(render_frame(&genetic_algorithms, &leaders, true), true);
+                }
+                controls.poll(Duration::from_millis(50));
+            }
+            if controls.quit {
+                interrupted.store(true, Ordering::SeqCst);
+            }
+        }
+        if interrupted.load(Ordering::SeqCst) {
+            break;
+        }
+        let mut running = false;
+        let mut stepped = false;
+        let mut scored_generation = false;
+        for ((genetic_algorithm, trace), leader) in genetic_algorithms
+            .iter_mut()
+            .zip(traces.iter_mut())
+            .zip(leaders.iter_mut())
+            .filter(|((g, _), _)| !g.is_finished())
+        {
+            running = true;
+            let generation = genetic_algorithm.generation();
+            let num_stats = genetic_algorithm.stats().len();
+            let state = genetic_algorithm.state().clone();
+            let started = args.trace.then(std::time::Instant::now);
+            stepped |= genetic_algorithm.next().is_some();
+            scored_generation |= genetic_algorithm.stats().len() > num_stats;
+            // A solved run stops while sorting, so its leader is the solution.
+            if args.leader
+                && (matches!(state, STATE::Reorder)
+                    && !matches!(genetic_algorithm.state(), STATE::Reorder)
+                    || genetic_algorithm.is_finished())
+            {
+                *leader = genetic_algorithm.best().cloned();
+            }
+            if let Some(started) = started {
+                if let Some(line) = trace.record(
+                    generation,
+                    &state,
+                    genetic_algorithm.state(),
+                    started.elapsed(),
+                ) {
+                    eprintln!("{}", label_for(&genetic_algorithm.context().target, &line));
+                }
+            }
+            if format == OutputFormat::Csv && genetic_algorithm.stats().len() > num_stats {
+                write_csv_row(&mut std::io::stdout().lock(), genetic_algorithm, multiple);
+            }
+            #[cfg(feature = "alloc-counts")]
+            if args.alloc_counts && genetic_algorithm.stats().len() > num_stats {
+                let stats = genetic_algorithm.stats().last().expect("just scored");
+                let line = format!("generation {}: {}", stats.generation, stats.alloc_counts);
+                eprintln!("{}", label_for(&genetic_algorithm.context().target, &line));
+            }
+            #[cfg(feature = "serde")]
+            if let Some(path) = &args.checkpoint {
+                if genetic_algorithm.generation() != generation {
+                    if let Err(err) = genetic_algorithm.checkpoint().save(path) {
+                        drop(controls.take());
+                        eprintln!("error: cannot write checkpoint {}: {}", path.display(), err);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        if !running {
+            break;
+        }
+        #[cfg(feature = "metrics")]
+        if scored_generation {
+            publish_metrics(&genetic_algorithms);
+        }
+        // Finishing to score a generation is a transition, which is not a step of its own.
+        if (stepped || scored_generation) && animate {
+            steps_since_frame += stepped as usize;
+            let due = if args.scroll {
+                scored_generation
+            } else {
+                stepped && steps_since_frame >= args.skip
+            };
+            if due {
+                steps_since_frame = 0;
+                if let Some(wait) = frame_time
+                    .checked_sub(last_frame.elapsed())
+                    .filter(|_| !args.scroll)
+                {
+                    std::thread::sleep(wait);
+                }
+                last_frame = std::time::Instant::now();
+                let paused = controls.as_ref().is_some_and(|controls| controls.paused);
+                draw(
+                    render_frame(&genetic_algorithms, &leaders, paused),
+                    controls.is_some(),
+                );
+                // A single step while paused lasts until the next frame is drawn.
+                if let Some(controls) = &mut controls {
+                    controls.step = false;
+                }
+            }
+        }
+    }
+    // Show the final state even if it fell between two drawn frames.
+    if animate && steps_since_frame > 0 {
+        draw(
+            render_frame(&genetic_algorithms, &leaders, false),
+            controls.is_some(),
+        );
+    }
+    drop(controls);
+    let interrupted = interrupted.load(Ordering::SeqCst);
+    if interrupted && animate {
+        // Reset colors and show the cursor in case the frame was cut off mid-escape.
+        print!("\x1b[0m\x1b[?25h");
+    }
+
+    // Keep stdout machine-readable when it carries JSON lines, and free of anything but
+    // the results with --result-only.
+    let report = |line: String| match format {
+        _ if args.result_only => {}
+        OutputFormat::Grid => println!("{}", line),
+        OutputFormat::Csv => eprintln!("{}", line),
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => eprintln!("{}", line),
+    };
+    let mut all_solved = true;
+    let mut any_stalled = false;
+    for genetic_algorithm in &genetic_algorithms {
+        let target_str = genetic_algorithm.context().target.as_str();
+        let max_fitness = String::max_fitness(genetic_algorithm.context());
+        let generation = genetic_algorithm.generation();
+        let (best_genome, best_fitness) = match genetic_algorithm.best() {
+            Some(best) => (best.genome.as_str(), fitness_str(best.fitness)),
+            None => ("", "-".to_string()),
+        };
+        if args.result_only {
+            println!("{} ({})", best_genome, outcome_str(genetic_algorithm));
+        }
+        if genetic_algorithm.is_solved() && genetic_algorithm.max_fitness() != max_fitness {
+            report(format!(
+                "reached the best possible \"{}\" ({}/{}) in {} generations",
+                best_genome, best_fitness, max_fitness, generation
+            ));
+        } else if genetic_algorithm.is_solved() {
+            report(format!(
+                "solved \"{}\" in {} generations",
+                best_genome, generation
+            ));
+        } else if genetic_algorithm.is_stalled() {
+            report(format!(
+                "stalled after {} generations without improvement, best was \"{}\" ({}/{})",
+                genetic_algorithm.generations_since_improvement(),
+                best_genome,
+                best_fitness,
+                max_fitness
+            ));
+            any_stalled = true;
+        } else if genetic_algorithm.is_stopped() {
+            report(format!(
+                "stopped after {} generations when the stream client went away, best was \"{}\" ({}/{})",
+                generation, best_genome, best_fitness, max_fitness
+            ));
+            all_solved = false;
+        } else if interrupted {
+            report(format!(
+                "interrupted after {} generations, best was \"{}\" ({}/{})",
+                generation, best_genome, best_fitness, max_fitness
+            ));
+            all_solved = false;
+        } else {
+            report(format!(
+                "no solution after {} generations, best was \"{}\" ({}/{})",
+                generation, best_genome, best_fitness, max_fitness
+            ));
+            all_solved = false;
+        }
+        report(label_for(
+            target_str,
+            &format!(
+                "{:.2?} elapsed, {:.0} generations per second",
+                genetic_algorithm.elapsed(),
+                genetic_algorithm.generations_per_second()
+            ),
+        ));
+        if args.restart.is_some() {
+            report(label_for(
+                target_str,
+                &format!("restarts: {}", genetic_algorithm.restarts()),
+            ));
+        }
+        if args.hint_prob > 0.0 {
+            report(label_for(
+                target_str,
+                &format!(
+                    "hinted characters: {}",
+                    genetic_algorithm.context().hints.get()
+                ),
+            ));
+        }
+        if !genetic_algorithm.hall_of_fame().is_empty() {
+            report(label_for(target_str, "hall of fame:"));
+            for (rank, famous) in genetic_algorithm.hall_of_fame().iter().enumerate() {
+                report(format!(
+                    "  {}. \"{}\" ({}/{})",
+                    rank + 1,
+                    famous.genome,
+                    fitness_str(famous.fitness),
+                    max_fitness
+                ));
+            }
+        }
+        if let (Some(lineage), Some(best)) = (genetic_algorithm.lineage(), genetic_algorithm.best())
+        {
+            report(label_for(target_str, "lineage of the best:"));
+            for ancestor in lineage.ancestry(best.id) {
+                report(format!(
+                    "  gen {:>4}  \"{}\" ({}/{})",
+                    ancestor.generation,
+                    ancestor.genome,
+                    ancestor.genome.fitness(genetic_algorithm.context()),
+                    max_fitness
+                ));
+            }
+        }
+        if let Some(cache) = genetic_algorithm.fitness_cache() {
+            report(label_for(
+                target_str,
+                &format!(
+                    "fitness cache: {:.0}% hits, {} distinct candidates scored",
+                    cache.hit_rate() * 100.0,
+                    cache.len()
+                ),
+            ));
+        }
+    }
+    if args.trace {
+        for (genetic_algorithm, trace) in genetic_algorithms.iter().zip(&traces) {
+            eprintln!(
+                "{}",
+                label_for(
+                    &genetic_algorithm.context().target,
+                    &format!("time spent: {}", trace.breakdown())
+                )
+            );
+        }
+    }
+    if interrupted && !all_solved {
+        std::process::exit(EXIT_INTERRUPTED);
+    }
+    if any_stalled {
+        std::process::exit(EXIT_STALLED);
+    }
+    if !all_solved {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,100 @@
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::ControlFlow;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::Serialize;
+
+use crate::algorithm::GeneticAlgorithm;
+use crate::candidate::Candidate;
+use crate::genome::{Crossover, Distance, Fitness};
+
+/// Where a run stands, as served by `MetricsServer`.
+#[derive(Clone, Debug, Serialize)]
+pub struct Metrics<'a, G, S = isize> {
+    pub generation: usize,
+    pub best_fitness: Option<S>,
+    pub max_fitness: S,
+    pub solved: bool,
+    pub finished: bool,
+    pub best: Option<&'a Candidate<G, S>>,
+}
+
+impl<'a, G> Metrics<'a, G, G::Score>
+where
+    G: Crossover + Fitness + Distance,
+{
+    pub fn of<F>(genetic_algorithm: &'a GeneticAlgorithm<F, G>) -> Self
+    where
+        F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    {
+        Self {
+            generation: genetic_algorithm.generation(),
+            best_fitness: genetic_algorithm.best_fitness(),
+            max_fitness: genetic_algorithm.max_fitness(),
+            solved: genetic_algorithm.is_solved(),
+            finished: genetic_algorithm.is_finished(),
+            best: genetic_algorithm.best(),
+        }
+    }
+}
+
+/// Answers every HTTP request with the JSON last `publish`ed, from a background thread,
+/// so that dashboards can poll a running process. The thread lives as long as the process.
+pub struct MetricsServer {
+    snapshot: Arc<Mutex<String>>,
+    local_addr: SocketAddr,
+}
+
+impl MetricsServer {
+    /// Listens on `addr`; serves `null` until the first `publish`.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let snapshot = Arc::new(Mutex::new("null".to_string()));
+        let served = Arc::clone(&snapshot);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let body = served.lock().expect("publishing never panics").clone();
+                // A client that hangs up early is its own problem.
+                let _ = respond(stream, &body);
+            }
+        });
+        Ok(Self {
+            snapshot,
+            local_addr,
+        })
+    }
+
+    /// The address actually bound, e.g. to find the port after binding port 0.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Serves `snapshot`, e.g. `Metrics::of` a run, from now on.
+    pub fn publish<T: Serialize>(&self, snapshot: &T) {
+        let json = serde_json::to_string(snapshot).expect("metrics are always serializable");
+        *self.snapshot.lock().expect("serving never panics") = json;
+    }
+}
+
+/// Reads the request head, whatever it asks for, and answers with `body`.
+fn respond(mut stream: TcpStream, body: &str) -> io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,398 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::prelude::*;
+
+use crate::alphabet::Alphabet;
+use crate::crossover::mutate;
+use crate::strategy::Strategy;
+
+/// How a string genome is mutated after recombination. Every operator but `Scramble`
+/// fires at each position with the mutation probability.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MutationOp {
+    /// Replace the character with a random one from the alphabet.
+    #[default]
+    Replace,
+    /// Swap the character with the one after it. Introduces no new characters, so it
+    /// relies on crossover and the initial population to supply them.
+    SwapAdjacent,
+    /// Replace the character with the one before or after it in the alphabet, wrapping
+    /// around, so a mutation only moves a small step.
+    NeighborShift,
+    /// Delete the character or insert a random one before it. Changes the genome's length,
+    /// so it is meant for length-independent fitness such as `levenshtein`.
+    InsertDelete,
+    /// Shuffle a random window of 2 to `max_len` characters, with the mutation probability
+    /// per candidate rather than per position. A larger jump that can leave a plateau
+    /// without randomizing the genome; like `SwapAdjacent` it introduces no new characters.
+    Scramble { max_len: usize },
+}
+
+/// Parses `replace`, `swap`, `neighbor`, `insert-delete` or `scramble:N` with N at least 2.
+impl FromStr for MutationOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "replace" => Ok(MutationOp::Replace),
+            "swap" => Ok(MutationOp::SwapAdjacent),
+            "neighbor" => Ok(MutationOp::NeighborShift),
+            "insert-delete" => Ok(MutationOp::InsertDelete),
+            _ => match s.strip_prefix("scramble:") {
+                Some(max_len) => match max_len.parse() {
+                    Ok(max_len) if max_len >= 2 => Ok(MutationOp::Scramble { max_len }),
+                    _ => Err(format!(
+                        "invalid scramble window '{}', expected a length of at least 2",
+                        max_len
+                    )),
+                },
+                None => Err(format!("unknown mutation operator '{}'", s)),
+            },
+        }
+    }
+}
+
+/// The name `from_str` accepts.
+impl fmt::Display for MutationOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MutationOp::Replace => f.write_str("replace"),
+            MutationOp::SwapAdjacent => f.write_str("swap"),
+            MutationOp::NeighborShift => f.write_str("neighbor"),
+            MutationOp::InsertDelete => f.write_str("insert-delete"),
+            MutationOp::Scramble { max_len } => write!(f, "scramble:{}", max_len),
+        }
+    }
+}
+
+impl Strategy for MutationOp {
+    fn variants() -> Vec<Self> {
+        vec![
+            MutationOp::Replace,
+            MutationOp::SwapAdjacent,
+            MutationOp::NeighborShift,
+            MutationOp::InsertDelete,
+            MutationOp::Scramble { max_len: 4 },
+        ]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            MutationOp::Replace => "Replace a character with a random one from the alphabet",
+            MutationOp::SwapAdjacent => "Swap a character with the one after it",
+            MutationOp::NeighborShift => "Move a character one step up or down the alphabet",
+            MutationOp::InsertDelete => {
+                "Delete a character or insert a random one, changing the length"
+            }
+            MutationOp::Scramble { .. } => "Shuffle a random window of up to that many characters",
+        }
+    }
+}
+
+impl MutationOp {
+    /// Mutates `text` with this operator, drawing new characters from `alphabet`.
+    pub fn apply(
+        &self,
+        text: &str,
+        mutation_prob: f64,
+        alphabet: &(impl Alphabet + ?Sized),
+        rng: &mut impl Rng,
+    ) -> String {
+        match self {
+            MutationOp::Replace => mutate(text, mutation_prob, alphabet, rng),
+            MutationOp::SwapAdjacent => swap_adjacent(text, mutation_prob, rng),
+            MutationOp::NeighborShift => shift_to_neighbor(text, mutation_prob, alphabet, rng),
+            MutationOp::InsertDelete => insert_delete(text, mutation_prob, alphabet, rng),
+            MutationOp::Scramble { max_len } => scramble(text, mutation_prob, *max_len, rng),
+        }
+    }
+}
+
+/// Swaps each character with the next one with probability `mutation_prob`. The result
+/// holds the same characters as `text`.
+pub fn swap_adjacent(text: &str, mutation_prob: f64, rng: &mut impl Rng) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for i in 1..chars.len() {
+        if rng.gen_bool(mutation_prob) {
+            chars.swap(i - 1, i);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Sets each character of `mutated` that differs from `original` at the same position to
+/// the character of `target` there, with probability `hint_prob`. Returns the result and
+/// how many characters were set.
+pub fn hint(
+    original: &str,
+    mutated: &str,
+    target: &str,
+    hint_prob: f64,
+    rng: &mut impl Rng,
+) -> (String, usize) {
+    let mut original = original.chars();
+    let mut target = target.chars();
+    let mut hints = 0;
+    let hinted = mutated
+        .chars()
+        .map(|c| match (original.next(), target.next()) {
+            (Some(before), Some(target_char))
+                if c != before && c != target_char && rng.gen_bool(hint_prob) =>
+            {
+                hints += 1;
+                target_char
+            }
+            _ => c,
+        })
+        .collect();
+    (hinted, hints)
+}
+
+/// Moves each character one step up or down `alphabet` with probability `mutation_prob`,
+/// wrapping around at the ends. Characters outside the alphabet are replaced with a random
+/// one from it.
+pub fn shift_to_neighbor(
+    text: &str,
+    mutation_prob: f64,
+    alphabet: &(impl Alphabet + ?Sized),
+    rng: &mut impl Rng,
+) -> String {
+    let letters: Vec<char> = alphabet.letters().chars().collect();
+    text.chars()
+        .map(|c| {
+            if !rng.gen_bool(mutation_prob) {
+                return c;
+            }
+            match letters.iter().position(|&a| a == c) {
+                Some(i) => {
+                    let step = if rng.gen_bool(0.5) {
+                        1
+                    } else {
+                        letters.len() - 1
+                    };
+                    letters[(i + step) % letters.len()]
+                }
+                None => alphabet.random_char(rng),
+            }
+        })
+        .collect()
+}
+
+/// With probability `mutation_prob` per character, deletes it or inserts a random one from
+/// `alphabet` before it, with equal odds. The last remaining character is never deleted.
+pub fn insert_delete(
+    text: &str,
+    mutation_prob: f64,
+    alphabet: &(impl Alphabet + ?Sized),
+    rng: &mut impl Rng,
+) -> String {
+    let len = text.chars().count();
+    let mut deleted = 0;
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !rng.gen_bool(mutation_prob) {
+            out.push(c);
+        } else if rng.gen_bool(0.5) && deleted + 1 < len {
+            deleted += 1;
+        } else {
+            out.push(alphabet.random_char(rng));
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// With probability `mutation_prob`, shuffles a random window of 2 to `max_len` characters
+/// and leaves the rest alone. The result holds the same characters as `text`.
+pub fn scramble(text: &str, mutation_prob: f64, max_len: usize, rng: &mut impl Rng) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let max_len = max_len.min(chars.len());
+    if max_len < 2 || !rng.gen_bool(mutation_prob) {
+        return text.to_string();
+    }
+    let len = rng.gen_range(2..=max_len);
+    let start = rng.gen_range(0..=chars.len() - len);
+    chars[start..start + len].shuffle(rng);
+    chars.into_iter().collect()
+}
+
+/// A mutation probability that depends only on the generation, like the temperature of
+/// simulated annealing: high early on to explore, lower later to fine-tune. Unlike
+/// `AdaptiveMutation` it does not look at how the run is going.
+pub trait MutationSchedule {
+    fn rate(&self, generation: usize) -> f64;
+}
+
+impl<F: Fn(usize) -> f64> MutationSchedule for F {
+    fn rate(&self, generation: usize) -> f64 {
+        self(generation)
+    }
+}
+
+/// The built-in schedules.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cooling {
+    /// The same rate every generation, which is what a plain mutation probability does.
+    Constant(f64),
+    /// Falls in a straight line from `start` to `floor` over `generations`, then stays.
+    Linear {
+        start: f64,
+        floor: f64,
+        generations: usize,
+    },
+    /// `start` multiplied by `decay` every generation, but never below `floor`.
+    Exponential { start: f64, floor: f64, decay: f64 },
+}
+
+impl Strategy for Cooling {
+    fn variants() -> Vec<Self> {
+        vec![
+            Cooling::Constant(0.15),
+            Cooling::Linear {
+                start: 0.5,
+                floor: 0.05,
+                generations: 100,
+            },
+            Cooling::Exponential {
+                start: 0.5,
+                floor: 0.05,
+                decay: 0.97,
+            },
+        ]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Cooling::Constant(_) => "The same mutation probability every generation",
+            Cooling::Linear { .. } => {
+                "Falls in a straight line from the start rate to the floor over the generations"
+            }
+            Cooling::Exponential { .. } => {
+                "The start rate multiplied by the decay every generation, down to the floor"
+            }
+        }
+    }
+}
+
+impl Cooling {
+    /// The highest and lowest rate the schedule produces.
+    pub fn range(&self) -> (f64, f64) {
+        match *self {
+            Cooling::Constant(rate) => (rate, rate),
+            Cooling::Linear { start, floor, .. } | Cooling::Exponential { start, floor, .. } => {
+                (start, floor)
+            }
+        }
+    }
+}
+
+impl MutationSchedule for Cooling {
+    fn rate(&self, generation: usize) -> f64 {
+        match *self {
+            Cooling::Constant(rate) => rate,
+            Cooling::Linear {
+                start,
+                floor,
+                generations,
+            } => {
+                let progress = (generation as f64 / generations.max(1) as f64).min(1.0);
+                start + (floor - start) * progress
+            }
+            Cooling::Exponential {
+                start,
+                floor,
+                decay,
+            } => (start * decay.powi(generation.min(i32::MAX as usize) as i32)).max(floor),
+        }
+    }
+}
+
+/// Parses `constant:P`, `linear:START:FLOOR:GENERATIONS` or
+/// `exponential:START:FLOOR:DECAY`, e.g. `linear:0.5:0.05:200`.
+impl FromStr for Cooling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("expected a number, got '{}'", value))
+        };
+        let parts: Vec<&str> = s.split(':').collect();
+        match parts[..] {
+            ["constant", rate] => Ok(Cooling::Constant(number(rate)?)),
+            ["linear", start, floor, generations] => Ok(Cooling::Linear {
+                start: number(start)?,
+                floor: number(floor)?,
+                generations: generations.parse().map_err(|_| {
+                    format!(
+                        "number of generations must be a non-negative integer, got '{}'",
+                        generations
+                    )
+                })?,
+            }),
+            ["exponential", start, floor, decay] => Ok(Cooling::Exponential {
+                start: number(start)?,
+                floor: number(floor)?,
+                decay: number(decay)?,
+            }),
+            _ => Err(format!("unknown mutation schedule '{}'", s)),
+        }
+    }
+}
+
+/// The form `from_str` accepts.
+impl fmt::Display for Cooling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cooling::Constant(rate) => write!(f, "constant:{}", rate),
+            Cooling::Linear {
+                start,
+                floor,
+                generations,
+            } => write!(f, "linear:{}:{}:{}", start, floor, generations),
+            Cooling::Exponential {
+                start,
+                floor,
+                decay,
+            } => write!(f, "exponential:{}:{}:{}", start, floor, decay),
+        }
+    }
+}
+
+/// Raises the mutation probability while the best fitness is stuck on a plateau and lowers it
+/// back to the base rate once it improves again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveMutation {
+    /// Generations without improvement before the rate starts rising.
+    pub patience: usize,
+    /// The rate never rises above this.
+    pub max_prob: f64,
+    /// The rate is multiplied by this every stagnant generation past `patience`, and divided
+    /// by it every generation that improves.
+    pub factor: f64,
+}
+
+impl Default for AdaptiveMutation {
+    fn default() -> Self {
+        Self {
+            patience: 10,
+            max_prob: 0.5,
+            factor: 1.5,
+        }
+    }
+}
+
+impl AdaptiveMutation {
+    /// The mutation probability for the next generation.
+    pub fn adapt(&self, current: f64, base: f64, generations_since_improvement: usize) -> f64 {
+        if generations_since_improvement == 0 {
+            (current / self.factor).max(base)
+        } else if generations_since_improvement >= self.patience {
+            (current * self.factor).min(self.max_prob)
+        } else {
+            current
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::genome::Score;
+use crate::strategy::Strategy;
+
+/// Whether higher or lower fitness wins. Either way a candidate that is not scored yet
+/// ranks below every scored one.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Objective {
+    #[default]
+    Maximize,
+    /// For distance-like scores, where the perfect solution scores lowest.
+    Minimize,
+}
+
+impl Strategy for Objective {
+    fn variants() -> Vec<Self> {
+        vec![Objective::Maximize, Objective::Minimize]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Objective::Maximize => "Higher fitness is better",
+            Objective::Minimize => "Lower fitness is better, for distance-like scores",
+        }
+    }
+}
+
+impl Objective {
+    /// A sort key for `fitness` where higher is always better; `None` sorts last.
+    pub fn key<S: Ord>(&self, fitness: Option<S>) -> Option<Key<S>> {
+        fitness.map(|score| Key {
+            score,
+            objective: *self,
+        })
+    }
+
+    /// Whether fitness `a` beats fitness `b`.
+    pub fn is_better<S: Ord>(&self, a: Option<S>, b: Option<S>) -> bool {
+        self.key(a) > self.key(b)
+    }
+}
+
+/// A score ordered so that the better one is greater, see `Objective::key`. Only keys
+/// made by the same objective are meant to be compared.
+#[derive(Clone, Copy, Debug)]
+pub struct Key<S> {
+    pub score: S,
+    pub objective: Objective,
+}
+
+impl<S: Score> Key<S> {
+    /// The score as a float that, like the key, is greater when better.
+    pub fn to_f64(self) -> f64 {
+        match self.objective {
+            Objective::Maximize => self.score.to_f64(),
+            Objective::Minimize => -self.score.to_f64(),
+        }
+    }
+}
+
+impl<S: Ord> Ord for Key<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.objective {
+            Objective::Maximize => self.score.cmp(&other.score),
+            Objective::Minimize => other.score.cmp(&self.score),
+        }
+    }
+}
+
+impl<S: Ord> PartialOrd for Key<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Ord> PartialEq for Key<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: Ord> Eq for Key<S> {}
+
+/// Parses `maximize` or `minimize`.
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "maximize" => Ok(Objective::Maximize),
+            "minimize" => Ok(Objective::Minimize),
+            _ => Err(format!("unknown objective '{}'", s)),
+        }
+    }
+}
+
+/// The name `from_str` accepts.
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Objective::Maximize => "maximize",
+            Objective::Minimize => "minimize",
+        })
+    }
+}
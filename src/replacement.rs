@@ -0,0 +1,136 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::prelude::*;
+
+use crate::candidate::{breed_at, Candidate};
+use crate::crossover::CrossoverMode;
+use crate::genome::{Crossover, Fitness};
+use crate::objective::Objective;
+use crate::selection::{breeding_parents, SelectionStrategy};
+use crate::strategy::Strategy;
+
+/// How children enter the population.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReplacementMode {
+    /// Cull down to the survivors, then breed the population back up.
+    #[default]
+    Generational,
+    /// Breed one child at a time; it replaces the worst candidate if it is fitter.
+    /// A generation is one attempt per member of the population.
+    SteadyState,
+}
+
+impl Strategy for ReplacementMode {
+    fn variants() -> Vec<Self> {
+        vec![ReplacementMode::Generational, ReplacementMode::SteadyState]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            ReplacementMode::Generational => {
+                "Cull down to the survivors, then breed the population back up"
+            }
+            ReplacementMode::SteadyState => {
+                "Breed one child at a time to replace the worst candidate"
+            }
+        }
+    }
+}
+
+/// Parses `generational` or `steady-state`.
+impl FromStr for ReplacementMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "generational" => Ok(ReplacementMode::Generational),
+            "steady-state" => Ok(ReplacementMode::SteadyState),
+            _ => Err(format!("unknown replacement mode '{}'", s)),
+        }
+    }
+}
+
+/// The name `from_str` accepts.
+impl fmt::Display for ReplacementMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ReplacementMode::Generational => "generational",
+            ReplacementMode::SteadyState => "steady-state",
+        })
+    }
+}
+
+/// The outcome of one `replace_worst` attempt, as indices into the population after it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replacement {
+    /// The parents, unless one of them was the candidate replaced.
+    pub parents: Vec<usize>,
+    /// Where the child was inserted, if it replaced the worst candidate.
+    pub child: Option<usize>,
+}
+
+/// Breeds and scores one child and lets it replace the last, i.e. worst, candidate of a
+/// population sorted by fitness if it scores better. The child is inserted where it keeps
+/// the population sorted.
+#[allow(clippy::too_many_arguments)]
+pub fn replace_worst<G: Crossover + Fitness>(
+    population: &mut Vec<Candidate<G, G::Score>>,
+    mutation_prob: f64,
+    selection: &SelectionStrategy,
+    objective: Objective,
+    crossover: CrossoverMode,
+    num_parents: usize,
+    context: &G::Context,
+    rng: &mut impl Rng,
+) -> Replacement {
+    let selected = breeding_parents(
+        population,
+        selection,
+        num_parents,
+        crossover,
+        objective,
+        rng,
+    );
+    let mut child = breed_at(
+        population,
+        &selected,
+        crossover,
+        mutation_prob,
+        context,
+        rng,
+    );
+    if child.fitness.is_none() {
+        child.set_fitness(context);
+    }
+    // A parent picked more than once is listed once.
+    let mut parents = Vec::new();
+    for p in selected {
+        if !parents.contains(&p) {
+            parents.push(p);
+        }
+    }
+    match population.last() {
+        Some(worst) if objective.is_better(child.fitness, worst.fitness) => {
+            population.pop();
+            let position = population
+                .partition_point(|c| objective.key(c.fitness) >= objective.key(child.fitness));
+            population.insert(position, child);
+            // The worst candidate is gone and everything from `position` on moved down one.
+            let worst = population.len() - 1;
+            let parents = parents
+                .into_iter()
+                .filter(|&p| p != worst)
+                .map(|p| if p >= position { p + 1 } else { p })
+                .collect();
+            Replacement {
+                parents,
+                child: Some(position),
+            }
+        }
+        _ => Replacement {
+            parents,
+            child: None,
+        },
+    }
+}
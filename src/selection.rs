@@ -0,0 +1,321 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::prelude::*;
+
+use crate::candidate::Candidate;
+use crate::crossover::CrossoverMode;
+use crate::genome::Score;
+use crate::objective::Objective;
+use crate::strategy::Strategy;
+
+/// How `breed_new` picks the parents of each child.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SelectionStrategy {
+    /// Two different candidates picked uniformly at random.
+    #[default]
+    RandomPair,
+    /// Each parent is the fittest of `k` candidates drawn at random.
+    Tournament { k: usize },
+    /// Each parent is picked with probability proportional to its fitness.
+    RouletteWheel,
+    /// Linear ranking: the probability falls linearly from `pressure / n` for the best of
+    /// the `n` scored candidates to `(2 - pressure) / n` for the worst, however far apart
+    /// their scores are. `pressure` lies between 1 (uniform) and 2.
+    Rank { pressure: f64 },
+}
+
+impl Strategy for SelectionStrategy {
+    fn variants() -> Vec<Self> {
+        vec![
+            SelectionStrategy::RandomPair,
+            SelectionStrategy::Tournament { k: 3 },
+            SelectionStrategy::RouletteWheel,
+            SelectionStrategy::Rank { pressure: 1.5 },
+        ]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            SelectionStrategy::RandomPair => "Two different candidates picked uniformly at random",
+            SelectionStrategy::Tournament { .. } => {
+                "Each parent is the fittest of that many candidates drawn at random"
+            }
+            SelectionStrategy::RouletteWheel => {
+                "Parents picked with probability proportional to their fitness"
+            }
+            SelectionStrategy::Rank { .. } => {
+                "Parents picked by rank, the best that many times (1 to 2) as often as average"
+            }
+        }
+    }
+}
+
+impl SelectionStrategy {
+    /// Pick a single parent, for asexual breeding.
+    pub fn select_one<G, S: Score>(
+        &self,
+        population: &[Candidate<G, S>],
+        objective: Objective,
+        rng: &mut impl Rng,
+    ) -> usize {
+        match *self {
+            SelectionStrategy::RandomPair => rng.gen_range(0..population.len()),
+            SelectionStrategy::Tournament { k } => tournament(population, k, objective, None, rng),
+            SelectionStrategy::RouletteWheel => roulette(population, objective, None, rng),
+            SelectionStrategy::Rank { pressure } => {
+                rank(population, pressure, objective, None, rng)
+            }
+        }
+    }
+
+    /// Pick two distinct parents. With a single candidate both indices are 0, and breeding
+    /// a genome with itself leaves only mutation, so the child is an asexual copy.
+    pub fn select_parents<G, S: Score>(
+        &self,
+        population: &[Candidate<G, S>],
+        objective: Objective,
+        rng: &mut impl Rng,
+    ) -> (usize, usize) {
+        let num_fit = population.len();
+        if num_fit < 2 {
+            return (0, 0);
+        }
+        match *self {
+            SelectionStrategy::RandomPair => {
+                let i = rng.gen_range(0..num_fit);
+                let j = (i + rng.gen_range(1..num_fit)) % num_fit;
+                (i, j)
+            }
+            SelectionStrategy::Tournament { k } => {
+                let i = tournament(population, k, objective, None, rng);
+                (i, tournament(population, k, objective, Some(i), rng))
+            }
+            SelectionStrategy::RouletteWheel => {
+                let i = roulette(population, objective, None, rng);
+                (i, roulette(population, objective, Some(i), rng))
+            }
+            SelectionStrategy::Rank { pressure } => {
+                let i = rank(population, pressure, objective, None, rng);
+                (i, rank(population, pressure, objective, Some(i), rng))
+            }
+        }
+    }
+
+    /// Pick `k` parents: one as `select_one` does, otherwise two distinct ones as
+    /// `select_parents` does and any more with `select_one`, which may pick the same
+    /// candidate again.
+    pub fn select_many<G, S: Score>(
+        &self,
+        population: &[Candidate<G, S>],
+        k: usize,
+        objective: Objective,
+        rng: &mut impl Rng,
+    ) -> Vec<usize> {
+        if k == 1 {
+            return vec![self.select_one(population, objective, rng)];
+        }
+        let (i, j) = self.select_parents(population, objective, rng);
+        let mut parents = vec![i, j];
+        while parents.len() < k {
+            parents.push(self.select_one(population, objective, rng));
+        }
+        parents
+    }
+}
+
+/// The parents of the next child: `num_parents` of them picked by `selection`, or one when
+/// `crossover` is asexual. A biased uniform crossover gets the fitter of two parents first.
+pub(crate) fn breeding_parents<G, S: Score>(
+    population: &[Candidate<G, S>],
+    selection: &SelectionStrategy,
+    num_parents: usize,
+    crossover: CrossoverMode,
+    objective: Objective,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let num_parents = if crossover == CrossoverMode::Asexual {
+        1
+    } else {
+        num_parents
+    };
+    let mut parents = selection.select_many(population, num_parents, objective, rng);
+    if let [i, j] = parents[..] {
+        if crossover.swaps_parents(population[i].fitness, population[j].fitness, objective) {
+            parents.swap(0, 1);
+        }
+    }
+    parents
+}
+
+/// Parses `random`, `roulette`, `tournament:K` or `rank:P`, e.g. `tournament:3` or
+/// `rank:1.5`.
+impl FromStr for SelectionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "random" => Ok(SelectionStrategy::RandomPair),
+            None if s == "roulette" => Ok(SelectionStrategy::RouletteWheel),
+            Some(("tournament", k)) => match k.parse() {
+                Ok(k) if k > 0 => Ok(SelectionStrategy::Tournament { k }),
+                _ => Err(format!(
+                    "tournament size must be a positive integer, got '{}'",
+                    k
+                )),
+            },
+            Some(("rank", pressure)) => match pressure.parse() {
+                Ok(pressure) if (1.0..=2.0).contains(&pressure) => {
+                    Ok(SelectionStrategy::Rank { pressure })
+                }
+                _ => Err(format!(
+                    "rank pressure must be a number between 1 and 2, got '{}'",
+                    pressure
+                )),
+            },
+            _ => Err(format!("unknown selection strategy '{}'", s)),
+        }
+    }
+}
+
+/// The form `from_str` accepts.
+impl fmt::Display for SelectionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectionStrategy::RandomPair => write!(f, "random"),
+            SelectionStrategy::Tournament { k } => write!(f, "tournament:{}", k),
+            SelectionStrategy::RouletteWheel => write!(f, "roulette"),
+            SelectionStrategy::Rank { pressure } => write!(f, "rank:{}", pressure),
+        }
+    }
+}
+
+pub fn select_tournament<G, S: Score>(
+    population: &[Candidate<G, S>],
+    k: usize,
+    objective: Objective,
+    rng: &mut impl Rng,
+) -> usize {
+    tournament(population, k, objective, None, rng)
+}
+
+/// Fitness-proportionate selection. Unscored candidates (`fitness == None`) are never
+/// picked unless nothing has been scored; if all weights are zero the pick is uniform
+/// among the scored candidates.
+/// When minimizing, a candidate's weight is how far it scores below the worst one.
+pub fn select_roulette<G, S: Score>(
+    population: &[Candidate<G, S>],
+    objective: Objective,
+    rng: &mut impl Rng,
+) -> usize {
+    roulette(population, objective, None, rng)
+}
+
+/// A uniformly random index, skipping `exclude`.
+fn random_index(len: usize, exclude: Option<usize>, rng: &mut impl Rng) -> usize {
+    match exclude {
+        Some(excluded) => {
+            let i = rng.gen_range(0..len - 1);
+            if i >= excluded {
+                i + 1
+            } else {
+                i
+            }
+        }
+        None => rng.gen_range(0..len),
+    }
+}
+
+fn tournament<G, S: Score>(
+    population: &[Candidate<G, S>],
+    k: usize,
+    objective: Objective,
+    exclude: Option<usize>,
+    rng: &mut impl Rng,
+) -> usize {
+    (0..k)
+        .map(|_| random_index(population.len(), exclude, rng))
+        .max_by(|&i, &j| population[i].rank_cmp(&population[j], objective))
+        .unwrap()
+}
+
+fn roulette<G, S: Score>(
+    population: &[Candidate<G, S>],
+    objective: Objective,
+    exclude: Option<usize>,
+    rng: &mut impl Rng,
+) -> usize {
+    let worst = population
+        .iter()
+        .filter_map(|c| c.fitness)
+        .max()
+        .map_or(0.0, Score::to_f64);
+    // Shared out by the niche count like `Candidate::rank_key`.
+    let weight = |c: &Candidate<G, S>| match (objective, c.fitness) {
+        (_, None) => 0.0,
+        (Objective::Maximize, Some(fitness)) => fitness.to_f64().max(0.0) / c.niche_count,
+        (Objective::Minimize, Some(fitness)) => (worst - fitness.to_f64()) / c.niche_count,
+    };
+    let cumulative: Vec<f64> = population
+        .iter()
+        .enumerate()
+        .scan(0.0, |total, (i, c)| {
+            if Some(i) != exclude {
+                *total += weight(c);
+            }
+            Some(*total)
+        })
+        .collect();
+    let total = cumulative.last().copied().unwrap_or(0.0);
+    if total <= 0.0 {
+        let scored: Vec<usize> = (0..population.len())
+            .filter(|&i| population[i].fitness.is_some() && Some(i) != exclude)
+            .collect();
+        return match scored.choose(rng) {
+            Some(&i) => i,
+            None => random_index(population.len(), exclude, rng),
+        };
+    }
+    let pick = rng.gen_range(0.0..total);
+    cumulative.partition_point(|&w| w <= pick)
+}
+
+/// Linear rank selection, see `SelectionStrategy::Rank`.
+pub fn select_rank<G, S: Score>(
+    population: &[Candidate<G, S>],
+    pressure: f64,
+    objective: Objective,
+    rng: &mut impl Rng,
+) -> usize {
+    rank(population, pressure, objective, None, rng)
+}
+
+fn rank<G, S: Score>(
+    population: &[Candidate<G, S>],
+    pressure: f64,
+    objective: Objective,
+    exclude: Option<usize>,
+    rng: &mut impl Rng,
+) -> usize {
+    // Usually already in order after `Reorder`, in which case the sort is a single pass.
+    let mut ranked: Vec<usize> = (0..population.len())
+        .filter(|&i| population[i].fitness.is_some() && Some(i) != exclude)
+        .collect();
+    ranked.sort_by(|&i, &j| population[j].rank_cmp(&population[i], objective));
+    let n = ranked.len();
+    match n {
+        0 => return random_index(population.len(), exclude, rng),
+        1 => return ranked[0],
+        _ => {}
+    }
+    let weight = |r: usize| pressure - (2.0 * pressure - 2.0) * r as f64 / (n - 1) as f64;
+    let mut pick = rng.gen::<f64>() * n as f64;
+    for (r, &i) in ranked.iter().enumerate() {
+        pick -= weight(r);
+        if pick < 0.0 {
+            return i;
+        }
+    }
+    // Rounding can leave a sliver past the last weight.
+    ranked[n - 1]
+}
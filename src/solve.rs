@@ -0,0 +1,82 @@
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use crate::algorithm::{generations_per_second, GeneticAlgorithm};
+use crate::candidate::Candidate;
+use crate::genome::{Crossover, Distance, Fitness};
+
+/// Why `GeneticAlgorithm::solve` stopped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// A candidate reached the target fitness.
+    Reached,
+    /// The generation cap set with `with_max_generations` ran out first.
+    GenerationLimit,
+    /// The best fitness stopped improving, see `with_patience`.
+    Stalled,
+    /// The callback returned `ControlFlow::Break`, or the run was stopped from outside,
+    /// e.g. by `IslandModel` once another island reached the target.
+    Stopped,
+}
+
+/// The result of a complete run.
+#[derive(Clone, Debug)]
+pub struct SolveResult<G = String, S = isize> {
+    /// The best candidate found.
+    pub best: Candidate<G, S>,
+    /// Number of completed generations.
+    pub generations: usize,
+    pub outcome: Outcome,
+    /// Time the run took, see `GeneticAlgorithm::elapsed`.
+    pub elapsed: Duration,
+}
+
+impl<G, S> SolveResult<G, S> {
+    /// Whether the best candidate reached the target fitness.
+    pub fn is_solved(&self) -> bool {
+        self.outcome == Outcome::Reached
+    }
+
+    /// Completed generations per second, or 0 if no time was measured.
+    pub fn generations_per_second(&self) -> f64 {
+        generations_per_second(self.generations, self.elapsed)
+    }
+}
+
+impl<F, G> GeneticAlgorithm<F, G>
+where
+    F: Fn(&Vec<Candidate<G, G::Score>>, &str, &[usize]) -> ControlFlow<()>,
+    G: Crossover + Fitness + Distance,
+{
+    /// Runs until the run is finished and reports the best candidate, e.g. for embedding
+    /// the algorithm without driving the iterator. The callback still sees every step.
+    /// Without a generation cap, patience or a callback that stops the run this only
+    /// returns once the target is reached.
+    pub fn solve(&mut self) -> SolveResult<G, G::Score> {
+        for _ in self.by_generation() {}
+        self.result()
+    }
+
+    /// The run so far summed up like `solve` does; a run that is not finished counts as
+    /// stopped.
+    pub(crate) fn result(&self) -> SolveResult<G, G::Score> {
+        let outcome = if self.is_solved() {
+            Outcome::Reached
+        } else if self.is_stalled() {
+            Outcome::Stalled
+        } else if self.is_stopped() || !self.is_finished() {
+            Outcome::Stopped
+        } else {
+            Outcome::GenerationLimit
+        };
+        SolveResult {
+            best: self
+                .best()
+                .expect("a finished run has scored candidates")
+                .clone(),
+            generations: self.generation(),
+            outcome,
+            elapsed: self.elapsed(),
+        }
+    }
+}
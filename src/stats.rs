@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+#[cfg(feature = "alloc-counts")]
+use crate::alloc_counts::AllocCounts;
+use crate::candidate::Candidate;
+use crate::genome::Score;
+use crate::objective::Objective;
+
+/// Fitness summary of one generation, recorded once all of its candidates are scored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenerationStats<G = String, S = isize> {
+    pub generation: usize,
+    pub best_fitness: S,
+    pub mean_fitness: f64,
+    pub std_dev_fitness: f64,
+    pub best_genome: G,
+    /// Share of fitness lookups answered from the fitness cache so far, if there is one.
+    pub cache_hit_rate: Option<f64>,
+    /// How often the population has been restarted before this generation, see
+    /// `RestartPolicy`.
+    pub restarts: usize,
+    /// Mean `Candidate::age`, which shows how fast the population turns over.
+    pub mean_age: f64,
+    /// `GeneticAlgorithm::elapsed` when the generation was scored.
+    pub elapsed: Duration,
+    /// What breeding the generation and scoring it cloned and allocated, counted since the
+    /// previous generation was scored.
+    #[cfg(feature = "alloc-counts")]
+    pub alloc_counts: AllocCounts,
+}
+
+impl<G: Clone, S: Score> GenerationStats<G, S> {
+    /// Summarizes the scored candidates in `population`, which must hold at least one;
+    /// `objective` decides which candidate is the best.
+    pub fn from_population(
+        generation: usize,
+        population: &[Candidate<G, S>],
+        objective: Objective,
+    ) -> Self {
+        let best = population
+            .iter()
+            .filter(|c| c.fitness.is_some())
+            .max_by_key(|c| objective.key(c.fitness))
+            .unwrap();
+        let scores: Vec<f64> = population
+            .iter()
+            .filter_map(|c| c.fitness)
+            .map(Score::to_f64)
+            .collect();
+        let n = scores.len() as f64;
+        let mean_fitness = scores.iter().sum::<f64>() / n;
+        let variance = scores
+            .iter()
+            .map(|fitness| (fitness - mean_fitness).powi(2))
+            .sum::<f64>()
+            / n;
+        let mean_age =
+            population.iter().map(|c| c.age as f64).sum::<f64>() / population.len() as f64;
+        Self {
+            generation,
+            best_fitness: best.fitness.expect("filtered to scored candidates"),
+            mean_fitness,
+            std_dev_fitness: variance.sqrt(),
+            best_genome: best.genome.clone(),
+            cache_hit_rate: None,
+            restarts: 0,
+            mean_age,
+            elapsed: Duration::ZERO,
+            #[cfg(feature = "alloc-counts")]
+            alloc_counts: AllocCounts::default(),
+        }
+    }
+}
+
+/// Column names matching `GenerationStats::csv_row`.
+pub const CSV_HEADER: &str = "generation,best_fitness,mean_fitness,max_possible";
+
+impl<G, S: Score> GenerationStats<G, S> {
+    /// The numbers of this generation as one CSV row, without a trailing newline.
+    /// `max_fitness` fills the `max_possible` column.
+    pub fn csv_row(&self, max_fitness: S) -> String {
+        format!(
+            "{},{},{},{}",
+            self.generation, self.best_fitness, self.mean_fitness, max_fitness
+        )
+    }
+}
+
+/// Whether the best fitness has not improved during the last `patience` generations, given
+/// the best fitness of every generation so far, oldest first, ordered so that higher is
+/// better, e.g. by `Objective::key`.
+pub fn is_plateau<K: Ord>(best_fitness_history: &[K], patience: usize) -> bool {
+    if patience == 0 || best_fitness_history.len() <= patience {
+        return false;
+    }
+    let (before, recent) = best_fitness_history.split_at(best_fitness_history.len() - patience);
+    recent.iter().max() <= before.iter().max()
+}
@@ -0,0 +1,11 @@
+use std::fmt;
+
+/// A pluggable component chosen by name, such as `SelectionStrategy`, whose `Display` is
+/// the form its `FromStr` accepts. `--list-strategies` lists every one of them.
+pub trait Strategy: fmt::Display + Sized {
+    /// One value of every variant, with example parameters, to list the choices.
+    fn variants() -> Vec<Self>;
+
+    /// A one-line summary of what this variant does.
+    fn description(&self) -> &'static str;
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::ops::ControlFlow;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::candidate::Candidate;
+use crate::json::json_line;
+
+/// What a `FrameStream` does once the client has gone away.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OnDisconnect {
+    /// Stop sending and let the run go on.
+    #[default]
+    Continue,
+    /// Stop the run, as if the callback had returned `ControlFlow::Break`.
+    Stop,
+}
+
+/// Parses `continue` or `stop`.
+impl FromStr for OnDisconnect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "continue" => Ok(OnDisconnect::Continue),
+            "stop" => Ok(OnDisconnect::Stop),
+            _ => Err(format!("unknown disconnect policy '{}'", s)),
+        }
+    }
+}
+
+/// The name `from_str` accepts.
+impl fmt::Display for OnDisconnect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OnDisconnect::Continue => "continue",
+            OnDisconnect::Stop => "stop",
+        })
+    }
+}
+
+/// Writes `frame` prefixed with its length in bytes as a big-endian `u32`.
+pub fn write_frame(writer: &mut impl Write, frame: &str) -> io::Result<()> {
+    let len = u32::try_from(frame.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too long"))?;
+    let mut buf = Vec::with_capacity(4 + frame.len());
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(frame.as_bytes());
+    writer.write_all(&buf)?;
+    writer.flush()
+}
+
+/// Sends every callback invocation to a client as a length-prefixed frame holding the same
+/// JSON object as `json_line`, so that a separate process can render the run.
+pub struct FrameStream<W = TcpStream> {
+    writer: Option<W>,
+    on_disconnect: OnDisconnect,
+}
+
+impl FrameStream {
+    /// Connects to a client listening on `addr`.
+    pub fn connect(addr: impl ToSocketAddrs, on_disconnect: OnDisconnect) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        // Frames are small and should arrive as soon as the step is done.
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream, on_disconnect))
+    }
+}
+
+impl<W: Write> FrameStream<W> {
+    pub fn new(writer: W, on_disconnect: OnDisconnect) -> Self {
+        Self {
+            writer: Some(writer),
+            on_disconnect,
+        }
+    }
+
+    /// Whether the client is still there, as far as the last write could tell.
+    pub fn is_connected(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Sends one frame; once a write fails nothing more is sent, and the result says
+    /// whether the run should go on.
+    pub fn send<G: Serialize, S: Serialize>(
+        &mut self,
+        population: &[Candidate<G, S>],
+        label: &str,
+        focus: &[usize],
+    ) -> ControlFlow<()> {
+        if let Some(writer) = &mut self.writer {
+            if write_frame(writer, &json_line(population, label, focus)).is_err() {
+                self.writer = None;
+            }
+        }
+        match (self.is_connected(), self.on_disconnect) {
+            (false, OnDisconnect::Stop) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
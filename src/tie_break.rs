@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::algorithm::apply_order;
+use crate::candidate::Candidate;
+use crate::genome::{Distance, Score};
+use crate::objective::Objective;
+use crate::strategy::Strategy;
+
+/// How candidates of equal fitness are ordered when the population is sorted, which decides
+/// which of them survive culling and make the elite.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TieBreak {
+    /// Keep their current relative order.
+    #[default]
+    Stable,
+    /// Order by `Genome::lexical_cmp`, e.g. alphabetically for strings.
+    Lexical,
+    /// Put the candidate farthest from the rest of the population, by total distance,
+    /// first, to keep variety among the survivors. Costs a distance per pair per sort.
+    Diverse,
+}
+
+impl Strategy for TieBreak {
+    fn variants() -> Vec<Self> {
+        vec![TieBreak::Stable, TieBreak::Lexical, TieBreak::Diverse]
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            TieBreak::Stable => "Equally fit candidates keep their current order",
+            TieBreak::Lexical => "Equally fit candidates are ordered alphabetically",
+            TieBreak::Diverse => "The equally fit candidate farthest from the rest goes first",
+        }
+    }
+}
+
+/// Parses `stable`, `lexical` or `diverse`.
+impl FromStr for TieBreak {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stable" => Ok(TieBreak::Stable),
+            "lexical" => Ok(TieBreak::Lexical),
+            "diverse" => Ok(TieBreak::Diverse),
+            _ => Err(format!("unknown tie break '{}'", s)),
+        }
+    }
+}
+
+/// The name `from_str` accepts.
+impl fmt::Display for TieBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TieBreak::Stable => "stable",
+            TieBreak::Lexical => "lexical",
+            TieBreak::Diverse => "diverse",
+        })
+    }
+}
+
+/// Each candidate's total distance to the others, if `tie_break` needs it.
+fn spread<G: Distance, S: Score>(
+    population: &[Candidate<G, S>],
+    tie_break: TieBreak,
+) -> Vec<usize> {
+    if tie_break != TieBreak::Diverse {
+        return vec![0; population.len()];
+    }
+    population
+        .iter()
+        .map(|a| {
+            population
+                .iter()
+                .map(|b| a.genome.distance(&b.genome))
+                .sum()
+        })
+        .collect()
+}
+
+/// `Less` if `a`, whose total distance to the others is `spread_a`, belongs before `b`.
+fn compare<G: Distance, S: Score>(
+    (a, spread_a): (&Candidate<G, S>, usize),
+    (b, spread_b): (&Candidate<G, S>, usize),
+    objective: Objective,
+    tie_break: TieBreak,
+) -> Ordering {
+    b.rank_cmp(a, objective).then_with(|| match tie_break {
+        TieBreak::Stable => Ordering::Equal,
+        TieBreak::Lexical => a.genome.lexical_cmp(&b.genome),
+        TieBreak::Diverse => spread_b.cmp(&spread_a),
+    })
+}
+
+/// `reorder_by_fitness` with ties ordered by `tie_break`.
+pub fn reorder_by_fitness_with<G: Distance, S: Score>(
+    population: &mut [Candidate<G, S>],
+    objective: Objective,
+    tie_break: TieBreak,
+) -> bool {
+    let order = fitness_order_with(population, objective, tie_break);
+    apply_order(population, &order)
+}
+
+/// `reorder_by_fitness_fast` with ties ordered by `tie_break`.
+pub fn reorder_by_fitness_fast_with<G: Distance, S: Score>(
+    population: &mut [Candidate<G, S>],
+    objective: Objective,
+    tie_break: TieBreak,
+) -> bool {
+    let order = fitness_order_fast_with(population, objective, tie_break);
+    apply_order(population, &order)
+}
+
+/// `fitness_order` with ties ordered by `tie_break`.
+pub(crate) fn fitness_order_with<G: Distance, S: Score>(
+    population: &[Candidate<G, S>],
+    objective: Objective,
+    tie_break: TieBreak,
+) -> Vec<usize> {
+    let spread = spread(population, tie_break);
+    let mut order: Vec<usize> = (0..population.len()).collect();
+    let n = order.len();
+    for i in 0..n {
+        for j in 0..n - i - 1 {
+            let (a, b) = (order[j + 1], order[j]);
+            let cmp = compare(
+                (&population[a], spread[a]),
+                (&population[b], spread[b]),
+                objective,
+                tie_break,
+            );
+            if cmp == Ordering::Less {
+                order.swap(j, j + 1);
+            }
+        }
+    }
+    order
+}
+
+/// `fitness_order_fast` with ties ordered by `tie_break`.
+pub(crate) fn fitness_order_fast_with<G: Distance, S: Score>(
+    population: &[Candidate<G, S>],
+    objective: Objective,
+    tie_break: TieBreak,
+) -> Vec<usize> {
+    let spread = spread(population, tie_break);
+    let mut order: Vec<usize> = (0..population.len()).collect();
+    order.sort_by(|&i, &j| {
+        compare(
+            (&population[i], spread[i]),
+            (&population[j], spread[j]),
+            objective,
+            tie_break,
+        )
+    });
+    order
+}
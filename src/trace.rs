@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use crate::algorithm::STATE;
+
+/// Collects how long each phase of a run takes, for `--trace`. A phase lasts from the
+/// first step in a state until the algorithm moves on to a different phase, so the
+/// per-candidate steps of scoring or replacement add up to one entry.
+#[derive(Clone, Debug)]
+pub struct Trace {
+    start: Instant,
+    phase_time: Duration,
+    totals: Vec<(&'static str, Duration)>,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            phase_time: Duration::ZERO,
+            totals: Vec::new(),
+        }
+    }
+
+    /// Records one step that took `elapsed` and moved the algorithm from `from` to `to`.
+    /// Returns a log line when the step ended a phase, with the time since the trace
+    /// started and how long the whole phase took.
+    pub fn record(
+        &mut self,
+        generation: usize,
+        from: &STATE,
+        to: &STATE,
+        elapsed: Duration,
+    ) -> Option<String> {
+        self.phase_time += elapsed;
+        match self
+            .totals
+            .iter_mut()
+            .find(|(phase, _)| *phase == from.phase())
+        {
+            Some((_, total)) => *total += elapsed,
+            None => self.totals.push((from.phase(), elapsed)),
+        }
+        if from.phase() == to.phase() {
+            return None;
+        }
+        let line = format!(
+            "[{:>10.3}ms] generation {}: {} -> {} ({:.3}ms)",
+            self.start.elapsed().as_secs_f64() * 1000.0,
+            generation,
+            from.description(),
+            to.description(),
+            self.phase_time.as_secs_f64() * 1000.0
+        );
+        self.phase_time = Duration::ZERO;
+        Some(line)
+    }
+
+    /// Total time spent in each phase so far, in the order the phases first ran.
+    pub fn totals(&self) -> &[(&'static str, Duration)] {
+        &self.totals
+    }
+
+    /// Each phase's share of the traced time, largest first, e.g.
+    /// `fitness: 40%, breeding: 35%, sorting: 25%`.
+    pub fn breakdown(&self) -> String {
+        let total: Duration = self.totals.iter().map(|(_, time)| *time).sum();
+        let mut totals = self.totals.clone();
+        totals.sort_by_key(|(_, time)| std::cmp::Reverse(*time));
+        totals
+            .iter()
+            .map(|(phase, time)| {
+                let share = if total.is_zero() {
+                    0.0
+                } else {
+                    time.as_secs_f64() / total.as_secs_f64() * 100.0
+                };
+                format!("{}: {:.0}%", phase, share)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
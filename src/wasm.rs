@@ -0,0 +1,60 @@
+use wasm_bindgen::prelude::*;
+
+use crate::algorithm::GeneticAlgorithm;
+use crate::builder::{GeneticAlgorithmBuilder, NoCallback};
+use crate::json::json_line;
+
+/// A string-evolving run for JavaScript. It only steps the algorithm; rendering and
+/// timing are up to the caller, e.g. one `step` per animation frame.
+#[wasm_bindgen]
+pub struct WasmGeneticAlgorithm {
+    inner: GeneticAlgorithm<NoCallback>,
+}
+
+#[wasm_bindgen]
+impl WasmGeneticAlgorithm {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        target: &str,
+        population_size: usize,
+        seed: Option<u64>,
+    ) -> Result<WasmGeneticAlgorithm, JsError> {
+        let mut builder = GeneticAlgorithmBuilder::new()
+            .target(target)
+            .population_size(population_size);
+        if let Some(seed) = seed {
+            builder = builder.seed(seed);
+        }
+        Ok(Self {
+            inner: builder.build()?,
+        })
+    }
+
+    /// Advance one micro-step. Returns false at the end of a phase or once solved, like
+    /// `Iterator::next` returning `None`.
+    pub fn step(&mut self) -> bool {
+        self.inner.next().is_some()
+    }
+
+    /// Advance to the end of the current generation.
+    pub fn step_generation(&mut self) {
+        self.inner.by_generation().next();
+    }
+
+    /// The current state and population as JSON, in the format of `--format json`.
+    pub fn snapshot(&self) -> String {
+        json_line(
+            self.inner.population(),
+            self.inner.state().description(),
+            self.inner.focus(),
+        )
+    }
+
+    pub fn generation(&self) -> usize {
+        self.inner.generation()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.inner.is_solved()
+    }
+}
@@ -0,0 +1,83 @@
+use ga::{
+    promote_young, Candidate, CullStrategy, GeneticAlgorithmBuilder, Objective, ReplacementMode,
+};
+
+fn aged(genome: &str, fitness: isize, age: usize) -> Candidate {
+    let mut candidate = Candidate::new(genome.to_string());
+    candidate.fitness = Some(fitness);
+    candidate.age = age;
+    candidate
+}
+
+fn genomes(population: &[Candidate]) -> Vec<&str> {
+    population.iter().map(|c| c.genome.as_str()).collect()
+}
+
+#[test]
+fn younger_candidates_win_ties_and_old_ones_go_last() {
+    let mut population = vec![
+        aged("old", 5, 4),
+        aged("young", 5, 0),
+        aged("best", 7, 9),
+        aged("weak", 1, 0),
+    ];
+    promote_young(&mut population, Objective::Maximize, None);
+    assert_eq!(genomes(&population), ["best", "young", "old", "weak"]);
+    promote_young(&mut population, Objective::Maximize, Some(4));
+    assert_eq!(genomes(&population), ["young", "weak", "best", "old"]);
+}
+
+#[test]
+fn elites_age_and_children_are_born_young() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .population_size(20)
+        .num_fit_to_keep(4)
+        .elite_count(2)
+        .seed(4)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().take(3).for_each(drop);
+    let population = genetic_algorithm.population();
+    assert!(population[..2].iter().all(|c| c.age >= 1));
+    assert!(population[2..].iter().all(|c| c.age == 0));
+    let stats = genetic_algorithm.stats();
+    assert_eq!(stats[0].mean_age, 0.0);
+}
+
+#[test]
+fn steady_state_ages_the_whole_population() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .replacement(ReplacementMode::SteadyState)
+        .target("a long target that takes a while")
+        .seed(4)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().take(2).for_each(drop);
+    assert!(genetic_algorithm.population().iter().all(|c| c.age >= 1));
+}
+
+#[test]
+fn age_capped_culling_never_keeps_survivors_past_the_cap() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("a long target that takes a while")
+        .cull(CullStrategy::Age { max_age: Some(3) })
+        .max_generations(30)
+        .seed(4)
+        .build()
+        .unwrap();
+    while genetic_algorithm.by_generation().next().is_some() {
+        assert!(genetic_algorithm.population().iter().all(|c| c.age <= 3));
+    }
+}
+
+#[test]
+fn age_cull_strategy_parses() {
+    assert_eq!("age".parse(), Ok(CullStrategy::Age { max_age: None }));
+    assert_eq!("age:5".parse(), Ok(CullStrategy::Age { max_age: Some(5) }));
+    assert_eq!(CullStrategy::Age { max_age: Some(5) }.to_string(), "age:5");
+    assert!("age:0".parse::<CullStrategy>().is_err());
+    assert!(GeneticAlgorithmBuilder::new()
+        .cull(CullStrategy::Age { max_age: Some(0) })
+        .build()
+        .is_err());
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "alloc-counts")]
+
+use ga::{AllocCounts, Candidate, CountingAllocator, GeneticAlgorithmBuilder};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Other tests run at the same time and count toward the same totals, hence the lower
+// bounds.
+
+#[test]
+fn clones_and_allocations_are_counted() {
+    let candidate: Candidate = Candidate::new("hello".to_string());
+    let before = AllocCounts::now();
+    let copy = candidate.clone();
+    let counted = AllocCounts::now().since(before);
+    assert!(counted.candidate_clones >= 1);
+    assert!(counted.allocations >= 1);
+    assert!(counted.bytes >= copy.genome.len());
+}
+
+#[test]
+fn every_generation_records_what_it_cost() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .population_size(20)
+        .num_fit_to_keep(4)
+        .seed(0)
+        .build()
+        .unwrap();
+    let stats: Vec<_> = ga.by_generation().take(3).collect();
+    for stats in &stats[1..] {
+        // Each of the 16 children needs a genome of its own.
+        assert!(stats.alloc_counts.allocations >= 16, "{:?}", stats);
+    }
+}
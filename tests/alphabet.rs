@@ -0,0 +1,71 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    alphabet_distance, mutate, Alphabet, AlphabetWeights, GaError, GeneticAlgorithmBuilder, Genome,
+    StringContext,
+};
+
+#[test]
+fn weights_parse_and_print_as_a_comma_separated_list() {
+    let weights: AlphabetWeights = "5, 1,0".parse().unwrap();
+    assert_eq!(weights, AlphabetWeights(vec![5, 1, 0]));
+    assert_eq!(weights.to_string(), "5,1,0");
+    assert!("5,-1".parse::<AlphabetWeights>().is_err());
+    assert!("".parse::<AlphabetWeights>().is_err());
+}
+
+#[test]
+fn characters_are_drawn_in_proportion_to_their_weights() {
+    let context = StringContext::new("aab")
+        .with_alphabet("abc")
+        .with_alphabet_weights(AlphabetWeights(vec![3, 1, 0]));
+    let mut rng = StdRng::seed_from_u64(0);
+    let drawn: String = (0..4000).map(|_| context.random_char(&mut rng)).collect();
+    let count = |c| drawn.chars().filter(|&d| d == c).count();
+    assert_eq!(count('c'), 0);
+    assert!((2800..3200).contains(&count('a')), "{}", count('a'));
+
+    // Seeding and mutation draw from the same weights.
+    let seeded = String::random(&context, &mut rng);
+    assert!(!seeded.contains('c'));
+    let mutated = mutate("cccccccc", 1.0, &context, &mut rng);
+    assert!(!mutated.contains('c'));
+}
+
+#[test]
+fn plain_alphabets_draw_uniformly() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let drawn: String = (0..3000).map(|_| "abc".random_char(&mut rng)).collect();
+    for c in "abc".chars() {
+        let count = drawn.chars().filter(|&d| d == c).count();
+        assert!((850..1150).contains(&count), "{}: {}", c, count);
+    }
+}
+
+#[test]
+fn weights_must_fit_the_alphabet() {
+    for weights in [vec![1, 2], vec![0, 0, 0]] {
+        let result = GeneticAlgorithmBuilder::new()
+            .target("cab")
+            .alphabet("abc")
+            .alphabet_weights(AlphabetWeights(weights))
+            .build();
+        assert!(matches!(result, Err(GaError::InvalidParameter(_))));
+    }
+    let genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("cab")
+        .alphabet("abc")
+        .alphabet_weights(AlphabetWeights(vec![1, 1, 2]))
+        .seed(0)
+        .build();
+    assert!(genetic_algorithm.is_ok());
+}
+
+#[test]
+fn alphabet_distance_wraps_around() {
+    assert_eq!(alphabet_distance("abcde", 'a', 'c'), Some(2));
+    assert_eq!(alphabet_distance("abcde", 'a', 'e'), Some(1));
+    assert_eq!(alphabet_distance("abcde", 'd', 'd'), Some(0));
+    assert_eq!(alphabet_distance("abcde", 'a', 'z'), None);
+}
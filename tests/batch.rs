@@ -0,0 +1,43 @@
+use ga::{GeneticAlgorithmBuilder, ReplacementMode, STATE};
+
+#[test]
+fn batch_breeding_matches_one_child_per_step() {
+    for replacement in [ReplacementMode::Generational, ReplacementMode::SteadyState] {
+        let run = |batch: bool| {
+            let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+                .seed(5)
+                .replacement(replacement)
+                .batch(batch)
+                .build()
+                .unwrap();
+            let generations = genetic_algorithm.by_generation().count();
+            let genomes: Vec<String> = genetic_algorithm
+                .population()
+                .iter()
+                .map(|c| c.genome.clone())
+                .collect();
+            (generations, genomes)
+        };
+        let (generations, genomes) = run(false);
+        let (batch_generations, batch_genomes) = run(true);
+        assert_eq!(generations, batch_generations);
+        assert_eq!(genomes, batch_genomes);
+    }
+}
+
+#[test]
+fn batch_breeding_focuses_every_new_child() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .seed(1)
+        .population_size(20)
+        .num_fit_to_keep(8)
+        .batch(true)
+        .build()
+        .unwrap();
+    while !matches!(genetic_algorithm.state(), STATE::BreedNew) {
+        genetic_algorithm.next();
+    }
+    genetic_algorithm.next();
+    assert_eq!(genetic_algorithm.population().len(), 20);
+    assert_eq!(genetic_algorithm.focus(), (8..20).collect::<Vec<_>>());
+}
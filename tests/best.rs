@@ -0,0 +1,36 @@
+use ga::{levenshtein, GeneticAlgorithmBuilder, Objective};
+
+#[test]
+fn best_is_the_fittest_scored_candidate_before_sorting() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new().seed(2).build().unwrap();
+    assert!(genetic_algorithm.best().is_none());
+    assert_eq!(genetic_algorithm.best_fitness(), None);
+    // Seed the population and score a few candidates, without sorting anything yet.
+    for _ in 0..ga::POPULATION_SIZE + 10 {
+        genetic_algorithm.next();
+    }
+    let best_fitness = genetic_algorithm
+        .population()
+        .iter()
+        .filter_map(|c| c.fitness)
+        .max();
+    assert!(best_fitness.is_some());
+    assert_eq!(genetic_algorithm.best_fitness(), best_fitness);
+}
+
+#[test]
+fn best_follows_the_objective() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .fitness_fn(|text, target| levenshtein(text, target) as isize)
+        .objective(Objective::Minimize)
+        .seed(2)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().next();
+    let lowest = genetic_algorithm
+        .population()
+        .iter()
+        .filter_map(|c| c.fitness)
+        .min();
+    assert_eq!(genetic_algorithm.best_fitness(), lowest);
+}
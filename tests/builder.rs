@@ -0,0 +1,94 @@
+use ga::{GaError, GeneticAlgorithmBuilder, SelectionStrategy};
+
+#[test]
+fn defaults_build() {
+    let ga = GeneticAlgorithmBuilder::new().build().unwrap();
+    assert_eq!(ga.context().target, "hello world");
+}
+
+#[test]
+fn rejects_inconsistent_settings() {
+    let builder = GeneticAlgorithmBuilder::new;
+    assert!(builder().target("").build().is_err());
+    assert!(builder().target("Hello").build().is_err());
+    assert!(builder().alphabet("").build().is_err());
+    assert!(builder().mutation_prob(1.5).build().is_err());
+    assert!(builder().num_fit_to_keep(1).build().is_err());
+    assert!(builder().population_size(5).build().is_err());
+    assert!(builder().elite_count(6).build().is_err());
+    assert!(builder()
+        .selection(SelectionStrategy::Tournament { k: 0 })
+        .build()
+        .is_err());
+}
+
+#[test]
+fn reports_what_is_wrong() {
+    let error = |builder: GeneticAlgorithmBuilder| builder.build().err().unwrap();
+    assert_eq!(
+        error(GeneticAlgorithmBuilder::new().target("")),
+        GaError::EmptyTarget
+    );
+    assert_eq!(
+        error(GeneticAlgorithmBuilder::new().alphabet("")),
+        GaError::EmptyAlphabet
+    );
+    assert_eq!(
+        error(GeneticAlgorithmBuilder::new().target("Hi").alphabet("hi")),
+        GaError::TargetNotInAlphabet {
+            character: 'H',
+            alphabet: "hi".to_string()
+        }
+    );
+    assert!(matches!(
+        error(GeneticAlgorithmBuilder::new().mutation_prob(-0.1)),
+        GaError::InvalidParameter(_)
+    ));
+}
+
+#[test]
+fn configuration_prints_in_command_line_syntax() {
+    let builder = GeneticAlgorithmBuilder::new()
+        .selection(SelectionStrategy::Tournament { k: 3 })
+        .seed(42);
+    let config = builder.to_string();
+    assert!(config.contains("target: \"hello world\""));
+    assert!(config.contains("selection: tournament:3"));
+    assert!(config.contains("seed: 42"));
+    let selection = SelectionStrategy::Tournament { k: 3 };
+    assert_eq!(selection.to_string().parse(), Ok(selection));
+}
+
+#[test]
+fn unreachable_targets_stop_at_the_reachable_ceiling() {
+    let builder = GeneticAlgorithmBuilder::new()
+        .target("Hello")
+        .alphabet("helo");
+    assert!(builder.clone().build().is_err());
+    let mut ga = builder.allow_unreachable(true).seed(3).build().unwrap();
+    assert_eq!(ga.context().unreachable_chars(), ['H']);
+    assert_eq!(ga.context().reachable_max_fitness(), 4);
+    assert_eq!(ga.max_fitness(), 4);
+    ga.by_generation().take(200).for_each(drop);
+    assert!(ga.is_solved());
+    assert_eq!(ga.best_fitness(), Some(4));
+    // The empty alphabet is still an error.
+    assert_eq!(
+        GeneticAlgorithmBuilder::new()
+            .alphabet("")
+            .allow_unreachable(true)
+            .build()
+            .err(),
+        Some(GaError::EmptyAlphabet)
+    );
+}
+
+#[test]
+fn reachable_targets_keep_the_full_maximum() {
+    let ga = GeneticAlgorithmBuilder::new()
+        .allow_unreachable(true)
+        .build()
+        .unwrap();
+    assert!(ga.context().unreachable_chars().is_empty());
+    assert_eq!(ga.max_fitness(), 11);
+}
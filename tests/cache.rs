@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use ga::{position_match, FitnessCache, GeneticAlgorithmBuilder, StringContext};
+
+#[test]
+fn cache_scores_each_genome_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let context = StringContext::new("abc").with_fitness_fn(move |text, target| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        position_match(text, target)
+    });
+    let mut cache = FitnessCache::new();
+    assert_eq!(cache.score(&"abd".to_string(), &context), 2);
+    assert_eq!(cache.score(&"abd".to_string(), &context), 2);
+    assert_eq!(cache.score(&"xyz".to_string(), &context), 0);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 2, 2));
+    assert!((cache.hit_rate() - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn cached_runs_match_uncached_ones() {
+    for batch in [false, true] {
+        let run = |fitness_cache: bool| {
+            let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+                .seed(3)
+                .batch(batch)
+                .fitness_cache(fitness_cache)
+                .build()
+                .unwrap();
+            let best: Vec<isize> = genetic_algorithm
+                .by_generation()
+                .map(|stats| stats.best_fitness)
+                .collect();
+            let hit_rate = genetic_algorithm.stats().last().unwrap().cache_hit_rate;
+            (best, hit_rate)
+        };
+        let (uncached, no_rate) = run(false);
+        let (cached, rate) = run(true);
+        assert_eq!(uncached, cached);
+        assert_eq!(no_rate, None);
+        assert!(rate.unwrap() > 0.0);
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "serde")]
+
+use ga::{levenshtein, Checkpoint, GeneticAlgorithmBuilder};
+
+fn builder() -> GeneticAlgorithmBuilder {
+    GeneticAlgorithmBuilder::new()
+        .target("hello checkpoint")
+        .seed(7)
+}
+
+#[test]
+fn resumed_run_matches_uninterrupted_run() {
+    let mut uninterrupted = builder().build().unwrap();
+    while !uninterrupted.is_solved() && uninterrupted.generation() < 30 {
+        uninterrupted.next();
+    }
+
+    let mut first_half = builder().build().unwrap();
+    while first_half.generation() < 10 {
+        first_half.next();
+    }
+    // Stop in the middle of a generation to exercise the saved state.
+    for _ in 0..20 {
+        first_half.next();
+    }
+    let path = std::env::temp_dir().join("ga-checkpoint-test.json");
+    first_half.checkpoint().save(&path).unwrap();
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(checkpoint.generation(), 10);
+
+    let mut resumed = builder().resume(checkpoint).build().unwrap();
+    while !resumed.is_solved() && resumed.generation() < 30 {
+        resumed.next();
+    }
+
+    let genomes = |population: &[ga::Candidate]| -> Vec<String> {
+        population.iter().map(|c| c.genome.clone()).collect()
+    };
+    assert_eq!(
+        genomes(resumed.population()),
+        genomes(uninterrupted.population())
+    );
+}
+
+#[test]
+fn negative_scores_survive_a_checkpoint() {
+    // Even, so no score is mistaken for the -1 older checkpoints used for unscored.
+    let builder = || builder().fitness_fn(|text, target| -2 * levenshtein(text, target) as isize);
+    let mut genetic_algorithm = builder().build().unwrap();
+    while genetic_algorithm.generation() < 3 {
+        genetic_algorithm.next();
+    }
+    let fitnesses = |population: &[ga::Candidate]| -> Vec<Option<isize>> {
+        population.iter().map(|c| c.fitness).collect()
+    };
+    let saved = fitnesses(genetic_algorithm.population());
+    assert!(saved.iter().any(|&fitness| fitness.is_some_and(|f| f < 0)));
+
+    let path = std::env::temp_dir().join("ga-checkpoint-negative-test.json");
+    genetic_algorithm.checkpoint().save(&path).unwrap();
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let resumed = builder().resume(checkpoint).build().unwrap();
+    assert_eq!(fitnesses(resumed.population()), saved);
+}
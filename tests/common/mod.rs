@@ -0,0 +1,23 @@
+//! Fixtures shared by the integration tests; each test crate uses only some of them.
+#![allow(dead_code)]
+
+use ga::{Candidate, StringContext};
+
+/// A string candidate with the given fitness.
+pub fn scored(genome: &str, fitness: isize) -> Candidate {
+    scored_genome(genome.to_string(), fitness)
+}
+
+/// A candidate of any genome with the given fitness.
+pub fn scored_genome<G>(genome: G, fitness: isize) -> Candidate<G> {
+    let mut candidate = Candidate::new(genome);
+    candidate.fitness = Some(fitness);
+    candidate
+}
+
+/// A string candidate scored against `context`.
+pub fn scored_against(genome: &str, context: &StringContext) -> Candidate {
+    let mut candidate = Candidate::new(genome.to_string());
+    candidate.set_fitness(context);
+    candidate
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "serde")]
+
+use ga::{AlphabetWeights, Config, GeneticAlgorithmBuilder, MutationOp, SelectionStrategy};
+
+#[test]
+fn keys_take_the_command_line_syntax() {
+    let config: Config = r#"
+        target = "hello toml"
+        population = 30
+        mutation = 0.1
+        mutation-op = "swap"
+        selection = "tournament:3"
+        seed-candidates = ["hello"]
+        alphabet-weights = "2,1"
+    "#
+    .parse()
+    .unwrap();
+    assert_eq!(config.target.as_deref(), Some("hello toml"));
+    assert_eq!(config.population, Some(30));
+    assert_eq!(config.mutation, Some(0.1));
+    assert_eq!(config.mutation_op, Some(MutationOp::SwapAdjacent));
+    assert_eq!(
+        config.selection,
+        Some(SelectionStrategy::Tournament { k: 3 })
+    );
+    assert_eq!(config.seed_candidates, Some(vec!["hello".to_string()]));
+    assert_eq!(config.alphabet_weights, Some(AlphabetWeights(vec![2, 1])));
+    assert_eq!(config.seed, None);
+}
+
+#[test]
+fn unknown_keys_and_bad_values_are_rejected() {
+    assert!("populaton = 30".parse::<Config>().is_err());
+    assert!("selection = \"bogus\"".parse::<Config>().is_err());
+    assert!("population = -1".parse::<Config>().is_err());
+}
+
+#[test]
+fn apply_only_overrides_present_settings() {
+    let config: Config = "population = 30\nseed = 4".parse().unwrap();
+    let builder = config.apply(GeneticAlgorithmBuilder::new().target("kept"));
+    let expected = GeneticAlgorithmBuilder::new()
+        .target("kept")
+        .population_size(30)
+        .seed(4);
+    assert_eq!(builder.to_string(), expected.to_string());
+    let genetic_algorithm = builder.build().unwrap();
+    assert_eq!(genetic_algorithm.context().target, "kept");
+}
+
+#[test]
+fn load_reads_a_file() {
+    let path = std::env::temp_dir().join(format!("ga-config-{}.toml", std::process::id()));
+    std::fs::write(&path, "target = \"from file\"").unwrap();
+    let config = Config::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(config.target.as_deref(), Some("from file"));
+    assert!(Config::load(&path).is_err());
+}
@@ -0,0 +1,43 @@
+use std::ops::ControlFlow;
+
+use ga::{Candidate, GeneticAlgorithm, StringContext, MUTATION_PROB, NUM_FIT_TO_KEEP};
+
+// The default settings need up to about 200 generations for the first eight seeds.
+const MAX_GENERATIONS: usize = 500;
+
+fn generations_to_solve(seed: u64) -> Option<usize> {
+    let mut genetic_algorithm = GeneticAlgorithm::new(
+        StringContext::new("hello world"),
+        NUM_FIT_TO_KEEP,
+        ga::POPULATION_SIZE,
+        MUTATION_PROB,
+        Some(seed),
+        |_: &Vec<Candidate>, _: &str, _: &[usize]| ControlFlow::Continue(()),
+    )
+    .with_fast_sort(true)
+    .with_batch(true);
+    while !genetic_algorithm.is_solved() && genetic_algorithm.generation() < MAX_GENERATIONS {
+        genetic_algorithm.next();
+    }
+    genetic_algorithm
+        .is_solved()
+        .then(|| genetic_algorithm.generation())
+}
+
+#[test]
+fn default_settings_reach_hello_world() {
+    for seed in 0..8 {
+        let generations = generations_to_solve(seed);
+        assert!(
+            generations.is_some(),
+            "seed {} did not solve \"hello world\" within {} generations",
+            seed,
+            MAX_GENERATIONS
+        );
+    }
+}
+
+#[test]
+fn seeded_runs_are_deterministic() {
+    assert_eq!(generations_to_solve(1), generations_to_solve(1));
+}
@@ -0,0 +1,223 @@
+use ga::{
+    breed, mutate, single_point, two_point, uniform_many, Candidate, CrossoverMode,
+    GeneticAlgorithmBuilder, Objective, StringContext, LETTERS,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn single_point_cut_at_zero_yields_parent_b() {
+    assert_eq!(single_point("hello", "world", 0), "world");
+    assert_eq!(single_point("hello", "world", 5), "hello");
+    assert_eq!(single_point("hello", "world", 2), "herld");
+}
+
+#[test]
+fn two_point_takes_middle_segment_from_parent_b() {
+    assert_eq!(two_point("hello", "world", 1, 3), "horlo");
+    assert_eq!(two_point("hello", "world", 0, 5), "world");
+    assert_eq!(two_point("hello", "world", 2, 2), "hello");
+}
+
+#[test]
+fn mutation_is_applied_after_recombination() {
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(mutate("hello", 0.0, LETTERS, &mut rng), "hello");
+    assert_ne!(mutate("hello world", 1.0, LETTERS, &mut rng), "hello world");
+}
+
+#[test]
+fn single_point_child_is_a_prefix_plus_suffix() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let parent_a = Candidate::new("aaaaaaaaaa".to_string());
+    let parent_b = Candidate::new("bbbbbbbbbb".to_string());
+    let context = StringContext::new("hello world");
+    for _ in 0..100 {
+        let child = breed(
+            &[&parent_a, &parent_b],
+            CrossoverMode::SinglePoint,
+            0.0,
+            &context,
+            &mut rng,
+        );
+        let cut = child.genome.find('b').unwrap_or(10);
+        assert_eq!(child.genome, single_point("aaaaaaaaaa", "bbbbbbbbbb", cut));
+    }
+}
+
+#[test]
+fn asexual_child_copies_one_parent() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let parent_a = Candidate::new("aaaaaaaaaa".to_string());
+    let parent_b = Candidate::new("bbbbbbbbbb".to_string());
+    let context = StringContext::new("hello world");
+    let child = breed(
+        &[&parent_a, &parent_b],
+        CrossoverMode::Asexual,
+        0.0,
+        &context,
+        &mut rng,
+    );
+    assert_eq!(child.genome, parent_a.genome);
+}
+
+fn uniform_children(mutation_prob: f64, alphabet: &str, n: usize) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let parent_a = Candidate::new("aaaaaaaaaa".to_string());
+    let parent_b = Candidate::new("bbbbbbbbbb".to_string());
+    let context = StringContext::new("hello world").with_alphabet(alphabet);
+    (0..n)
+        .map(|_| {
+            breed(
+                &[&parent_a, &parent_b],
+                CrossoverMode::default(),
+                mutation_prob,
+                &context,
+                &mut rng,
+            )
+            .genome
+        })
+        .collect()
+}
+
+#[test]
+fn uniform_without_mutation_only_inherits() {
+    let genes: String = uniform_children(0.0, "z", 100).concat();
+    assert!(genes.chars().all(|c| c == 'a' || c == 'b'));
+    // Both parents contribute about half of the genes.
+    let from_a = genes.chars().filter(|&c| c == 'a').count();
+    assert!(
+        (400..600).contains(&from_a),
+        "{} of 1000 genes from a",
+        from_a
+    );
+}
+
+#[test]
+fn uniform_with_full_mutation_redraws_every_gene() {
+    for child in uniform_children(1.0, "z", 10) {
+        assert_eq!(child, "zzzzzzzzzz");
+    }
+}
+
+#[test]
+fn uniform_mutation_rate_is_independent_of_inheritance() {
+    let genes: String = uniform_children(0.3, "z", 200).concat();
+    let mutated = genes.chars().filter(|&c| c == 'z').count();
+    // 30% of 2000 genes, with generous slack for randomness.
+    assert!(
+        (500..700).contains(&mutated),
+        "{} of 2000 genes mutated",
+        mutated
+    );
+}
+
+#[test]
+fn uniform_bias_sets_the_share_of_genes_from_parent_a() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let parent_a = Candidate::new("aaaaaaaaaa".to_string());
+    let parent_b = Candidate::new("bbbbbbbbbb".to_string());
+    let context = StringContext::new("hello world");
+    let mut child = |bias| {
+        breed(
+            &[&parent_a, &parent_b],
+            CrossoverMode::Uniform { bias },
+            0.0,
+            &context,
+            &mut rng,
+        )
+        .genome
+    };
+    assert_eq!(child(1.0), "aaaaaaaaaa");
+    assert_eq!(child(0.0), "bbbbbbbbbb");
+    let genes: String = (0..100).map(|_| child(0.8)).collect();
+    let from_a = genes.chars().filter(|&c| c == 'a').count();
+    assert!(
+        (750..850).contains(&from_a),
+        "{} of 1000 genes from a",
+        from_a
+    );
+}
+
+#[test]
+fn biased_uniform_puts_the_fitter_parent_first() {
+    let biased = CrossoverMode::Uniform { bias: 0.7 };
+    assert!(biased.swaps_parents(Some(1), Some(5), Objective::Maximize));
+    assert!(!biased.swaps_parents(Some(5), Some(1), Objective::Maximize));
+    assert!(biased.swaps_parents(Some(5), Some(1), Objective::Minimize));
+    // The unbiased default keeps the selection order.
+    assert!(!CrossoverMode::default().swaps_parents(Some(1), Some(5), Objective::Maximize));
+}
+
+#[test]
+fn uniform_bias_parses_and_prints() {
+    assert_eq!("uniform".parse(), Ok(CrossoverMode::Uniform { bias: 0.5 }));
+    assert_eq!("uniform:0.5".parse(), Ok(CrossoverMode::default()));
+    assert_eq!(
+        "uniform:0.8".parse::<CrossoverMode>().unwrap().to_string(),
+        "uniform:0.8"
+    );
+    assert_eq!(CrossoverMode::default().to_string(), "uniform");
+    assert!("uniform:1.5".parse::<CrossoverMode>().is_err());
+    assert!("uniform:x".parse::<CrossoverMode>().is_err());
+    assert!(GeneticAlgorithmBuilder::new()
+        .crossover(CrossoverMode::Uniform { bias: -0.1 })
+        .build()
+        .is_err());
+}
+
+#[test]
+fn uniform_many_takes_every_gene_from_one_of_the_parents() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let genes: String = (0..100)
+        .map(|_| uniform_many(&["aaaaaaaaaa", "bbbbbbbbbb", "cccccccccc"], &mut rng))
+        .collect();
+    assert_eq!(genes.len(), 1000);
+    for parent in ['a', 'b', 'c'] {
+        let count = genes.chars().filter(|&c| c == parent).count();
+        assert!(
+            (280..390).contains(&count),
+            "{} of 1000 from {}",
+            count,
+            parent
+        );
+    }
+    // Past the end of the shorter parents the longer ones donate alone.
+    for _ in 0..20 {
+        let child = uniform_many(&["aaaa", "b", "c"], &mut rng);
+        assert!(child.len() == 1 || &child[1..] == "aaa", "{}", child);
+    }
+}
+
+#[test]
+fn breeding_takes_any_number_of_parents() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let context = StringContext::new("hello world");
+    let parents: Vec<Candidate> = ["aaaaaaaaaa", "bbbbbbbbbb", "cccccccccc"]
+        .iter()
+        .map(|genome| Candidate::new(genome.to_string()))
+        .collect();
+    // One parent is copied and mutated, whatever the mode.
+    let copy = breed(
+        &[&parents[0]],
+        CrossoverMode::default(),
+        0.0,
+        &context,
+        &mut rng,
+    );
+    assert_eq!(copy.genome, "aaaaaaaaaa");
+    let three: Vec<&Candidate> = parents.iter().collect();
+    let genes: String = (0..50)
+        .map(|_| breed(&three, CrossoverMode::default(), 0.0, &context, &mut rng).genome)
+        .collect();
+    assert!(genes.contains('a') && genes.contains('b') && genes.contains('c'));
+    assert!(GeneticAlgorithmBuilder::new()
+        .num_parents(3)
+        .crossover(CrossoverMode::SinglePoint)
+        .build()
+        .is_err());
+    assert!(GeneticAlgorithmBuilder::new()
+        .num_parents(0)
+        .build()
+        .is_err());
+}
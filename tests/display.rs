@@ -0,0 +1,253 @@
+use ga::{
+    center_text, display, fit_text, fitness_histogram, render_grid, render_histogram,
+    render_progress, visible_len, Candidate, Fitness, FitnessMode, StringContext, LETTERS,
+};
+
+mod common;
+
+use common::scored_against;
+
+#[test]
+fn visible_len_ignores_ansi_colors() {
+    assert_eq!(visible_len("hello"), 5);
+    assert_eq!(visible_len("\x1b[91mh\x1b[0m\x1b[92mi\x1b[0m"), 2);
+    assert_eq!(visible_len("\x1b[1m\x1b[96m日本\x1b[0m"), 4);
+}
+
+#[test]
+fn colored_and_plain_cells_have_the_same_width() {
+    let mut candidate = Candidate::new("hxllo".to_string());
+    candidate.fitness = Some(4);
+    assert_eq!(
+        visible_len(&candidate.display_str("hello", false, true)),
+        visible_len(&candidate.display_str("hello", false, false))
+    );
+}
+
+#[test]
+fn center_text_pads_by_visible_width() {
+    assert_eq!(center_text("\x1b[1mab\x1b[0m", 6), "  \x1b[1mab\x1b[0m  ");
+}
+
+#[test]
+fn fit_text_pads_exact_and_shorter_text() {
+    assert_eq!(fit_text("hello", 5), "hello");
+    assert_eq!(fit_text("hello", 9), "  hello  ");
+    assert_eq!(fit_text("héllo", 7), " héllo ");
+    assert_eq!(fit_text("", 2), "  ");
+}
+
+#[test]
+fn fit_text_cuts_longer_text_with_an_ellipsis() {
+    assert_eq!(fit_text("hello world", 6), "hello…");
+    assert_eq!(fit_text("héllo wörld", 4), "hél…");
+    assert_eq!(fit_text("hello", 1), "…");
+    assert_eq!(fit_text("hello", 0), "");
+    // Wide characters take two columns; one that does not fit leaves padding instead.
+    assert_eq!(fit_text("日本語", 4), "日… ");
+    assert_eq!(visible_len(&fit_text("日本語テキスト", 7)), 7);
+}
+
+#[test]
+fn long_labels_do_not_widen_the_grid() {
+    let population = vec![Candidate::new("abc".to_string())];
+    let lines = render_grid(
+        &population,
+        &[],
+        "a label far wider than the grid",
+        9,
+        "abc",
+        3,
+        false,
+        1,
+        1,
+        false,
+        None,
+        None,
+    );
+    assert_eq!(visible_len(&lines[0]), 9);
+    assert!(lines[0].ends_with('…'));
+}
+
+#[test]
+fn render_progress_shows_the_best_scored_candidate() {
+    let mut population = vec![
+        Candidate::new("hxllo".to_string()),
+        Candidate::new("hello".to_string()),
+    ];
+    assert_eq!(
+        render_progress(&population, 5),
+        "best: -/5 [....................]"
+    );
+    population[0].fitness = Some(4);
+    assert_eq!(
+        render_progress(&population, 5),
+        "best: 4/5 [################....]"
+    );
+}
+
+#[test]
+fn every_candidate_gets_a_cell() {
+    for (population_len, population_size, num_columns) in [(50, 50, 4), (7, 7, 3), (52, 50, 4)] {
+        let population: Vec<Candidate> = (0..population_len)
+            .map(|i| Candidate::new(format!("c{:02}", i)))
+            .collect();
+        let lines = render_grid(
+            &population,
+            &[],
+            "label",
+            9,
+            "abc",
+            3,
+            false,
+            population_size,
+            num_columns,
+            false,
+            None,
+            None,
+        );
+        let rows = &lines[4..];
+        let cells: Vec<&str> = rows.iter().flat_map(|row| row.split_whitespace()).collect();
+        assert!(cells.len() >= population.len());
+        for candidate in &population {
+            assert!(
+                cells.contains(&candidate.genome.as_str()),
+                "{} is hidden",
+                candidate.genome
+            );
+        }
+    }
+}
+
+#[test]
+fn histogram_counts_scored_candidates_per_fitness() {
+    let population: Vec<Candidate> = [Some(3), Some(1), Some(3), None, Some(7)]
+        .into_iter()
+        .map(|fitness| {
+            let mut candidate = Candidate::new("abc".to_string());
+            candidate.fitness = fitness;
+            candidate
+        })
+        .collect();
+    // Unscored candidates are left out, scores past the target length land in the top bucket.
+    assert_eq!(fitness_histogram(&population, 3, 4), vec![0, 1, 0, 3]);
+}
+
+#[test]
+fn histogram_groups_weighted_fitness_into_ranges() {
+    let context = StringContext {
+        fitness_mode: FitnessMode::Weighted(vec![100; 5]),
+        ..StringContext::new("hello")
+    };
+    let population: Vec<Candidate> = ["hello", "hellx", "xxxxx", "xxxxo"]
+        .iter()
+        .map(|genome| scored_against(genome, &context))
+        .collect();
+    let counts = fitness_histogram(&population, String::max_fitness(&context) as usize, 6);
+    assert_eq!(counts, vec![1, 1, 0, 0, 1, 1]);
+    let lines = render_histogram(&counts, 500, 12);
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].trim_start().starts_with("500 |"), "{:?}", lines);
+    assert!(lines[5].trim_start().starts_with("0 |"), "{:?}", lines);
+}
+
+#[test]
+fn histogram_bars_scale_to_the_largest_bucket() {
+    let lines = render_histogram(&[0, 1, 4], 2, 12);
+    assert_eq!(
+        lines,
+        vec!["   2 |####### 4", "   1 |##      1", "   0 |        0",]
+    );
+}
+
+/// Three scored candidates, one unscored and one empty cell, with the second in focus,
+/// as written by `display`.
+fn snapshot(color: bool) -> String {
+    let context = StringContext::new("hello");
+    let mut population: Vec<Candidate> = ["hello", "hxllo", "jelly", "olleh"]
+        .iter()
+        .map(|genome| Candidate::new(genome.to_string()))
+        .collect();
+    for candidate in &mut population[..3] {
+        candidate.set_fitness(&context);
+    }
+    let mut out = Vec::new();
+    display(
+        &mut out,
+        &population,
+        &[1],
+        "compute fitness",
+        11,
+        "hello",
+        5,
+        false,
+        5,
+        2,
+        color,
+        None,
+        None,
+    )
+    .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn grid_matches_the_plain_snapshot() {
+    assert_eq!(snapshot(false), include_str!("snapshots/grid_plain.txt"));
+}
+
+#[test]
+fn grid_matches_the_color_snapshot() {
+    assert_eq!(snapshot(true), include_str!("snapshots/grid_color.txt"));
+}
+
+#[test]
+fn shading_colors_mismatches_by_alphabet_distance() {
+    let mut candidate = Candidate::new("hfmz?".to_string());
+    candidate.fitness = Some(1);
+    // h matches, f is 1 step from e, m is 3 from j, z is 13 from l, ? is not in the alphabet.
+    assert_eq!(
+        candidate.display_str_shaded("hejlo", LETTERS, false),
+        "\x1b[92mh\x1b[0m\x1b[93mf\x1b[0m\x1b[93mm\x1b[0m\x1b[91mz\x1b[0m\x1b[91m?\x1b[0m"
+    );
+    let mut candidate = Candidate::new("hello".to_string());
+    candidate.fitness = Some(1);
+    assert!(candidate
+        .display_str_shaded("heelo", LETTERS, false)
+        .contains("\x1b[38;5;208ml"));
+    // Unscored candidates stay plain, as with `display_str`.
+    assert_eq!(
+        Candidate::new("hfllo".to_string()).display_str_shaded("hello", LETTERS, false),
+        "hfllo"
+    );
+}
+
+#[test]
+fn leader_gets_a_line_above_the_cells() {
+    let mut leader = Candidate::new("hxllo".to_string());
+    leader.fitness = Some(4);
+    let population = vec![leader.clone(), Candidate::new("jelly".to_string())];
+    let grid = |leader: Option<&Candidate>, color: bool| {
+        render_grid(
+            &population,
+            &[],
+            "label",
+            11,
+            "hello",
+            5,
+            false,
+            2,
+            2,
+            color,
+            None,
+            leader,
+        )
+    };
+    let plain = grid(Some(&leader), false);
+    assert_eq!(plain[4].trim(), "leader: hxllo 4/5");
+    assert_eq!(plain[5], "");
+    assert!(plain[6].contains("hxllo") && plain[6].contains("jelly"));
+    // Without a leader the cells follow the progress bar as before.
+    assert!(grid(None, false)[4].contains("jelly"));
+    assert!(grid(Some(&leader), true)[4].contains("\x1b[1m\x1b[93mleader:"));
+}
@@ -0,0 +1,45 @@
+use ga::{population_diversity, promote_diverse, CullStrategy, Distance};
+
+mod common;
+
+use common::scored;
+
+#[test]
+fn hamming_distance_counts_extra_characters() {
+    assert_eq!("hello".to_string().distance(&"hxllo".to_string()), 1);
+    assert_eq!("hello".to_string().distance(&"hell".to_string()), 1);
+}
+
+#[test]
+fn diversity_is_mean_pairwise_distance() {
+    assert_eq!(
+        population_diversity(&[scored("aaa", 0), scored("aaa", 0)]),
+        0.0
+    );
+    let population = [scored("aaa", 0), scored("aab", 0), scored("bbb", 0)];
+    // Distances 1, 3 and 2.
+    assert_eq!(population_diversity(&population), 2.0);
+}
+
+#[test]
+fn diverse_culling_keeps_a_distinct_weaker_candidate() {
+    let mut population = vec![
+        scored("hello", 5),
+        scored("hellx", 4),
+        scored("hellz", 4),
+        scored("qwert", 0),
+    ];
+    promote_diverse(&mut population, 3, 1);
+    let kept: Vec<&str> = population[..3].iter().map(|c| c.genome.as_str()).collect();
+    assert_eq!(kept, ["hello", "hellx", "qwert"]);
+}
+
+#[test]
+fn cull_strategy_parses() {
+    assert_eq!("fitness".parse(), Ok(CullStrategy::Fitness));
+    assert_eq!(
+        "diversity:2".parse(),
+        Ok(CullStrategy::Diversity { reserved: 2 })
+    );
+    assert!("diversity:x".parse::<CullStrategy>().is_err());
+}
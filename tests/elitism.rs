@@ -0,0 +1,44 @@
+use ga::GeneticAlgorithmBuilder;
+
+fn best_fitness_history(elite_count: usize, seed: u64) -> Vec<isize> {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("hello genetic world")
+        .elite_count(elite_count)
+        .seed(seed)
+        .build()
+        .unwrap();
+    while !ga.is_solved() && ga.generation() < 100 {
+        for _ in ga.by_ref() {}
+    }
+    ga.stats().iter().map(|s| s.best_fitness).collect()
+}
+
+#[test]
+fn best_fitness_never_decreases_with_elitism() {
+    for seed in 0..5 {
+        let history = best_fitness_history(1, seed);
+        assert!(history.len() > 1);
+        assert!(
+            history.windows(2).all(|w| w[0] <= w[1]),
+            "best fitness decreased: {:?}",
+            history
+        );
+    }
+}
+
+#[test]
+fn population_size_is_kept_with_partial_elitism() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .population_size(20)
+        .elite_count(2)
+        .seed(0)
+        .build()
+        .unwrap();
+    for _ in 0..10 {
+        let generation = ga.generation();
+        while !ga.is_solved() && ga.generation() == generation {
+            for _ in ga.by_ref() {}
+        }
+        assert_eq!(ga.population().len(), 20);
+    }
+}
@@ -0,0 +1,170 @@
+use ga::{
+    chars_match, fold_case, max_fitness, position_match, weighted_position_match, Candidate,
+    Fitness, FitnessMode, GaError, GeneticAlgorithmBuilder, StringContext, LETTERS,
+};
+
+#[test]
+fn custom_fitness_closure_is_used() {
+    let double = |text: &str, target: &str| 2 * position_match(text, target);
+    let context = StringContext::new("hello").with_fitness_fn(double);
+    let mut candidate = Candidate::new("hxllo".to_string());
+    candidate.set_fitness(&context);
+    assert_eq!(candidate.fitness, Some(8));
+}
+
+#[test]
+fn custom_fitness_solves_at_the_target_score() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .fitness_fn(|text, target| 2 * position_match(text, target))
+        .seed(0)
+        .build()
+        .unwrap();
+    let last = ga.by_generation().last().unwrap();
+    assert!(ga.is_solved());
+    assert_eq!(last.best_fitness, 10);
+    assert_eq!(last.best_genome, "hello");
+}
+
+#[test]
+fn default_fitness_is_position_match() {
+    assert_eq!(
+        StringContext::new("x").fitness_mode,
+        FitnessMode::PositionMatch
+    );
+}
+
+fn score(genome: &str, context: &StringContext) -> isize {
+    let mut candidate = Candidate::new(genome.to_string());
+    candidate.set_fitness(context);
+    candidate.fitness.unwrap()
+}
+
+#[test]
+fn weighted_fitness_values_early_matches_more() {
+    let unweighted = StringContext::new("hello");
+    let weighted = StringContext {
+        fitness_mode: FitnessMode::Weighted(vec![5, 4, 3, 2, 1]),
+        ..StringContext::new("hello")
+    };
+    // Both candidates match three positions, but at different places.
+    assert_eq!(score("helxx", &unweighted), score("xxllo", &unweighted));
+    assert_eq!(score("helxx", &weighted), 12);
+    assert_eq!(score("xxllo", &weighted), 6);
+    assert_eq!(String::max_fitness(&weighted), 15);
+}
+
+#[test]
+fn unit_weights_match_position_match() {
+    let weights = vec![1; 5];
+    for genome in ["hello", "jello", "", "hex", "hello world"] {
+        assert_eq!(
+            weighted_position_match(genome, "hello", &weights),
+            position_match(genome, "hello")
+        );
+    }
+}
+
+#[test]
+fn weights_must_fit_the_target() {
+    let build = |weights: Vec<isize>| {
+        GeneticAlgorithmBuilder::new()
+            .target("abc")
+            .fitness_mode(FitnessMode::Weighted(weights))
+            .build()
+    };
+    assert!(build(vec![3, 2, 1]).is_ok());
+    assert!(build(vec![3, 2]).is_err());
+    assert!(build(vec![3, -2, 1]).is_err());
+    let mode: FitnessMode = "weighted:3,2,1".parse().unwrap();
+    assert_eq!(mode, FitnessMode::Weighted(vec![3, 2, 1]));
+    assert_eq!(mode.to_string(), "weighted:3,2,1");
+    assert!("weighted:3,x".parse::<FitnessMode>().is_err());
+}
+
+#[test]
+fn ignoring_case_credits_letters_in_either_case() {
+    let exact = StringContext::new("Hello World").with_alphabet("helowrdHELOWRD ");
+    let ignore_case = exact.clone().with_ignore_case(true);
+    assert_eq!(score("hELLO wORLD", &exact), 1);
+    assert_eq!(score("hELLO wORLD", &ignore_case), 11);
+    assert_eq!(score("Hello World", &ignore_case), 11);
+    assert_eq!(String::max_fitness(&ignore_case), 11);
+
+    let levenshtein = StringContext {
+        fitness_mode: FitnessMode::Levenshtein,
+        ..ignore_case.clone()
+    };
+    assert_eq!(score("ello World", &levenshtein), 10);
+    let weighted = StringContext {
+        fitness_mode: FitnessMode::Weighted(vec![1; 11]),
+        ..ignore_case
+    };
+    assert_eq!(score("HELLO WORLD", &weighted), 11);
+}
+
+#[test]
+fn ignoring_case_makes_the_other_case_reachable() {
+    let context = StringContext::new("Hello World");
+    assert!(matches!(
+        context.validate(),
+        Err(GaError::TargetNotInAlphabet { character: 'H', .. })
+    ));
+    let context = context.with_ignore_case(true);
+    assert_eq!(context.validate(), Ok(()));
+    assert!(context.unreachable_chars().is_empty());
+    assert_eq!(context.reachable_max_fitness(), 11);
+
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("Hello World")
+        .ignore_case(true)
+        .seed(1)
+        .build()
+        .unwrap();
+    assert!(genetic_algorithm.solve().is_solved());
+}
+
+#[test]
+fn display_keeps_the_evolved_case() {
+    let mut candidate = Candidate::new("hELlo".to_string());
+    candidate.fitness = Some(5);
+    let green = |c: char| format!("\x1b[92m{}\x1b[0m", c);
+    let expected: String = "hELlo".chars().map(green).collect();
+    assert_eq!(candidate.display_str("Hello", true, true), expected);
+    assert!(candidate
+        .display_str("Hello", false, true)
+        .contains("\x1b[91mE"));
+}
+
+#[test]
+fn case_folding_keeps_positions() {
+    assert!(chars_match('a', 'A', true));
+    assert!(!chars_match('a', 'A', false));
+    assert!(!chars_match('a', 'b', true));
+    // 'İ' lowercases to two characters and is kept as it is.
+    assert_eq!(fold_case("AbİC"), "abİc");
+}
+
+#[test]
+fn max_fitness_counts_only_what_the_alphabet_can_spell() {
+    let weighted = FitnessMode::Weighted(vec![5, 4, 3, 2, 1]);
+    assert_eq!(
+        max_fitness("hello", &FitnessMode::PositionMatch, LETTERS),
+        5
+    );
+    assert_eq!(max_fitness("hello", &FitnessMode::PositionMatch, "helx"), 4);
+    assert_eq!(max_fitness("hello", &FitnessMode::Levenshtein, "hex"), 2);
+    assert_eq!(max_fitness("hello", &weighted, "elo"), 10);
+    assert_eq!(max_fitness("hello", &FitnessMode::PositionMatch, ""), 0);
+
+    // The context and the run agree with it.
+    let context = StringContext::new("hello").with_alphabet("helx");
+    assert_eq!(context.reachable_max_fitness(), 4);
+    let genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .alphabet("helx")
+        .allow_unreachable(true)
+        .build()
+        .unwrap();
+    assert_eq!(genetic_algorithm.max_fitness(), 4);
+}
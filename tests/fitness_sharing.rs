@@ -0,0 +1,93 @@
+use ga::{
+    apply_fitness_sharing, reorder_by_fitness, Candidate, FitnessSharing, GeneticAlgorithmBuilder,
+    Objective,
+};
+
+mod common;
+
+use common::scored;
+
+/// Three candidates crowded around `aaaa` and one far away, all equally fit.
+fn equally_fit() -> Vec<Candidate> {
+    vec![
+        scored("aaaa", 2),
+        scored("aaab", 2),
+        scored("aaba", 2),
+        scored("zzzz", 2),
+    ]
+}
+
+#[test]
+fn crowded_candidates_get_larger_niche_counts() {
+    let mut population = equally_fit();
+    apply_fitness_sharing(&mut population, 2, 1.0);
+    // aaaa is 1 away from both neighbors: 1 + 0.5 + 0.5; they are 2 apart from each other.
+    assert_eq!(population[0].niche_count, 2.0);
+    assert_eq!(population[1].niche_count, 1.5);
+    assert_eq!(population[2].niche_count, 1.5);
+    assert_eq!(population[3].niche_count, 1.0);
+    // The raw score is left alone.
+    assert!(population.iter().all(|c| c.fitness == Some(2)));
+}
+
+#[test]
+fn isolated_candidates_rank_ahead_of_equally_fit_crowded_ones() {
+    let mut population = equally_fit();
+    apply_fitness_sharing(&mut population, 2, 1.0);
+    while reorder_by_fitness(&mut population, Objective::Maximize) {}
+    assert_eq!(population[0].genome, "zzzz");
+    assert_eq!(population[3].genome, "aaaa");
+
+    // A better score still beats a lonelier one.
+    let mut fitter = equally_fit();
+    fitter[0].fitness = Some(5);
+    apply_fitness_sharing(&mut fitter, 2, 1.0);
+    while reorder_by_fitness(&mut fitter, Objective::Maximize) {}
+    assert_eq!(fitter[0].genome, "aaaa");
+}
+
+#[test]
+fn sharing_runs_still_solve() {
+    let sharing = FitnessSharing {
+        radius: 3,
+        alpha: 1.0,
+    };
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .fitness_sharing(sharing)
+        .seed(3)
+        .max_generations(2000)
+        .build()
+        .unwrap();
+    genetic_algorithm.solve();
+    assert!(genetic_algorithm.is_solved());
+    assert!(genetic_algorithm
+        .population()
+        .iter()
+        .all(|c| c.niche_count >= 1.0));
+}
+
+#[test]
+fn fitness_sharing_parses_radius_and_alpha() {
+    assert_eq!(
+        "3:1".parse(),
+        Ok(FitnessSharing {
+            radius: 3,
+            alpha: 1.0
+        })
+    );
+    assert_eq!(
+        "3:0.5".parse::<FitnessSharing>().unwrap().to_string(),
+        "3:0.5"
+    );
+    assert!("3".parse::<FitnessSharing>().is_err());
+    assert!("0:1".parse::<FitnessSharing>().is_err());
+    assert!("3:0".parse::<FitnessSharing>().is_err());
+    assert!(GeneticAlgorithmBuilder::new()
+        .fitness_sharing(FitnessSharing {
+            radius: 3,
+            alpha: f64::NAN
+        })
+        .build()
+        .is_err());
+}
@@ -0,0 +1,172 @@
+use std::cell::RefCell;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use ga::{Candidate, GeneticAlgorithmBuilder, ReplacementMode, TieBreak, STATE};
+
+type Calls = Rc<RefCell<Vec<(String, Vec<usize>, usize)>>>;
+
+/// Records the state label, focus and population size of every callback invocation.
+fn recording(calls: &Calls) -> impl Fn(&Vec<Candidate>, &str, &[usize]) -> ControlFlow<()> {
+    let calls = Rc::clone(calls);
+    move |population, label, focus| {
+        calls
+            .borrow_mut()
+            .push((label.to_string(), focus.to_vec(), population.len()));
+        ControlFlow::Continue(())
+    }
+}
+
+#[test]
+fn the_callback_receives_the_indices_each_step_touched() {
+    let calls: Calls = Rc::default();
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .population_size(10)
+        .seed(3)
+        .callback(recording(&calls))
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().take(2).for_each(drop);
+    for (label, focus, len) in calls.borrow().iter() {
+        assert!(focus.iter().all(|&i| i < *len), "{} {:?}", label, focus);
+        match label.as_str() {
+            "Seeding the population" => assert_eq!(focus, &[len - 1]),
+            "Computing fitness" => assert_eq!(focus.len(), 1),
+            "Sorting by fitness" => assert!(!focus.is_empty()),
+            "Breeding new candidates" => {
+                assert_eq!(focus.last(), Some(&(len - 1)));
+                assert!(focus.len() >= 2);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn focus_is_available_between_steps() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new().seed(3).build().unwrap();
+    assert!(genetic_algorithm.focus().is_empty());
+    genetic_algorithm.next();
+    assert_eq!(genetic_algorithm.focus(), [0]);
+    genetic_algorithm.next();
+    assert_eq!(genetic_algorithm.focus(), [1]);
+}
+
+#[test]
+fn steady_state_focus_follows_the_inserted_child() {
+    let calls: Calls = Rc::default();
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .population_size(10)
+        .replacement(ReplacementMode::SteadyState)
+        .seed(3)
+        .callback(recording(&calls))
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().take(5).for_each(drop);
+    assert!(calls
+        .borrow()
+        .iter()
+        .all(|(_, focus, len)| focus.iter().all(|&i| i < *len)));
+}
+
+/// Checks the focus after every `next()` against what the step did, comparing with the
+/// population before it.
+fn check_focus_semantics(batch: bool, replacement: ReplacementMode) {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .population_size(12)
+        .num_fit_to_keep(4)
+        .batch(batch)
+        .replacement(replacement)
+        .seed(5)
+        .max_generations(4)
+        .build()
+        .unwrap();
+    while !genetic_algorithm.is_finished() {
+        let state = genetic_algorithm.state().clone();
+        let before = genetic_algorithm.population().to_vec();
+        let stepped = genetic_algorithm.next().is_some();
+        let population = genetic_algorithm.population();
+        let focus = genetic_algorithm.focus().to_vec();
+        if !stepped {
+            assert!(focus.is_empty(), "{:?} {:?}", state, focus);
+            continue;
+        }
+        assert!(!focus.is_empty(), "{:?}", state);
+        assert!(focus.windows(2).all(|w| w[0] < w[1]), "{:?}", focus);
+        assert!(focus.iter().all(|&i| i < population.len()));
+        match state {
+            STATE::Init => {
+                assert_eq!(focus, [before.len()]);
+                assert_eq!(population.len(), before.len() + 1);
+            }
+            STATE::ComputeFitness => {
+                let newly_scored: Vec<usize> = (0..population.len())
+                    .filter(|&i| before[i].fitness.is_none() && population[i].fitness.is_some())
+                    .collect();
+                assert_eq!(focus, newly_scored);
+                assert_eq!(focus.len() == 1, !batch || before.len() == 1);
+            }
+            STATE::Reorder => {
+                let moved: Vec<usize> = (0..population.len())
+                    .filter(|&i| population[i].fitness != before[i].fitness)
+                    .collect();
+                assert_eq!(focus, moved);
+            }
+            STATE::RemoveUnfit => {
+                assert_eq!(population.len(), before.len() - 1);
+                assert_eq!(focus, [population.len() - 1]);
+            }
+            STATE::BreedNew => {
+                let children: Vec<usize> = (before.len()..population.len()).collect();
+                assert!(children.iter().all(|i| focus.contains(i)));
+                if !batch {
+                    assert_eq!(children.len(), 1);
+                    // The parents come from the candidates that were there before.
+                    assert!(focus.len() <= 3);
+                    assert!(focus[..focus.len() - 1].iter().all(|&i| i < before.len()));
+                }
+            }
+            STATE::Replace(_) => assert!(focus.len() <= 3),
+        }
+    }
+}
+
+#[test]
+fn focus_matches_what_each_step_did() {
+    for batch in [false, true] {
+        check_focus_semantics(batch, ReplacementMode::Generational);
+        check_focus_semantics(batch, ReplacementMode::SteadyState);
+    }
+}
+
+#[test]
+fn sorting_with_a_tie_break_focuses_every_changed_slot() {
+    for fast_sort in [false, true] {
+        let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+            .population_size(12)
+            .num_fit_to_keep(4)
+            .tie_break(TieBreak::Lexical)
+            .fast_sort(fast_sort)
+            .seed(5)
+            .max_generations(4)
+            .build()
+            .unwrap();
+        while !genetic_algorithm.is_finished() {
+            let sorting = matches!(genetic_algorithm.state(), STATE::Reorder);
+            let before = genetic_algorithm.population().to_vec();
+            genetic_algorithm.next();
+            // A population already in order moves straight on to the next step.
+            if !sorting || !matches!(genetic_algorithm.state(), STATE::Reorder) {
+                continue;
+            }
+            let population = genetic_algorithm.population();
+            let changed: Vec<usize> = (0..population.len())
+                .filter(|&i| {
+                    population[i].fitness != before[i].fitness
+                        || population[i].genome != before[i].genome
+                })
+                .collect();
+            assert_eq!(genetic_algorithm.focus(), changed);
+        }
+    }
+}
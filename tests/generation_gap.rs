@@ -0,0 +1,51 @@
+use ga::{children_per_generation, GeneticAlgorithmBuilder};
+
+#[test]
+fn the_gap_sets_how_many_children_are_bred() {
+    assert_eq!(children_per_generation(20, 1.0), 20);
+    assert_eq!(children_per_generation(20, 0.25), 5);
+    assert_eq!(children_per_generation(20, 0.01), 1);
+
+    for (gap, children) in [(0.25, 5), (0.9, 18), (1.0, 20)] {
+        let mut ga = GeneticAlgorithmBuilder::new()
+            .population_size(20)
+            .num_fit_to_keep(4)
+            .generation_gap(gap)
+            .seed(0)
+            .build()
+            .unwrap();
+        for _ in ga.by_generation().take(3) {}
+        // Children are born at age 0, everyone carried over has aged.
+        let newborn = ga.population().iter().filter(|c| c.age == 0).count();
+        assert_eq!(newborn, children, "gap {}", gap);
+        assert_eq!(ga.population().len(), 20);
+    }
+}
+
+#[test]
+fn the_gap_must_be_a_fraction_and_excludes_an_elite() {
+    for gap in [0.0, -0.5, 1.5, f64::NAN] {
+        assert!(GeneticAlgorithmBuilder::new()
+            .generation_gap(gap)
+            .build()
+            .is_err());
+    }
+    assert!(GeneticAlgorithmBuilder::new()
+        .generation_gap(0.5)
+        .elite_count(1)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn small_gaps_still_solve() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .generation_gap(0.2)
+        .seed(1)
+        .max_generations(2000)
+        .build()
+        .unwrap();
+    ga.solve();
+    assert!(ga.is_solved());
+}
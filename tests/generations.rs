@@ -0,0 +1,43 @@
+use ga::{GeneticAlgorithmBuilder, STATE};
+
+#[test]
+fn by_generation_yields_each_completed_generation() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("a rather long target string")
+        .seed(0)
+        .build()
+        .unwrap();
+    let generations: Vec<usize> = ga.by_generation().take(5).map(|s| s.generation).collect();
+    assert_eq!(generations, [0, 1, 2, 3, 4]);
+    assert_eq!(ga.generation(), 5);
+}
+
+#[test]
+fn by_generation_ends_with_the_solving_generation() {
+    let mut ga = GeneticAlgorithmBuilder::new().seed(1).build().unwrap();
+    let last = ga.by_generation().last().unwrap();
+    assert!(ga.is_solved());
+    assert_eq!(last.generation, ga.generation());
+    assert_eq!(last.best_genome, "hello world");
+}
+
+#[test]
+fn generation_counts_completed_cycles_of_the_state_machine() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("a rather long target string")
+        .seed(0)
+        .build()
+        .unwrap();
+    assert_eq!(ga.generation(), 0);
+    let mut cycles = 0;
+    while cycles < 7 {
+        let was_init = matches!(ga.state(), STATE::Init);
+        ga.next();
+        // A cycle ends when breeding hands back to seeding.
+        if !was_init && matches!(ga.state(), STATE::Init) {
+            cycles += 1;
+            assert_eq!(ga.generation(), cycles);
+        }
+    }
+    assert_eq!(ga.generation(), 7);
+}
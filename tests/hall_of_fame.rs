@@ -0,0 +1,98 @@
+use ga::{
+    admit_to_hall_of_fame, Candidate, Distance, GeneticAlgorithmBuilder, Objective, ReplacementMode,
+};
+
+mod common;
+
+use common::scored;
+
+fn genomes(hall_of_fame: &[Candidate]) -> Vec<&str> {
+    hall_of_fame.iter().map(|c| c.genome.as_str()).collect()
+}
+
+#[test]
+fn hall_of_fame_keeps_the_best_distinct_candidates() {
+    let mut hall_of_fame = Vec::new();
+    for (genome, fitness) in [("b", 2), ("a", 1), ("c", 3), ("b", 2), ("d", 2), ("e", 0)] {
+        admit_to_hall_of_fame(
+            &mut hall_of_fame,
+            &scored(genome, fitness),
+            3,
+            Objective::Maximize,
+        );
+    }
+    // "d" ties with "b" but came later; "e" and "a" no longer fit.
+    assert_eq!(genomes(&hall_of_fame), ["c", "b", "d"]);
+    assert!(!admit_to_hall_of_fame(
+        &mut hall_of_fame,
+        &Candidate::new("x".to_string()),
+        3,
+        Objective::Maximize
+    ));
+}
+
+#[test]
+fn minimizing_puts_the_lowest_score_first() {
+    let mut hall_of_fame = Vec::new();
+    for (genome, fitness) in [("b", 2), ("a", 1), ("c", 3)] {
+        admit_to_hall_of_fame(
+            &mut hall_of_fame,
+            &scored(genome, fitness),
+            2,
+            Objective::Minimize,
+        );
+    }
+    assert_eq!(genomes(&hall_of_fame), ["a", "b"]);
+}
+
+#[test]
+fn hall_of_fame_outlives_the_population() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("a target that takes a while")
+        .hall_of_fame(5)
+        .max_generations(40)
+        .seed(2)
+        .build()
+        .unwrap();
+    let best_ever = genetic_algorithm
+        .by_generation()
+        .map(|stats| stats.best_fitness)
+        .max()
+        .unwrap();
+    let hall_of_fame = genetic_algorithm.hall_of_fame();
+    assert_eq!(hall_of_fame.len(), 5);
+    assert_eq!(hall_of_fame[0].fitness, Some(best_ever));
+    assert!(hall_of_fame
+        .windows(2)
+        .all(|w| w[0].fitness >= w[1].fitness));
+    for (i, a) in hall_of_fame.iter().enumerate() {
+        for b in &hall_of_fame[i + 1..] {
+            assert_ne!(a.genome.distance(&b.genome), 0);
+        }
+    }
+}
+
+#[test]
+fn no_hall_of_fame_by_default() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new().seed(2).build().unwrap();
+    genetic_algorithm.by_generation().take(3).for_each(drop);
+    assert!(genetic_algorithm.hall_of_fame().is_empty());
+}
+
+#[test]
+fn steady_state_children_reach_the_hall_of_fame() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("a target that takes a while")
+        .hall_of_fame(5)
+        .replacement(ReplacementMode::SteadyState)
+        .max_generations(40)
+        .seed(2)
+        .build()
+        .unwrap();
+    let best_ever = genetic_algorithm
+        .by_generation()
+        .map(|stats| stats.best_fitness)
+        .max()
+        .unwrap();
+    assert_eq!(genetic_algorithm.hall_of_fame()[0].fitness, Some(best_ever));
+}
@@ -0,0 +1,64 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{hint, Crossover, CrossoverMode, GeneticAlgorithmBuilder, StringContext};
+
+#[test]
+fn only_mutated_characters_are_hinted() {
+    let mut rng = StdRng::seed_from_u64(0);
+    // The first character was not mutated, the third already matches the target.
+    assert_eq!(
+        hint("abcd", "axcy", "qqcz", 1.0, &mut rng),
+        ("aqcz".to_string(), 2)
+    );
+    assert_eq!(
+        hint("abcd", "axcy", "qqcz", 0.0, &mut rng),
+        ("axcy".to_string(), 0)
+    );
+}
+
+#[test]
+fn hinted_mutation_moves_toward_the_target_and_is_counted() {
+    let mut rng = StdRng::seed_from_u64(1);
+    // With x missing from the alphabet, every character changes.
+    let context = StringContext::new("hello")
+        .with_alphabet("helo")
+        .with_hint_prob(1.0);
+    let child = "xxxxx".to_string().crossover(
+        &"xxxxx".to_string(),
+        CrossoverMode::Asexual,
+        1.0,
+        &context,
+        &mut rng,
+    );
+    assert_eq!(child, "hello");
+    // Characters the mutation already got right were not hinted.
+    assert!((1..=5).contains(&context.hints.get()));
+
+    let unhinted = StringContext::new("hello");
+    "xxxxx".to_string().crossover(
+        &"xxxxx".to_string(),
+        CrossoverMode::Asexual,
+        1.0,
+        &unhinted,
+        &mut rng,
+    );
+    assert_eq!(unhinted.hints.get(), 0);
+}
+
+#[test]
+fn hint_probability_is_validated_and_speeds_up_the_run() {
+    assert!(GeneticAlgorithmBuilder::new()
+        .hint_prob(1.5)
+        .build()
+        .is_err());
+    let generations = |hint_prob| {
+        let mut ga = GeneticAlgorithmBuilder::new()
+            .hint_prob(hint_prob)
+            .seed(1)
+            .build()
+            .unwrap();
+        ga.solve().generations
+    };
+    assert!(generations(0.5) < generations(0.0));
+}
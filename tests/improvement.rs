@@ -0,0 +1,30 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ga::GeneticAlgorithmBuilder;
+
+#[test]
+fn on_improvement_fires_only_on_strict_improvements() {
+    let improvements = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&improvements);
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("hello improvement")
+        .seed(3)
+        .build()
+        .unwrap()
+        .with_on_improvement(move |best, generation| {
+            log.borrow_mut().push((best.fitness, generation));
+        });
+    while !ga.is_solved() && ga.generation() < 200 {
+        for _ in ga.by_ref() {}
+    }
+
+    let improvements = improvements.borrow();
+    assert_eq!(improvements[0].1, 0);
+    assert!(improvements.windows(2).all(|w| w[0].0 < w[1].0));
+    let best_per_generation: Vec<isize> = ga.stats().iter().map(|s| s.best_fitness).collect();
+    assert_eq!(
+        improvements.last().unwrap().0,
+        best_per_generation.iter().max().copied()
+    );
+}
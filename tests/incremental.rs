@@ -0,0 +1,114 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    breed, CrossoverMode, FitnessMode, GeneticAlgorithmBuilder, Genome, MutationOp, StringContext,
+};
+
+mod common;
+
+use common::scored_against;
+
+const TARGET: &str = "the Quick brown fox";
+const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzQ ";
+
+#[test]
+fn incremental_fitness_matches_full_recomputation() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let weights: Vec<isize> = (1..=TARGET.len() as isize).collect();
+    for fitness_mode in [FitnessMode::PositionMatch, FitnessMode::Weighted(weights)] {
+        for ignore_case in [false, true] {
+            let context = StringContext {
+                fitness_mode: fitness_mode.clone(),
+                ..StringContext::new(TARGET)
+                    .with_alphabet(ALPHABET)
+                    .with_ignore_case(ignore_case)
+                    .with_incremental_fitness(true)
+            };
+            for mode in [
+                CrossoverMode::default(),
+                CrossoverMode::SinglePoint,
+                CrossoverMode::TwoPoint,
+                CrossoverMode::Asexual,
+            ] {
+                for _ in 0..200 {
+                    let parent_a = scored_against(&String::random(&context, &mut rng), &context);
+                    let parent_b = scored_against(&String::random(&context, &mut rng), &context);
+                    let child = breed(&[&parent_a, &parent_b], mode, 0.2, &context, &mut rng);
+                    assert!(child.fitness.is_some());
+                    let rescored = scored_against(&child.genome, &context);
+                    assert_eq!(child.fitness, rescored.fitness, "{:?}", child.genome);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn children_stay_unscored_when_the_fitness_cannot_be_derived() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let levenshtein = StringContext {
+        fitness_mode: FitnessMode::Levenshtein,
+        ..StringContext::new(TARGET).with_incremental_fitness(true)
+    };
+    let length_changing = StringContext::new(TARGET)
+        .with_mutation_op(MutationOp::InsertDelete)
+        .with_incremental_fitness(true);
+    let off = StringContext::new(TARGET);
+    for context in [&levenshtein, &off] {
+        let parent = scored_against(&String::random(context, &mut rng), context);
+        let child = breed(
+            &[&parent, &parent],
+            CrossoverMode::Asexual,
+            0.5,
+            context,
+            &mut rng,
+        );
+        assert_eq!(child.fitness, None);
+    }
+    for _ in 0..200 {
+        let parent = scored_against(
+            &String::random(&length_changing, &mut rng),
+            &length_changing,
+        );
+        let child = breed(
+            &[&parent, &parent],
+            CrossoverMode::Asexual,
+            0.5,
+            &length_changing,
+            &mut rng,
+        );
+        if child.fitness.is_some() {
+            assert_eq!(child.genome.chars().count(), parent.genome.chars().count());
+            assert_eq!(
+                child.fitness,
+                scored_against(&child.genome, &length_changing).fitness
+            );
+        }
+    }
+}
+
+#[test]
+fn incremental_runs_match_full_ones() {
+    let run = |incremental_fitness: bool| {
+        let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+            .target("incremental scoring")
+            .incremental_fitness(incremental_fitness)
+            .hall_of_fame(5)
+            .max_generations(50)
+            .seed(9)
+            .build()
+            .unwrap();
+        let stats = genetic_algorithm
+            .by_generation()
+            .map(|stats| (stats.best_fitness, stats.mean_fitness))
+            .collect::<Vec<_>>();
+        let hall_of_fame: Vec<(String, Option<isize>)> = genetic_algorithm
+            .hall_of_fame()
+            .iter()
+            .map(|c| (c.genome.clone(), c.fitness))
+            .collect();
+        (stats, hall_of_fame)
+    };
+    assert_eq!(run(false), run(true));
+}
@@ -0,0 +1,89 @@
+use std::ops::ControlFlow;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    flip_bits, from_gray, to_gray, Candidate, Crossover, CrossoverMode, Distance, Fitness,
+    GeneticAlgorithm, Genome, IntContext, IntGenome, Objective,
+};
+
+#[test]
+fn gray_code_round_trips_and_steps_by_one_bit() {
+    for value in 0..1024u32 {
+        assert_eq!(from_gray(to_gray(value)), value);
+        assert_eq!((to_gray(value) ^ to_gray(value + 1)).count_ones(), 1);
+    }
+    assert_eq!(from_gray(to_gray(u32::MAX)), u32::MAX);
+}
+
+#[test]
+fn fitness_is_the_total_distance_from_the_target() {
+    let context = IntContext::new([10, 20], 8);
+    assert_eq!(IntGenome(vec![13, 15]).fitness(&context), 8);
+    assert_eq!(IntGenome(vec![10, 20]).fitness(&context), 0);
+    assert_eq!(IntGenome::max_fitness(&context), 0);
+    assert_eq!(
+        IntGenome(vec![0b1010, 0]).distance(&IntGenome(vec![0b0110, 1])),
+        3
+    );
+}
+
+#[test]
+fn operators_stay_within_the_bit_width() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for gray_code in [true, false] {
+        let context = IntContext::new([5, 6, 7], 3).with_gray_code(gray_code);
+        for mode in [
+            CrossoverMode::default(),
+            CrossoverMode::SinglePoint,
+            CrossoverMode::TwoPoint,
+            CrossoverMode::Asexual,
+        ] {
+            for _ in 0..200 {
+                let a = IntGenome::random(&context, &mut rng);
+                let b = IntGenome::random(&context, &mut rng);
+                let child = a.crossover(&b, mode, 0.5, &context, &mut rng);
+                assert_eq!(child.0.len(), 3);
+                assert!(child.0.iter().all(|&value| value < 8), "{:?}", child);
+            }
+        }
+    }
+    assert_eq!(flip_bits(0b101, 3, 1.0, &mut rng), 0b010);
+    assert_eq!(flip_bits(0b101, 3, 0.0, &mut rng), 0b101);
+}
+
+#[test]
+fn crossover_without_mutation_only_mixes_parent_bits() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let context = IntContext::new([0], 8).with_gray_code(false);
+    let child = IntGenome(vec![0b1111_0000]).crossover(
+        &IntGenome(vec![0b0000_1111]),
+        CrossoverMode::SinglePoint,
+        0.0,
+        &context,
+        &mut rng,
+    );
+    // A single cut keeps a prefix of the first parent's bits and the rest of the second's.
+    let value = child.0[0];
+    assert!(
+        (0..=8).any(|cut| value == (0b1111_0000 & !(0xff >> cut)) | (0b0000_1111 & (0xff >> cut)))
+    );
+}
+
+#[test]
+fn minimizing_reaches_the_target_numbers() {
+    let mut genetic_algorithm: GeneticAlgorithm<_, IntGenome> = GeneticAlgorithm::new(
+        IntContext::new([300, 7], 10),
+        8,
+        60,
+        0.03,
+        Some(2),
+        |_: &Vec<Candidate<IntGenome>>, _: &str, _: &[usize]| ControlFlow::Continue(()),
+    )
+    .with_objective(Objective::Minimize)
+    .with_max_generations(1000);
+    let result = genetic_algorithm.solve();
+    assert!(result.is_solved());
+    assert_eq!(result.best.genome, IntGenome(vec![300, 7]));
+}
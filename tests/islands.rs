@@ -0,0 +1,95 @@
+use ga::{Candidate, GeneticAlgorithmBuilder, IslandModel, NoCallback, Outcome};
+
+fn island(seed: u64) -> ga::GeneticAlgorithm<NoCallback> {
+    GeneticAlgorithmBuilder::new()
+        .target("islands")
+        .seed(seed)
+        .max_generations(500)
+        .build()
+        .unwrap()
+}
+
+fn summary(results: &[ga::SolveResult]) -> Vec<(String, usize, Outcome)> {
+    results
+        .iter()
+        .map(|r| (r.best.genome.clone(), r.generations, r.outcome))
+        .collect()
+}
+
+#[test]
+fn runs_are_reproducible_across_threads() {
+    let model = IslandModel::new(4, 11).with_migration(3, 2);
+    let first = model.run(island);
+    assert_eq!(first.len(), 4);
+    assert!(first.iter().any(|r| r.outcome == Outcome::Reached));
+    assert!(first
+        .iter()
+        .all(|r| matches!(r.outcome, Outcome::Reached | Outcome::Stopped)));
+    for _ in 0..3 {
+        assert_eq!(summary(&model.run(island)), summary(&first));
+    }
+}
+
+#[test]
+fn a_single_island_runs_like_solve() {
+    let results = IslandModel::new(1, 7).with_migration(4, 2).run(island);
+    let solved = island(7).solve();
+    assert_eq!(summary(&results), summary(&[solved]));
+}
+
+#[test]
+fn islands_stop_together_at_the_generation_cap() {
+    let results = IslandModel::new(3, 1).run(|seed| {
+        GeneticAlgorithmBuilder::new()
+            .target("far too long to solve in a few generations")
+            .seed(seed)
+            .max_generations(20)
+            .build()
+            .unwrap()
+    });
+    assert!(results
+        .iter()
+        .all(|r| r.outcome == Outcome::GenerationLimit && r.generations == 20));
+}
+
+#[test]
+fn immigrants_replace_the_last_candidates() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hi")
+        .population_size(6)
+        .num_fit_to_keep(2)
+        .seed(1)
+        .lineage(true)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().next();
+    let before = genetic_algorithm.population().to_vec();
+    let mut migrant = Candidate::new("hi".to_string());
+    migrant.fitness = Some(2);
+    migrant.id = 999;
+    genetic_algorithm.immigrate([migrant]);
+    let population = genetic_algorithm.population();
+    assert_eq!(population.len(), before.len());
+    assert!(population[..5]
+        .iter()
+        .zip(&before)
+        .all(|(a, b)| a.genome == b.genome));
+    let arrived = &population[5];
+    assert_eq!(arrived.genome, "hi");
+    assert_eq!(arrived.fitness, Some(2));
+    assert!(arrived.parents.is_empty());
+    // Registered in this population's lineage under a new id.
+    let lineage = genetic_algorithm.lineage().unwrap();
+    assert_eq!(lineage.get(arrived.id).unwrap().genome, "hi");
+    assert_ne!(arrived.id, 999);
+}
+
+#[test]
+fn fittest_lists_the_best_scored_candidates_first() {
+    let mut genetic_algorithm = island(3);
+    genetic_algorithm.by_generation().next();
+    let fittest = genetic_algorithm.fittest(3);
+    assert_eq!(fittest.len(), 3);
+    assert!(fittest.windows(2).all(|w| w[0].fitness >= w[1].fitness));
+    assert_eq!(fittest[0].fitness, genetic_algorithm.best_fitness());
+}
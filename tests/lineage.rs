@@ -0,0 +1,97 @@
+use ga::{
+    breed, Candidate, CrossoverMode, GeneticAlgorithmBuilder, Lineage, ReplacementMode,
+    StringContext,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn ancestry_follows_first_parents_back_to_a_seed() {
+    let mut lineage = Lineage::default();
+    let a = lineage.register(&"aaaa".to_string(), 0, Vec::new());
+    let b = lineage.register(&"bbbb".to_string(), 0, Vec::new());
+    let ab = lineage.register(&"abab".to_string(), 1, vec![a, b]);
+    let ba = lineage.register(&"baba".to_string(), 1, vec![b, a]);
+    let child = lineage.register(&"abba".to_string(), 2, vec![ab, ba]);
+    let chain: Vec<&str> = lineage
+        .ancestry(child)
+        .iter()
+        .map(|ancestor| ancestor.genome.as_str())
+        .collect();
+    assert_eq!(chain, ["aaaa", "abab", "abba"]);
+    assert!(lineage.ancestry(99).is_empty());
+}
+
+#[test]
+fn breeding_records_parent_ids_only_when_tracked() {
+    let context = StringContext::new("abcd");
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut parent_a = Candidate::new("abcd".to_string());
+    let mut parent_b = Candidate::new("dcba".to_string());
+    let child = breed(
+        &[&parent_a, &parent_b],
+        CrossoverMode::default(),
+        0.1,
+        &context,
+        &mut rng,
+    );
+    assert!(child.parents.is_empty());
+
+    parent_a.id = 3;
+    parent_b.id = 7;
+    let child = breed(
+        &[&parent_a, &parent_b],
+        CrossoverMode::default(),
+        0.1,
+        &context,
+        &mut rng,
+    );
+    assert_eq!(child.parents, [3, 7]);
+    let clone = breed(
+        &[&parent_a, &parent_a],
+        CrossoverMode::default(),
+        0.1,
+        &context,
+        &mut rng,
+    );
+    assert_eq!(clone.parents, [3]);
+}
+
+#[test]
+fn the_winner_descends_from_the_initial_population() {
+    for replacement in [ReplacementMode::Generational, ReplacementMode::SteadyState] {
+        for batch in [false, true] {
+            let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+                .target("hello")
+                .seed(3)
+                .replacement(replacement)
+                .batch(batch)
+                .lineage(true)
+                .build()
+                .unwrap();
+            let result = genetic_algorithm.solve();
+            assert!(result.is_solved());
+            let lineage = genetic_algorithm.lineage().unwrap();
+            let chain = lineage.ancestry(result.best.id);
+            assert_eq!(chain.last().unwrap().genome, result.best.genome);
+            assert_eq!(chain[0].generation, 0);
+            assert!(chain[0].parents.is_empty());
+            assert!(chain
+                .windows(2)
+                .all(|w| w[1].parents[0] == w[0].id && w[0].generation <= w[1].generation));
+        }
+    }
+}
+
+#[test]
+fn lineage_is_off_by_default() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hi")
+        .seed(1)
+        .build()
+        .unwrap();
+    let result = genetic_algorithm.solve();
+    assert!(genetic_algorithm.lineage().is_none());
+    assert_eq!(result.best.id, 0);
+    assert!(result.best.parents.is_empty());
+}
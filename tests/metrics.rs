@@ -0,0 +1,48 @@
+#![cfg(feature = "metrics")]
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+use ga::{GeneticAlgorithmBuilder, Metrics, MetricsServer};
+
+fn get(addr: SocketAddr) -> serde_json::Value {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"));
+    assert!(head.contains("Content-Type: application/json"));
+    serde_json::from_str(body).unwrap()
+}
+
+#[test]
+fn serves_the_latest_snapshot() {
+    let server = MetricsServer::bind("127.0.0.1:0").unwrap();
+    assert_eq!(get(server.local_addr()), serde_json::Value::Null);
+
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hi")
+        .seed(1)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().next();
+    server.publish(&Metrics::of(&genetic_algorithm));
+    let metrics = get(server.local_addr());
+    assert_eq!(metrics["generation"], 1);
+    assert_eq!(metrics["max_fitness"], 2);
+    assert_eq!(metrics["finished"], genetic_algorithm.is_finished());
+    assert_eq!(
+        metrics["best_fitness"],
+        genetic_algorithm.best_fitness().unwrap()
+    );
+
+    let result = genetic_algorithm.solve();
+    server.publish(&Metrics::of(&genetic_algorithm));
+    let metrics = get(server.local_addr());
+    assert_eq!(metrics["solved"], true);
+    assert_eq!(metrics["generation"], result.generations);
+    assert_eq!(metrics["best"]["genome"], "hi");
+}
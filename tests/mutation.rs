@@ -0,0 +1,240 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    insert_delete, scramble, shift_to_neighbor, swap_adjacent, Cooling, FitnessMode,
+    GeneticAlgorithmBuilder, MutationOp, MutationSchedule, Objective, LETTERS,
+};
+
+fn sorted_chars(text: &str) -> Vec<char> {
+    let mut chars: Vec<char> = text.chars().collect();
+    chars.sort_unstable();
+    chars
+}
+
+#[test]
+fn swap_keeps_the_characters() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let mutated = swap_adjacent("hello world", 0.5, &mut rng);
+        assert_eq!(sorted_chars(&mutated), sorted_chars("hello world"));
+    }
+    assert_ne!(swap_adjacent("abcdef", 1.0, &mut rng), "abcdef");
+}
+
+#[test]
+fn neighbor_shift_moves_one_step_along_the_alphabet() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let alphabet = "abcde";
+    for _ in 0..100 {
+        let mutated = shift_to_neighbor("aace", 1.0, alphabet, &mut rng);
+        for (before, after) in "aace".chars().zip(mutated.chars()) {
+            let i = alphabet.find(before).unwrap() as isize;
+            let j = alphabet.find(after).unwrap() as isize;
+            // One step either way, wrapping around the five letters.
+            assert!(
+                [1, 4].contains(&(i - j).rem_euclid(5)),
+                "{} -> {}",
+                before,
+                after
+            );
+        }
+    }
+    assert_eq!(shift_to_neighbor("abc", 0.0, alphabet, &mut rng), "abc");
+}
+
+#[test]
+fn insert_delete_changes_length_but_never_empties() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut lengths = Vec::new();
+    for _ in 0..100 {
+        let mutated = insert_delete("hello", 0.5, LETTERS, &mut rng);
+        assert!(!mutated.is_empty());
+        assert!(mutated.chars().all(|c| LETTERS.contains(c)));
+        lengths.push(mutated.chars().count());
+    }
+    assert!(lengths.iter().any(|&len| len < 5));
+    assert!(lengths.iter().any(|&len| len > 5));
+    assert_eq!(
+        insert_delete("x", 1.0, LETTERS, &mut rng).chars().count(),
+        2
+    );
+}
+
+#[test]
+fn every_operator_leaves_text_alone_without_mutation() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for op in [
+        MutationOp::Replace,
+        MutationOp::SwapAdjacent,
+        MutationOp::NeighborShift,
+        MutationOp::InsertDelete,
+        MutationOp::Scramble { max_len: 3 },
+    ] {
+        assert_eq!(op.apply("hello", 0.0, LETTERS, &mut rng), "hello");
+        assert_eq!(op.to_string().parse(), Ok(op));
+    }
+    assert!("shuffle".parse::<MutationOp>().is_err());
+    assert!("scramble:1".parse::<MutationOp>().is_err());
+    assert!("scramble".parse::<MutationOp>().is_err());
+}
+
+#[test]
+fn scramble_only_shuffles_a_short_window() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let text = "abcdefghijklmnop";
+    let mut longest = 0;
+    for _ in 0..1000 {
+        let mutated = scramble(text, 1.0, 4, &mut rng);
+        assert_eq!(sorted_chars(&mutated), sorted_chars(text));
+        // Every moved character lies within one window of at most 4 positions.
+        let moved: Vec<usize> = text
+            .chars()
+            .zip(mutated.chars())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i)
+            .collect();
+        if let (Some(first), Some(last)) = (moved.first(), moved.last()) {
+            assert!(last - first < 4, "{}", mutated);
+            longest = longest.max(last - first + 1);
+        }
+    }
+    assert_eq!(longest, 4);
+    // Too short to shuffle anything.
+    assert_eq!(scramble("a", 1.0, 4, &mut rng), "a");
+}
+
+#[test]
+fn operators_that_introduce_characters_solve_a_short_target() {
+    // Swapping only rearranges what the initial population happened to contain.
+    for op in [MutationOp::Replace, MutationOp::InsertDelete] {
+        let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+            .target("hello")
+            .fitness_mode(FitnessMode::Levenshtein)
+            .mutation_op(op)
+            .seed(4)
+            .build()
+            .unwrap();
+        genetic_algorithm.by_generation().take(500).for_each(drop);
+        assert!(genetic_algorithm.is_solved(), "{} did not converge", op);
+    }
+}
+
+#[test]
+fn neighbor_shift_climbs_a_smooth_landscape() {
+    // Distance along the alphabet rewards every step towards the target character.
+    let alphabet_distance = |text: &str, target: &str| -> isize {
+        text.chars()
+            .zip(target.chars())
+            .map(|(a, b)| {
+                (LETTERS.find(a).unwrap() as isize - LETTERS.find(b).unwrap() as isize).abs()
+            })
+            .sum()
+    };
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .fitness_fn(alphabet_distance)
+        .objective(Objective::Minimize)
+        .mutation_op(MutationOp::NeighborShift)
+        .seed(4)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().take(500).for_each(drop);
+    assert!(genetic_algorithm.is_solved());
+}
+
+#[test]
+fn schedules_cool_down_to_their_floor() {
+    let constant = Cooling::Constant(0.15);
+    assert_eq!(constant.rate(0), 0.15);
+    assert_eq!(constant.rate(1000), 0.15);
+
+    let linear = Cooling::Linear {
+        start: 0.5,
+        floor: 0.1,
+        generations: 4,
+    };
+    let rates: Vec<f64> = (0..6).map(|g| linear.rate(g)).collect();
+    for (rate, expected) in rates.iter().zip([0.5, 0.4, 0.3, 0.2, 0.1, 0.1]) {
+        assert!((rate - expected).abs() < 1e-9, "{:?}", rates);
+    }
+
+    let exponential = Cooling::Exponential {
+        start: 0.5,
+        floor: 0.1,
+        decay: 0.5,
+    };
+    assert_eq!(exponential.rate(0), 0.5);
+    assert_eq!(exponential.rate(1), 0.25);
+    assert_eq!(exponential.rate(10), 0.1);
+    assert_eq!(exponential.rate(usize::MAX), 0.1);
+}
+
+#[test]
+fn schedules_parse_and_display_alike() {
+    for text in [
+        "constant:0.15",
+        "linear:0.5:0.05:200",
+        "exponential:0.5:0.05:0.98",
+    ] {
+        assert_eq!(text.parse::<Cooling>().unwrap().to_string(), text);
+    }
+    assert!("linear:0.5:0.05".parse::<Cooling>().is_err());
+    assert!("cosine:1:0:5".parse::<Cooling>().is_err());
+}
+
+#[test]
+fn the_algorithm_breeds_at_the_scheduled_rate() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("a long target that takes a while")
+        .mutation_schedule(Cooling::Linear {
+            start: 0.4,
+            floor: 0.1,
+            generations: 3,
+        })
+        .seed(1)
+        .build()
+        .unwrap();
+    let mut rates = vec![genetic_algorithm.current_mutation_prob()];
+    while rates.len() < 6 {
+        genetic_algorithm.by_generation().next();
+        rates.push(genetic_algorithm.current_mutation_prob());
+    }
+    for (rate, expected) in rates.iter().zip([0.4, 0.4, 0.3, 0.2, 0.1, 0.1]) {
+        assert!((rate - expected).abs() < 1e-9, "{:?}", rates);
+    }
+}
+
+#[test]
+fn closures_are_schedules_too() {
+    let halving = |generation: usize| 0.4 / 2f64.powi(generation as i32);
+    assert_eq!(halving.rate(2), 0.1);
+    let genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .build()
+        .unwrap()
+        .with_mutation_schedule(halving);
+    assert_eq!(genetic_algorithm.current_mutation_prob(), 0.4);
+}
+
+#[test]
+fn schedules_must_cool_down_within_probabilities() {
+    for schedule in [
+        Cooling::Linear {
+            start: 0.1,
+            floor: 0.4,
+            generations: 3,
+        },
+        Cooling::Constant(1.5),
+        Cooling::Exponential {
+            start: 0.5,
+            floor: 0.1,
+            decay: 1.5,
+        },
+    ] {
+        assert!(GeneticAlgorithmBuilder::new()
+            .mutation_schedule(schedule)
+            .build()
+            .is_err());
+    }
+}
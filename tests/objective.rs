@@ -0,0 +1,105 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    levenshtein, reorder_by_fitness, reorder_by_fitness_fast, select_roulette, select_tournament,
+    Candidate, FitnessMode, GaError, GeneticAlgorithmBuilder, Objective,
+};
+
+mod common;
+
+use common::scored;
+
+#[test]
+fn minimizing_sorts_lowest_first_and_unscored_last() {
+    let population = vec![
+        scored("a", 3),
+        Candidate::new("b".to_string()),
+        scored("c", 0),
+        scored("d", 7),
+    ];
+    let mut bubbled = population.clone();
+    while reorder_by_fitness(&mut bubbled, Objective::Minimize) {}
+    let mut sorted = population;
+    reorder_by_fitness_fast(&mut sorted, Objective::Minimize);
+    for population in [bubbled, sorted] {
+        let fitness: Vec<Option<isize>> = population.iter().map(|c| c.fitness).collect();
+        assert_eq!(fitness, [Some(0), Some(3), Some(7), None]);
+    }
+}
+
+#[test]
+fn minimizing_selection_prefers_low_scores() {
+    let population = vec![scored("far", 9), scored("near", 1)];
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        assert_eq!(
+            select_tournament(&population, 8, Objective::Minimize, &mut rng),
+            1
+        );
+        // The worst candidate gets no share of the wheel.
+        assert_eq!(
+            select_roulette(&population, Objective::Minimize, &mut rng),
+            1
+        );
+    }
+}
+
+#[test]
+fn minimizing_edit_distance_finds_the_target() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .fitness_fn(|text, target| levenshtein(text, target) as isize)
+        .objective(Objective::Minimize)
+        .seed(1)
+        .build()
+        .unwrap();
+    let best: Vec<isize> = genetic_algorithm
+        .by_generation()
+        .take(500)
+        .map(|stats| stats.best_fitness)
+        .collect();
+    assert!(genetic_algorithm.is_solved());
+    assert_eq!(best.last(), Some(&0));
+    assert!(best.windows(2).all(|w| w[1] <= w[0]));
+}
+
+#[test]
+fn built_in_fitness_modes_cannot_be_minimized() {
+    for fitness_mode in [
+        FitnessMode::PositionMatch,
+        FitnessMode::Levenshtein,
+        FitnessMode::Weighted(vec![1; 5]),
+    ] {
+        let result = GeneticAlgorithmBuilder::new()
+            .target("hello")
+            .fitness_mode(fitness_mode)
+            .objective(Objective::Minimize)
+            .build();
+        assert!(matches!(result, Err(GaError::InvalidParameter(_))));
+    }
+}
+
+#[test]
+fn negative_fitness_is_a_score_not_unscored() {
+    let population = vec![
+        Candidate::new("unscored".to_string()),
+        scored("worse", -3),
+        scored("better", -1),
+    ];
+    let mut sorted = population;
+    reorder_by_fitness_fast(&mut sorted, Objective::Maximize);
+    let genomes: Vec<&str> = sorted.iter().map(|c| c.genome.as_str()).collect();
+    assert_eq!(genomes, ["better", "worse", "unscored"]);
+
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .fitness_fn(|text, target| -(levenshtein(text, target) as isize))
+        .seed(0)
+        .max_generations(500)
+        .build()
+        .unwrap();
+    let result = genetic_algorithm.solve();
+    assert!(result.is_solved());
+    assert_eq!(result.best.fitness, Some(0));
+}
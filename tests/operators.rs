@@ -0,0 +1,110 @@
+use ga::{
+    remove_unfit, reorder_by_fitness, reorder_by_fitness_fast, Candidate, FitnessMode, Objective,
+    StringContext,
+};
+
+mod common;
+
+use common::scored;
+
+fn fitness_of(population: &[Candidate]) -> Vec<isize> {
+    population.iter().filter_map(|c| c.fitness).collect()
+}
+
+fn genomes_of(population: &[Candidate]) -> Vec<&str> {
+    population.iter().map(|c| c.genome.as_str()).collect()
+}
+
+#[test]
+fn set_fitness_counts_matching_positions() {
+    let context = StringContext::new("hello");
+    for (genome, expected) in [
+        ("hello", 5),
+        ("jello", 4),
+        ("", 0),
+        ("he", 2),
+        ("hello world", 5),
+        ("olleh", 1),
+    ] {
+        let mut candidate = Candidate::new(genome.to_string());
+        assert_eq!(candidate.fitness, None);
+        candidate.set_fitness(&context);
+        assert_eq!(candidate.fitness, Some(expected), "{:?}", genome);
+    }
+}
+
+#[test]
+fn set_fitness_compares_characters_not_bytes() {
+    let context = StringContext::new("héllo");
+    let mut candidate = Candidate::new("héllo".to_string());
+    candidate.set_fitness(&context);
+    assert_eq!(candidate.fitness, Some(5));
+
+    let context = StringContext {
+        fitness_mode: FitnessMode::Levenshtein,
+        ..StringContext::new("héllo")
+    };
+    candidate.genome = "hello".to_string();
+    candidate.set_fitness(&context);
+    assert_eq!(candidate.fitness, Some(4));
+}
+
+#[test]
+fn reorder_handles_empty_and_single_populations() {
+    for reorder in [reorder_by_fitness, reorder_by_fitness_fast] {
+        let mut empty: Vec<Candidate> = Vec::new();
+        assert!(!reorder(&mut empty, Objective::Maximize));
+        let mut single = vec![scored("a", 3)];
+        assert!(!reorder(&mut single, Objective::Maximize));
+    }
+}
+
+#[test]
+fn reorder_reports_whether_anything_moved() {
+    for reorder in [reorder_by_fitness, reorder_by_fitness_fast] {
+        let mut population = vec![scored("a", 1), scored("b", 3), scored("c", 2)];
+        assert!(reorder(&mut population, Objective::Maximize));
+        assert_eq!(fitness_of(&population), [3, 2, 1]);
+        assert!(!reorder(&mut population, Objective::Maximize));
+    }
+}
+
+#[test]
+fn reorder_keeps_ties_in_order_and_unscored_last() {
+    for reorder in [reorder_by_fitness, reorder_by_fitness_fast] {
+        let mut population = vec![
+            Candidate::new("unscored".to_string()),
+            scored("first", 2),
+            scored("low", 0),
+            scored("second", 2),
+        ];
+        while reorder(&mut population, Objective::Maximize) {}
+        assert_eq!(
+            genomes_of(&population),
+            ["first", "second", "low", "unscored"]
+        );
+    }
+}
+
+#[test]
+fn remove_unfit_pops_the_tail_down_to_the_keep_count() {
+    let mut population = vec![scored("a", 3), scored("b", 2), scored("c", 1)];
+    assert!(remove_unfit(&mut population, 1));
+    assert_eq!(genomes_of(&population), ["a", "b"]);
+    assert!(remove_unfit(&mut population, 1));
+    assert!(!remove_unfit(&mut population, 1));
+    assert_eq!(genomes_of(&population), ["a"]);
+}
+
+#[test]
+fn remove_unfit_leaves_small_populations_alone() {
+    let mut population = vec![scored("a", 3), scored("b", 2)];
+    assert!(!remove_unfit(&mut population, 2));
+    assert!(!remove_unfit(&mut population, 5));
+    assert_eq!(population.len(), 2);
+
+    let mut population = vec![scored("a", 3)];
+    assert!(remove_unfit(&mut population, 0));
+    assert!(population.is_empty());
+    assert!(!remove_unfit(&mut population, 0));
+}
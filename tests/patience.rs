@@ -0,0 +1,29 @@
+use ga::{is_plateau, GeneticAlgorithmBuilder};
+
+#[test]
+fn plateau_needs_patience_generations_without_improvement() {
+    assert!(!is_plateau::<isize>(&[], 3));
+    assert!(!is_plateau(&[1, 1, 1], 3));
+    assert!(is_plateau(&[1, 1, 1, 1], 3));
+    assert!(!is_plateau(&[1, 1, 2, 2], 3));
+    // A dip and recovery to the old best is still no improvement.
+    assert!(is_plateau(&[1, 4, 2, 3, 4], 3));
+    assert!(!is_plateau(&[1, 4, 2, 3, 5], 3));
+}
+
+#[test]
+fn run_stops_when_stalled() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .mutation_prob(0.0)
+        .patience(4)
+        .seed(0)
+        .build()
+        .unwrap();
+    while !ga.is_solved() && !ga.is_stalled() {
+        ga.next();
+    }
+    assert!(ga.is_stalled());
+    let history = ga.best_fitness_history();
+    assert!(is_plateau(&history, 4));
+    assert!(!is_plateau(&history[..history.len() - 1], 4));
+}
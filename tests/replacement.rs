@@ -0,0 +1,91 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    replace_worst, CrossoverMode, GeneticAlgorithmBuilder, Objective, ReplacementMode,
+    SelectionStrategy, StringContext,
+};
+
+mod common;
+
+use common::scored_against;
+
+#[test]
+fn fitter_child_replaces_the_worst_and_keeps_order() {
+    let context = StringContext::new("hello");
+    let mut population = vec![
+        scored_against("hello", &context),
+        scored_against("hellx", &context),
+        scored_against("qqqqq", &context),
+    ];
+    let mut rng = StdRng::seed_from_u64(0);
+    // Sooner or later a child beats "qqqqq" and takes its place.
+    let before = population.len();
+    let mut attempts = 0;
+    while population.iter().any(|c| c.genome == "qqqqq") && attempts < 100 {
+        replace_worst(
+            &mut population,
+            0.0,
+            &SelectionStrategy::RandomPair,
+            Objective::Maximize,
+            CrossoverMode::default(),
+            2,
+            &context,
+            &mut rng,
+        );
+        attempts += 1;
+    }
+    assert_eq!(population.len(), before);
+    assert!(population.iter().all(|c| c.genome != "qqqqq"));
+    assert!(population.windows(2).all(|w| w[0].fitness >= w[1].fitness));
+}
+
+#[test]
+fn steady_state_keeps_size_and_never_loses_the_best() {
+    let mut ga = GeneticAlgorithmBuilder::new()
+        .target("hello steady state")
+        .replacement(ReplacementMode::SteadyState)
+        .population_size(30)
+        .seed(5)
+        .build()
+        .unwrap();
+    let history: Vec<isize> = ga
+        .by_generation()
+        .take(50)
+        .map(|stats| stats.best_fitness)
+        .collect();
+    assert_eq!(ga.population().len(), 30);
+    assert!(history.windows(2).all(|w| w[0] <= w[1]), "{:?}", history);
+}
+
+#[test]
+fn replacement_reports_indices_after_the_insert() {
+    let context = StringContext::new("hello");
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..50 {
+        let mut population = vec![
+            scored_against("hello", &context),
+            scored_against("hellx", &context),
+            scored_against("qqqqq", &context),
+        ];
+        let before = population.clone();
+        let replacement = replace_worst(
+            &mut population,
+            0.0,
+            &SelectionStrategy::RandomPair,
+            Objective::Maximize,
+            CrossoverMode::default(),
+            2,
+            &context,
+            &mut rng,
+        );
+        if let Some(child) = replacement.child {
+            assert!(population.iter().all(|c| c.genome != "qqqqq"));
+            assert!(population[child].fitness > before[2].fitness);
+        }
+        for &parent in &replacement.parents {
+            assert_ne!(Some(parent), replacement.child);
+            assert!(before.iter().any(|c| c.genome == population[parent].genome));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "serde")]
+
+use ga::{json_line, parse_json_line, Candidate, RecordedStep};
+
+mod common;
+
+use common::scored;
+
+#[test]
+fn recorded_lines_read_back_as_they_were_written() {
+    let population = vec![scored("hallo", 4), Candidate::new("xyzzy".to_string())];
+    let line = json_line(&population, "Breeding new candidates", &[0, 1]);
+    let step: RecordedStep = parse_json_line(&line).unwrap();
+    assert_eq!(step.state, "Breeding new candidates");
+    assert_eq!(step.focus, vec![0, 1]);
+    assert_eq!(step.population.len(), 2);
+    assert_eq!(step.population[0].genome, "hallo");
+    assert_eq!(step.population[0].fitness, Some(4));
+    assert_eq!(step.population[1].fitness, None);
+}
+
+#[test]
+fn malformed_lines_are_errors() {
+    assert!(parse_json_line::<String, isize>("{broken").is_err());
+    assert!(parse_json_line::<String, isize>("").is_err());
+    assert!(parse_json_line::<String, isize>(r#"{"state": "Sorting"}"#).is_err());
+}
@@ -0,0 +1,64 @@
+use ga::{GeneticAlgorithmBuilder, RestartPolicy};
+
+/// A deceptive problem: every `a` earns a point, so the population converges on all `a`s,
+/// while only the exact target earns full marks.
+fn deceptive() -> GeneticAlgorithmBuilder {
+    GeneticAlgorithmBuilder::new()
+        .target("bbbbbbbbbbbb")
+        .alphabet("ab")
+        .fitness_fn(|text, target| {
+            if text == target {
+                100
+            } else {
+                text.chars().filter(|&c| c == 'a').count() as isize
+            }
+        })
+        .mutation_prob(0.0)
+        .seed(6)
+}
+
+#[test]
+fn converged_populations_are_restarted() {
+    let restart = RestartPolicy {
+        min_diversity: 0.5,
+        patience: 3,
+    };
+    let mut genetic_algorithm = deceptive().restart(restart).build().unwrap();
+    let stats: Vec<_> = genetic_algorithm.by_generation().take(60).collect();
+    assert!(genetic_algorithm.restarts() > 0);
+    assert_eq!(stats.last().unwrap().restarts, genetic_algorithm.restarts());
+    assert!(stats.windows(2).all(|w| w[0].restarts <= w[1].restarts));
+    assert_eq!(genetic_algorithm.population().len(), ga::POPULATION_SIZE);
+}
+
+#[test]
+fn without_a_policy_the_population_stays_converged() {
+    let mut genetic_algorithm = deceptive().build().unwrap();
+    let stats: Vec<_> = genetic_algorithm.by_generation().take(60).collect();
+    assert_eq!(genetic_algorithm.restarts(), 0);
+    assert!(stats.iter().all(|s| s.restarts == 0));
+}
+
+#[test]
+fn restart_policy_parses_diversity_and_patience() {
+    assert_eq!(
+        "1.5:20".parse(),
+        Ok(RestartPolicy {
+            min_diversity: 1.5,
+            patience: 20
+        })
+    );
+    assert_eq!(
+        "1.5:20".parse::<RestartPolicy>().unwrap().to_string(),
+        "1.5:20"
+    );
+    assert!("1.5".parse::<RestartPolicy>().is_err());
+    assert!("-1:20".parse::<RestartPolicy>().is_err());
+    assert!(deceptive()
+        .restart(RestartPolicy {
+            min_diversity: 1.0,
+            patience: 0
+        })
+        .build()
+        .is_err());
+}
@@ -0,0 +1,77 @@
+use std::ops::ControlFlow;
+
+use rand::Rng;
+
+use ga::{Candidate, Crossover, CrossoverMode, Distance, Fitness, GeneticAlgorithm, Genome};
+
+/// Eight bits scored by how many are set, in `u8` rather than the usual `isize`.
+#[derive(Clone, Debug, PartialEq)]
+struct Bits(u8);
+
+impl Genome for Bits {
+    type Context = ();
+
+    fn random(_context: &(), rng: &mut impl Rng) -> Self {
+        Bits(rng.gen())
+    }
+}
+
+impl Crossover for Bits {
+    fn crossover(
+        &self,
+        other: &Self,
+        _mode: CrossoverMode,
+        mutation_prob: f64,
+        _context: &(),
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mask: u8 = rng.gen();
+        let mut child = (self.0 & mask) | (other.0 & !mask);
+        for bit in 0..8 {
+            if rng.gen_bool(mutation_prob) {
+                child ^= 1 << bit;
+            }
+        }
+        Bits(child)
+    }
+}
+
+impl Fitness for Bits {
+    type Score = u8;
+
+    fn fitness(&self, _context: &()) -> u8 {
+        self.0.count_ones() as u8
+    }
+
+    fn max_fitness(_context: &()) -> u8 {
+        8
+    }
+}
+
+impl Distance for Bits {
+    fn distance(&self, other: &Self) -> usize {
+        (self.0 ^ other.0).count_ones() as usize
+    }
+}
+
+#[test]
+fn genomes_can_score_in_their_own_type() {
+    let mut genetic_algorithm = GeneticAlgorithm::new(
+        (),
+        4,
+        16,
+        0.1,
+        Some(3),
+        |_: &Vec<Candidate<Bits, u8>>, _: &str, _: &[usize]| ControlFlow::Continue(()),
+    )
+    .with_hall_of_fame(3)
+    .with_max_generations(200);
+    let result = genetic_algorithm.solve();
+    assert!(result.is_solved());
+    assert_eq!(result.best.genome, Bits(u8::MAX));
+    assert_eq!(result.best.fitness, Some(8u8));
+    assert_eq!(genetic_algorithm.hall_of_fame()[0].fitness, Some(8));
+    let stats = genetic_algorithm.stats().last().unwrap();
+    assert_eq!(stats.best_fitness, 8);
+    assert!(stats.mean_fitness <= 8.0);
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{FitnessMode, GeneticAlgorithmBuilder, Genome, MutationOp, StringContext};
+
+#[test]
+fn seed_candidates_come_first_and_are_fitted_to_the_target() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .population_size(10)
+        .seed_candidates(["hello world", "he"])
+        .seed(0)
+        .build()
+        .unwrap();
+    let population = genetic_algorithm.population();
+    assert_eq!(population.len(), 2);
+    assert_eq!(population[0].genome, "hello");
+    assert!(population[1].genome.starts_with("he"));
+    assert_eq!(population[1].genome.chars().count(), 5);
+    // Seeding fills up the rest of the population with random candidates.
+    genetic_algorithm.by_generation().next();
+    assert!(genetic_algorithm.is_solved());
+}
+
+#[test]
+fn seed_candidates_are_validated() {
+    let builder = || {
+        GeneticAlgorithmBuilder::new()
+            .target("abc")
+            .population_size(6)
+    };
+    assert!(builder().seed_candidates(["ABC"]).build().is_err());
+    assert!(builder().seed_candidates(vec!["abc"; 7]).build().is_err());
+    assert!(builder().seed_candidates(vec!["abc"; 6]).build().is_ok());
+}
+
+#[test]
+fn fit_to_target_pads_from_the_alphabet() {
+    let context = StringContext::new("abcdef").with_alphabet("xyz");
+    let mut rng = StdRng::seed_from_u64(0);
+    let fitted = context.fit_to_target("ab", &mut rng);
+    assert!(fitted.starts_with("ab"));
+    assert_eq!(fitted.chars().count(), 6);
+    assert!(fitted[2..].chars().all(|c| "xyz".contains(c)));
+    assert_eq!(context.fit_to_target("abcdefgh", &mut rng), "abcdef");
+}
+
+#[test]
+fn random_candidates_are_drawn_from_the_initial_length_range() {
+    let context = StringContext::new("hello").with_init_len(2, 8);
+    let mut rng = StdRng::seed_from_u64(0);
+    let lengths: Vec<usize> = (0..200)
+        .map(|_| String::random(&context, &mut rng).chars().count())
+        .collect();
+    assert!(lengths.iter().all(|len| (2..=8).contains(len)));
+    assert!(lengths.contains(&2) && lengths.contains(&8));
+
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .fitness_mode(FitnessMode::Levenshtein)
+        .mutation_op(MutationOp::InsertDelete)
+        .init_len_min(1)
+        .init_len_max(10)
+        .seed(0)
+        .build()
+        .unwrap();
+    genetic_algorithm.by_generation().next();
+    let lengths: BTreeSet<usize> = genetic_algorithm
+        .population()
+        .iter()
+        .map(|c| c.genome.chars().count())
+        .collect();
+    assert!(lengths.len() > 1, "{:?}", lengths);
+}
+
+#[test]
+fn initial_length_range_is_validated() {
+    let builder = || GeneticAlgorithmBuilder::new().target("hello");
+    assert!(builder().init_len_min(6).init_len_max(3).build().is_err());
+    assert!(builder().init_len_min(0).build().is_err());
+    // A missing bound is the target's length.
+    assert!(builder().init_len_min(7).build().is_err());
+    assert!(builder().init_len_max(7).build().is_ok());
+    let context = builder().init_len_min(3).build().unwrap().context().clone();
+    assert_eq!(context.init_len, Some((3, 5)));
+
+    let context = || StringContext::new("hello");
+    assert!(context().with_init_len(0, 3).validate().is_err());
+    assert!(context().with_init_len(4, 2).validate().is_err());
+    assert!(context().with_init_len(2, 2).validate().is_ok());
+}
@@ -0,0 +1,124 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ga::{
+    breed_new, select_rank, Candidate, CrossoverMode, Objective, SelectionStrategy, StringContext,
+};
+
+mod common;
+
+use common::scored;
+
+#[test]
+fn two_survivors_are_always_distinct_parents() {
+    let population = vec![scored("hello", 5), scored("jello", 0)];
+    let mut rng = StdRng::seed_from_u64(0);
+    for strategy in [
+        SelectionStrategy::RandomPair,
+        SelectionStrategy::Tournament { k: 3 },
+        SelectionStrategy::RouletteWheel,
+        SelectionStrategy::Rank { pressure: 2.0 },
+    ] {
+        for _ in 0..1000 {
+            let (i, j) = strategy.select_parents(&population, Objective::Maximize, &mut rng);
+            assert_ne!(i, j, "{:?} picked the same parent twice", strategy);
+        }
+    }
+}
+
+#[test]
+fn single_survivor_breeds_asexually() {
+    let mut population = vec![scored("hello", 5)];
+    let context = StringContext::new("hello");
+    let mut rng = StdRng::seed_from_u64(0);
+    while breed_new(
+        &mut population,
+        10,
+        0.0,
+        &SelectionStrategy::RouletteWheel,
+        Objective::Maximize,
+        CrossoverMode::default(),
+        2,
+        &context,
+        &mut rng,
+    )
+    .is_some()
+    {}
+    assert_eq!(population.len(), 10);
+    assert!(population.iter().all(|c| c.genome == "hello"));
+}
+
+#[test]
+fn rank_selection_ignores_how_far_apart_scores_are() {
+    let mut rng = StdRng::seed_from_u64(0);
+    // Roulette would almost always pick the dominant candidate; ranking only cares about order.
+    let population = vec![scored("best", 1000), scored("mid", 2), scored("worst", 1)];
+    let mut counts = [0i32; 3];
+    for _ in 0..30_000 {
+        counts[select_rank(&population, 1.5, Objective::Maximize, &mut rng)] += 1;
+    }
+    // Expected shares are 1.5/3, 1/3 and 0.5/3.
+    for (count, expected) in counts.iter().zip([15_000, 10_000, 5_000]) {
+        assert!((count - expected).abs() < 600, "{:?}", counts);
+    }
+}
+
+#[test]
+fn rank_pressure_must_lie_between_one_and_two() {
+    assert_eq!(
+        "rank:1.5".parse(),
+        Ok(SelectionStrategy::Rank { pressure: 1.5 })
+    );
+    assert!("rank:2.5".parse::<SelectionStrategy>().is_err());
+    assert!("rank:0.5".parse::<SelectionStrategy>().is_err());
+    assert_eq!(
+        SelectionStrategy::Rank { pressure: 1.5 }.to_string(),
+        "rank:1.5"
+    );
+}
+
+#[test]
+fn breed_new_selects_as_many_parents_as_asked() {
+    let population: Vec<Candidate> = ["aaaaa", "bbbbb", "ccccc", "ddddd"]
+        .iter()
+        .map(|genome| scored(genome, 1))
+        .collect();
+    let context = StringContext::new("hello");
+    let mut rng = StdRng::seed_from_u64(0);
+    for (num_parents, crossover) in [
+        (1, CrossoverMode::default()),
+        (2, CrossoverMode::Asexual),
+        (3, CrossoverMode::default()),
+    ] {
+        let mut bred = population.clone();
+        let parents = breed_new(
+            &mut bred,
+            5,
+            0.0,
+            &SelectionStrategy::Tournament { k: 2 },
+            Objective::Maximize,
+            crossover,
+            num_parents,
+            &context,
+            &mut rng,
+        )
+        .unwrap();
+        // Asexual breeding takes one parent whatever the count.
+        let expected = if crossover == CrossoverMode::Asexual {
+            1
+        } else {
+            num_parents
+        };
+        assert_eq!(parents.len(), expected);
+        if expected >= 2 {
+            assert_ne!(parents[0], parents[1]);
+        }
+        let child = &bred[4].genome;
+        assert!(child
+            .chars()
+            .all(|c| parents.iter().any(|&p| bred[p].genome.contains(c))));
+        if expected == 1 {
+            assert_eq!(*child, bred[parents[0]].genome);
+        }
+    }
+}
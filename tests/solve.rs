@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use ga::{generations_per_second, GeneticAlgorithmBuilder, Outcome};
+
+#[test]
+fn solve_runs_until_the_target_is_reached() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new().seed(1).build().unwrap();
+    let result = genetic_algorithm.solve();
+    assert_eq!(result.outcome, Outcome::Reached);
+    assert!(result.is_solved());
+    assert_eq!(result.best.genome, "hello world");
+    assert_eq!(result.best.fitness, Some(11));
+    assert_eq!(result.generations, genetic_algorithm.generation());
+}
+
+#[test]
+fn solve_respects_the_generation_limit() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("a target far too long to solve in three generations")
+        .max_generations(3)
+        .seed(1)
+        .build()
+        .unwrap();
+    let result = genetic_algorithm.solve();
+    assert_eq!(result.outcome, Outcome::GenerationLimit);
+    assert!(!result.is_solved());
+    assert_eq!(result.generations, 3);
+    assert!(result.best.fitness.is_some());
+    assert!(genetic_algorithm.is_finished());
+    assert!(genetic_algorithm.next().is_none());
+}
+
+#[test]
+fn solve_reports_a_stalled_run() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .mutation_prob(0.0)
+        .patience(5)
+        .seed(1)
+        .build()
+        .unwrap();
+    assert_eq!(genetic_algorithm.solve().outcome, Outcome::Stalled);
+}
+
+#[test]
+fn zero_generations_are_rejected() {
+    assert!(GeneticAlgorithmBuilder::new()
+        .max_generations(0)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn solve_reports_how_long_the_run_took() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new().seed(1).build().unwrap();
+    assert_eq!(genetic_algorithm.elapsed(), Duration::ZERO);
+    let result = genetic_algorithm.solve();
+    assert!(result.elapsed > Duration::ZERO);
+    assert_eq!(result.elapsed, genetic_algorithm.elapsed());
+    assert_eq!(
+        result.generations_per_second(),
+        result.generations as f64 / result.elapsed.as_secs_f64()
+    );
+    // Each generation's statistics record the time spent up to it.
+    let stats = genetic_algorithm.stats();
+    assert!(stats.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+    assert!(stats.last().unwrap().elapsed <= result.elapsed);
+}
+
+#[test]
+fn no_time_means_no_throughput() {
+    assert_eq!(generations_per_second(10, Duration::ZERO), 0.0);
+    assert_eq!(generations_per_second(10, Duration::from_secs(4)), 2.5);
+}
+
+#[test]
+fn three_parent_runs_solve() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .target("hello")
+        .num_parents(3)
+        .seed(2)
+        .max_generations(2000)
+        .build()
+        .unwrap();
+    genetic_algorithm.solve();
+    assert!(genetic_algorithm.is_solved());
+}
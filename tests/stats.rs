@@ -0,0 +1,17 @@
+use ga::{Candidate, GenerationStats, Objective, CSV_HEADER};
+
+#[test]
+fn csv_row_matches_header() {
+    let population: Vec<Candidate> = [("hxllo", 4), ("hxlxo", 3)]
+        .iter()
+        .map(|&(genome, fitness)| {
+            let mut candidate = Candidate::new(genome.to_string());
+            candidate.fitness = Some(fitness);
+            candidate
+        })
+        .collect();
+    let stats = GenerationStats::from_population(7, &population, Objective::Maximize);
+    let row = stats.csv_row(5);
+    assert_eq!(row, "7,4,3.5,5");
+    assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+}
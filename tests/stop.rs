@@ -0,0 +1,57 @@
+use std::cell::Cell;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use ga::{GeneticAlgorithmBuilder, Outcome, STATE};
+
+#[test]
+fn callback_stops_the_run_once_good_enough() {
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .seed(1)
+        .callback(|population, _, _| {
+            if population.iter().any(|c| c.fitness >= Some(8)) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .build()
+        .unwrap();
+    let result = genetic_algorithm.solve();
+    assert_eq!(result.outcome, Outcome::Stopped);
+    assert!(result.best.fitness >= Some(8));
+    assert!(result.best.fitness < Some(11));
+    assert!(genetic_algorithm.is_stopped());
+    assert!(genetic_algorithm.is_finished());
+    assert!(genetic_algorithm.next().is_none());
+}
+
+#[test]
+fn stopping_ends_the_step_and_generation_iterators() {
+    let calls = Rc::new(Cell::new(0));
+    let bred = Rc::new(Cell::new(false));
+    let (counter, seen_breeding) = (Rc::clone(&calls), Rc::clone(&bred));
+    let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+        .seed(2)
+        .callback(move |_, label, _| {
+            counter.set(counter.get() + 1);
+            // Stop while scoring the second generation.
+            if label == STATE::ComputeFitness.description() && seen_breeding.get() {
+                return ControlFlow::Break(());
+            }
+            seen_breeding.set(label == STATE::BreedNew.description() || seen_breeding.get());
+            ControlFlow::Continue(())
+        })
+        .build()
+        .unwrap();
+    let generations: Vec<usize> = genetic_algorithm
+        .by_generation()
+        .map(|stats| stats.generation)
+        .collect();
+    // The second generation was never fully scored, so only the first one is reported.
+    assert_eq!(generations, vec![0]);
+    assert_eq!(genetic_algorithm.generation(), 1);
+    let steps = calls.get();
+    assert!(genetic_algorithm.next().is_none());
+    assert_eq!(calls.get(), steps);
+}
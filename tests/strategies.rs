@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+use ga::{
+    Cooling, CrossoverMode, CullStrategy, FitnessMode, MutationOp, Objective, ReplacementMode,
+    SelectionStrategy, Strategy, TieBreak,
+};
+
+/// Every listed variant is described and parses back from how it is printed.
+fn check<T>()
+where
+    T: Strategy + FromStr + PartialEq + std::fmt::Debug,
+    T::Err: std::fmt::Debug,
+{
+    let variants = T::variants();
+    assert!(!variants.is_empty());
+    for variant in &variants {
+        assert!(!variant.description().is_empty());
+        assert_eq!(&variant.to_string().parse::<T>().unwrap(), variant);
+    }
+}
+
+#[test]
+fn every_strategy_variant_is_listed_and_parses() {
+    check::<SelectionStrategy>();
+    check::<CrossoverMode>();
+    check::<MutationOp>();
+    check::<Cooling>();
+    check::<ReplacementMode>();
+    check::<CullStrategy>();
+    check::<TieBreak>();
+    check::<FitnessMode>();
+    check::<Objective>();
+    assert_eq!(SelectionStrategy::variants().len(), 4);
+    assert_eq!(MutationOp::variants().len(), 5);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+
+use std::cell::RefCell;
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::ops::ControlFlow;
+use std::thread;
+
+use ga::{write_frame, Candidate, FrameStream, GeneticAlgorithmBuilder, OnDisconnect};
+
+/// A client that has gone away.
+struct Closed;
+
+impl Write for Closed {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn read_frames(mut reader: impl Read) -> Vec<serde_json::Value> {
+    let mut frames = Vec::new();
+    let mut len = [0; 4];
+    while reader.read_exact(&mut len).is_ok() {
+        let mut frame = vec![0; u32::from_be_bytes(len) as usize];
+        reader.read_exact(&mut frame).unwrap();
+        frames.push(serde_json::from_slice(&frame).unwrap());
+    }
+    frames
+}
+
+#[test]
+fn frames_are_prefixed_with_their_length() {
+    let mut buf = Vec::new();
+    write_frame(&mut buf, "{}").unwrap();
+    write_frame(&mut buf, "[1]").unwrap();
+    assert_eq!(buf, b"\0\0\0\x02{}\0\0\0\x03[1]");
+}
+
+#[test]
+fn every_step_reaches_the_client() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let client = thread::spawn(move || read_frames(listener.accept().unwrap().0));
+
+    let stream = RefCell::new(FrameStream::connect(addr, OnDisconnect::Continue).unwrap());
+    let mut steps = 0;
+    {
+        let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+            .target("hi")
+            .seed(1)
+            .callback(
+                |population: &Vec<Candidate>, label: &str, focus: &[usize]| {
+                    stream.borrow_mut().send(population, label, focus)
+                },
+            )
+            .build()
+            .unwrap();
+        while !genetic_algorithm.is_finished() {
+            steps += genetic_algorithm.next().is_some() as usize;
+        }
+    }
+    drop(stream);
+
+    let frames = client.join().unwrap();
+    assert_eq!(frames.len(), steps);
+    let last = frames.last().unwrap();
+    assert!(last["population"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|candidate| candidate["genome"] == "hi"));
+}
+
+#[test]
+fn a_lost_client_stops_the_run_only_if_asked_to() {
+    let population: Vec<Candidate> = vec![Candidate::new("hi".to_string())];
+    let mut stream = FrameStream::new(Closed, OnDisconnect::Continue);
+    assert_eq!(stream.send(&population, "", &[]), ControlFlow::Continue(()));
+    assert!(!stream.is_connected());
+
+    let mut stream = FrameStream::new(Closed, OnDisconnect::Stop);
+    assert_eq!(stream.send(&population, "", &[]), ControlFlow::Break(()));
+    assert_eq!(stream.send(&population, "", &[]), ControlFlow::Break(()));
+}
+
+#[test]
+fn disconnect_policies_parse_as_they_print() {
+    for policy in [OnDisconnect::Continue, OnDisconnect::Stop] {
+        assert_eq!(policy.to_string().parse(), Ok(policy));
+    }
+    assert!("ignore".parse::<OnDisconnect>().is_err());
+}
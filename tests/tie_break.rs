@@ -0,0 +1,102 @@
+use ga::{
+    reorder_by_fitness_fast_with, reorder_by_fitness_with, Candidate, GeneticAlgorithmBuilder,
+    IntGenome, Objective, TieBreak, STATE,
+};
+
+mod common;
+
+use common::{scored, scored_genome};
+
+fn population() -> Vec<Candidate> {
+    vec![
+        scored("cab", 2),
+        scored("low", 0),
+        scored("abc", 2),
+        scored("abd", 2),
+        scored("best", 3),
+        scored("bca", 2),
+    ]
+}
+
+fn sorted(tie_break: TieBreak) -> [Vec<String>; 2] {
+    let reorders = [reorder_by_fitness_with, reorder_by_fitness_fast_with];
+    reorders.map(|reorder| {
+        let mut population = population();
+        while reorder(&mut population, Objective::Maximize, tie_break) {}
+        population.into_iter().map(|c| c.genome).collect()
+    })
+}
+
+#[test]
+fn stable_ties_keep_their_order() {
+    for order in sorted(TieBreak::Stable) {
+        assert_eq!(order, ["best", "cab", "abc", "abd", "bca", "low"]);
+    }
+}
+
+#[test]
+fn lexical_ties_are_ordered_by_genome() {
+    for order in sorted(TieBreak::Lexical) {
+        assert_eq!(order, ["best", "abc", "abd", "bca", "cab", "low"]);
+    }
+    let mut population = vec![
+        scored_genome(IntGenome(vec![2, 0]), 1),
+        scored_genome(IntGenome(vec![1, 5]), 1),
+    ];
+    while reorder_by_fitness_with(&mut population, Objective::Minimize, TieBreak::Lexical) {}
+    assert_eq!(population[0].genome, IntGenome(vec![1, 5]));
+}
+
+#[test]
+fn diverse_ties_put_the_outlier_first() {
+    // "abd" is closest to the others, "cab" and "bca" are equally far and keep their order.
+    for order in sorted(TieBreak::Diverse) {
+        assert_eq!(order, ["best", "cab", "bca", "abc", "abd", "low"]);
+    }
+}
+
+#[test]
+fn sorted_populations_do_not_move() {
+    for tie_break in [TieBreak::Stable, TieBreak::Lexical, TieBreak::Diverse] {
+        let mut population = population();
+        while reorder_by_fitness_fast_with(&mut population, Objective::Maximize, tie_break) {}
+        assert!(!reorder_by_fitness_with(
+            &mut population,
+            Objective::Maximize,
+            tie_break
+        ));
+        assert!(!reorder_by_fitness_fast_with(
+            &mut population,
+            Objective::Maximize,
+            tie_break
+        ));
+    }
+}
+
+#[test]
+fn tie_breaks_parse_as_they_print() {
+    for tie_break in [TieBreak::Stable, TieBreak::Lexical, TieBreak::Diverse] {
+        assert_eq!(tie_break.to_string().parse(), Ok(tie_break));
+    }
+    assert!("random".parse::<TieBreak>().is_err());
+}
+
+#[test]
+fn swapping_equally_fit_candidates_is_focused() {
+    for tie_break in [TieBreak::Lexical, TieBreak::Diverse] {
+        let mut genetic_algorithm = GeneticAlgorithmBuilder::new()
+            .population_size(12)
+            .num_fit_to_keep(4)
+            .tie_break(tie_break)
+            .seed(5)
+            .max_generations(4)
+            .build()
+            .unwrap();
+        while !genetic_algorithm.is_finished() {
+            let sorting = matches!(genetic_algorithm.state(), STATE::Reorder);
+            if genetic_algorithm.next().is_some() && sorting {
+                assert!(!genetic_algorithm.focus().is_empty());
+            }
+        }
+    }
+}
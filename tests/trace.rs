@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use ga::{Trace, STATE};
+
+#[test]
+fn steps_within_a_phase_add_up_to_one_entry() {
+    let mut trace = Trace::new();
+    let step = Duration::from_millis(1);
+    assert!(trace
+        .record(0, &STATE::Replace(0), &STATE::Replace(1), step)
+        .is_none());
+    let line = trace
+        .record(0, &STATE::Replace(1), &STATE::Init, step)
+        .unwrap();
+    assert!(line.contains("generation 0: Replacing the worst candidate -> Seeding"));
+    assert!(line.ends_with("(2.000ms)"));
+    assert_eq!(trace.totals(), &[("breeding", 2 * step)]);
+}
+
+#[test]
+fn breakdown_lists_the_largest_share_first() {
+    let mut trace = Trace::new();
+    trace.record(
+        0,
+        &STATE::Reorder,
+        &STATE::RemoveUnfit,
+        Duration::from_millis(25),
+    );
+    trace.record(0, &STATE::BreedNew, &STATE::Init, Duration::from_millis(35));
+    trace.record(
+        1,
+        &STATE::ComputeFitness,
+        &STATE::Reorder,
+        Duration::from_millis(40),
+    );
+    assert_eq!(
+        trace.breakdown(),
+        "fitness: 40%, breeding: 35%, sorting: 25%"
+    );
+}